@@ -9,17 +9,22 @@ pub fn decompress(data: &[u8], compression: &Compression) -> Result<Vec<u8>, Err
         Compression::GZip => libflate::gzip::Decoder::new(data)?.read_to_end(&mut decompressed),
         Compression::Zlib => compress::zlib::Decoder::new(data).read_to_end(&mut decompressed),
         Compression::Uncompressed => return Ok(data.to_vec()),
-        Compression::Other => unimplemented!("Only GZip, ZLib and Uncompressed are supported"),
+        Compression::Other(compression) => return Err(Error::UnknownCompression(*compression)),
     }?;
     Ok(decompressed)
 }
 
+/// The compression of a chunk as stored in the byte in front of the chunk data.
 #[derive(Debug, PartialEq, Eq)]
 pub enum Compression {
-    GZip = 1,
-    Zlib = 2,
-    Uncompressed = 3,
-    Other,
+    /// 1
+    GZip,
+    /// 2
+    Zlib,
+    /// 3
+    Uncompressed,
+    /// Any compression type not supported by Minecraft
+    Other(u8),
 }
 
 impl From<u8> for Compression {
@@ -28,7 +33,7 @@ impl From<u8> for Compression {
             1 => Self::GZip,
             2 => Self::Zlib,
             3 => Self::Uncompressed,
-            _ => Self::Other,
+            other => Self::Other(other),
         }
     }
 }
@@ -37,12 +42,19 @@ impl From<u8> for Compression {
 pub enum Error {
     #[error(transparent)]
     Io(#[from] std::io::Error),
+    /// The compression type is not one of GZip, Zlib or Uncompressed.
+    #[error(
+        "Unknown compression type {0}. Only GZip (1), Zlib (2) and Uncompressed (3) are supported"
+    )]
+    UnknownCompression(u8),
 }
 
 impl PartialEq for Error {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
             (Self::Io(a), Self::Io(b)) => a.kind() == b.kind() && a.to_string() == b.to_string(),
+            (Self::UnknownCompression(a), Self::UnknownCompression(b)) => a == b,
+            _ => false,
         }
     }
 }
@@ -57,17 +69,19 @@ mod tests {
     #[test_case(1, Compression::GZip; "GZip")]
     #[test_case(2, Compression::Zlib; "ZLib")]
     #[test_case(3, Compression::Uncompressed; "Uncompressed")]
-    #[test_case(4, Compression::Other; "Other_4")]
-    #[test_case(5, Compression::Other; "Other_5")]
-    #[test_case(0, Compression::Other; "Other_0")]
+    #[test_case(4, Compression::Other(4); "Other_4")]
+    #[test_case(5, Compression::Other(5); "Other_5")]
+    #[test_case(0, Compression::Other(0); "Other_0")]
     fn compression_from(value: u8, expected: Compression) {
         assert_eq!(expected, value.into())
     }
 
     #[test]
-    #[should_panic(expected = "Only GZip, ZLib and Uncompressed are supported")]
-    fn compression_other_unimplemented() {
-        super::decompress(&[], &Compression::Other).unwrap();
+    fn compression_other_unsupported() {
+        assert_eq!(
+            super::decompress(&[], &Compression::Other(4)),
+            Err(super::Error::UnknownCompression(4))
+        );
     }
 
     #[test]
@@ -0,0 +1,94 @@
+//! Conversions between block, chunk and region coordinates.
+//! Conversions to a larger unit round towards negative infinity, conversions to a smaller unit return the origin.
+
+/// Number of blocks along one axis of a chunk.
+pub const BLOCKS_PER_CHUNK: i32 = 16;
+/// Number of chunks along one axis of a region.
+pub const CHUNKS_PER_REGION: i32 = 32;
+
+/// The position of a block in the world.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BlockPos {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+/// Get the chunk coordinate containing the given block coordinate.
+pub fn block_to_chunk(block: i32) -> i32 {
+    block >> 4
+}
+
+/// Get the region coordinate containing the given block coordinate.
+pub fn block_to_region(block: i32) -> i32 {
+    chunk_to_region(block_to_chunk(block))
+}
+
+/// Get the region coordinate containing the given chunk coordinate.
+pub fn chunk_to_region(chunk: i32) -> i32 {
+    chunk >> 5
+}
+
+/// Get the block coordinate of the origin of the given chunk coordinate.
+pub fn chunk_to_block(chunk: i32) -> i32 {
+    chunk * BLOCKS_PER_CHUNK
+}
+
+/// Get the chunk coordinate of the origin of the given region coordinate.
+pub fn region_to_chunk(region: i32) -> i32 {
+    region * CHUNKS_PER_REGION
+}
+
+/// Get the block coordinate of the origin of the given region coordinate.
+pub fn region_to_block(region: i32) -> i32 {
+    chunk_to_block(region_to_chunk(region))
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    #[test_case(0 => 0; "Origin")]
+    #[test_case(15 => 0; "Last block of first chunk")]
+    #[test_case(16 => 1; "First block of second chunk")]
+    #[test_case(-1 => -1; "Negative")]
+    #[test_case(-16 => -1; "First block of negative chunk")]
+    #[test_case(-17 => -2; "Last block of second negative chunk")]
+    fn test_block_to_chunk(block: i32) -> i32 {
+        super::block_to_chunk(block)
+    }
+
+    #[test_case(0 => 0; "Origin")]
+    #[test_case(511 => 0; "Last block of first region")]
+    #[test_case(512 => 1; "First block of second region")]
+    #[test_case(-1 => -1; "Negative")]
+    #[test_case(-513 => -2; "Last block of second negative region")]
+    fn test_block_to_region(block: i32) -> i32 {
+        super::block_to_region(block)
+    }
+
+    #[test_case(31 => 0; "Last chunk of first region")]
+    #[test_case(32 => 1; "First chunk of second region")]
+    #[test_case(-1 => -1; "Negative")]
+    fn test_chunk_to_region(chunk: i32) -> i32 {
+        super::chunk_to_region(chunk)
+    }
+
+    #[test_case(1 => 16; "Positive")]
+    #[test_case(-1 => -16; "Negative")]
+    fn test_chunk_to_block(chunk: i32) -> i32 {
+        super::chunk_to_block(chunk)
+    }
+
+    #[test_case(1 => 32; "Positive")]
+    #[test_case(-1 => -32; "Negative")]
+    fn test_region_to_chunk(region: i32) -> i32 {
+        super::region_to_chunk(region)
+    }
+
+    #[test_case(1 => 512; "Positive")]
+    #[test_case(-1 => -512; "Negative")]
+    fn test_region_to_block(region: i32) -> i32 {
+        super::region_to_block(region)
+    }
+}
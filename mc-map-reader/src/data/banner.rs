@@ -0,0 +1,315 @@
+//! Designs of banners and shields.
+
+use std::collections::HashMap;
+
+use super::{color::DyeColor, item::Item};
+use crate::nbt::Tag;
+
+/// The maximum number of patterns a loom can apply to a banner.
+pub const MAX_LOOM_PATTERNS: usize = 6;
+
+/// The design of a banner. This is the base color and all patterns painted on top of it.
+/// [Minecraft Wiki](https://minecraft.fandom.com/wiki/Banner#Block_data)
+#[derive(Debug, Clone, PartialEq)]
+pub struct BannerDesign {
+    /// The color of the banner itself. Shields without a base color do not have one.
+    pub base_color: Option<DyeColor>,
+    /// Patterns in the order they are painted. The first pattern is the lowest layer.
+    pub patterns: Vec<BannerLayer>,
+}
+
+/// A single pattern painted on a banner.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BannerLayer {
+    /// Pattern id. Older versions use short codes like `bs`, newer versions namespaced ids like `minecraft:stripe_bottom`.
+    pub pattern: String,
+    pub color: DyeColor,
+}
+
+impl BannerDesign {
+    /// Get the base color of a banner from its block or item id.
+    pub fn base_color_from_id(id: &str) -> Option<DyeColor> {
+        let id = id.strip_prefix("minecraft:")?;
+        id.strip_suffix("_wall_banner")
+            .or_else(|| id.strip_suffix("_banner"))
+            .and_then(DyeColor::from_name)
+    }
+
+    /// Returns `true` if the banner has more patterns than a loom can apply.
+    /// Such banners were created with commands or by editing the NBT data.
+    pub fn exceeds_loom_limit(&self) -> bool {
+        self.patterns.len() > MAX_LOOM_PATTERNS
+    }
+
+    /// Get the design of a banner block entity.
+    #[cfg(feature = "block_entity")]
+    pub fn from_block_entity(block_entity: &super::block_entity::BlockEntity) -> Option<Self> {
+        use super::block_entity::BlockEntityType;
+        let BlockEntityType::Banner(banner) = &block_entity.entity_type else {
+            return None;
+        };
+        let patterns = banner
+            .patterns
+            .iter()
+            .flat_map(|patterns| patterns.iter())
+            .filter_map(|pattern| {
+                Some(BannerLayer {
+                    pattern: pattern.pattern.clone(),
+                    color: DyeColor::from_id(pattern.color)?,
+                })
+            })
+            .collect();
+        Some(Self {
+            base_color: Self::base_color_from_id(&block_entity.id),
+            patterns,
+        })
+    }
+
+    /// Get the design of a banner or shield item.
+    /// The `minecraft:banner_patterns` component takes precedence over the legacy `BlockEntityTag`.
+    pub fn from_item(item: &Item) -> Option<Self> {
+        if !item.id.ends_with("_banner") && item.id != "minecraft:shield" {
+            return None;
+        }
+        let block_entity_tag = item
+            .tag
+            .as_ref()
+            .and_then(|tag| tag.get("BlockEntityTag"))
+            .and_then(|block_entity_tag| block_entity_tag.clone().get_as_map().ok());
+        let component = |key: &str| {
+            item.components
+                .as_ref()
+                .and_then(|components| components.get(key))
+        };
+        let legacy = |key: &str| {
+            block_entity_tag
+                .as_ref()
+                .and_then(|block_entity_tag| block_entity_tag.get(key))
+        };
+
+        let base_color = Self::base_color_from_id(&item.id)
+            .or_else(|| component("minecraft:base_color").and_then(color_from_tag))
+            .or_else(|| legacy("Base").and_then(color_from_tag));
+        let patterns = component("minecraft:banner_patterns")
+            .or_else(|| legacy("Patterns"))
+            .and_then(|patterns| patterns.clone().get_as_list().ok())
+            .map(|patterns| patterns.iter().filter_map(layer_from_tag).collect())
+            .unwrap_or_default();
+        Some(Self {
+            base_color,
+            patterns,
+        })
+    }
+}
+
+/// Parse a pattern in either the legacy `{Pattern, Color}` or the newer `{pattern, color}` format.
+fn layer_from_tag(tag: &Tag) -> Option<BannerLayer> {
+    let Tag::Compound(layer) = tag else {
+        return None;
+    };
+    let pattern = match layer.get("Pattern").or_else(|| layer.get("pattern"))? {
+        Tag::String(pattern) => pattern.clone(),
+        // Patterns that are not registered are stored inline
+        Tag::Compound(pattern) => string(pattern, "asset_id")?,
+        _ => return None,
+    };
+    let color = layer
+        .get("Color")
+        .or_else(|| layer.get("color"))
+        .and_then(color_from_tag)?;
+    Some(BannerLayer { pattern, color })
+}
+
+/// Colors are stored as numeric ids by older versions and as names by newer versions.
+fn color_from_tag(tag: &Tag) -> Option<DyeColor> {
+    match tag {
+        Tag::Int(id) => DyeColor::from_id(*id),
+        Tag::String(name) => DyeColor::from_name(name),
+        _ => None,
+    }
+}
+
+fn string(compound: &HashMap<String, Tag>, key: &str) -> Option<String> {
+    match compound.get(key)? {
+        Tag::String(value) => Some(value.clone()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use test_case::test_case;
+
+    use super::{BannerDesign, BannerLayer};
+    use crate::{
+        data::{color::DyeColor, item::Item},
+        nbt::{List, Tag},
+    };
+
+    fn legacy_pattern(pattern: &str, color: i32) -> Tag {
+        Tag::Compound(HashMap::from([
+            ("Pattern".to_string(), Tag::String(pattern.to_string())),
+            ("Color".to_string(), Tag::Int(color)),
+        ]))
+    }
+
+    fn pattern(pattern: &str, color: &str) -> Tag {
+        Tag::Compound(HashMap::from([
+            ("pattern".to_string(), Tag::String(pattern.to_string())),
+            ("color".to_string(), Tag::String(color.to_string())),
+        ]))
+    }
+
+    #[test_case("minecraft:red_banner" => Some(DyeColor::Red); "Banner")]
+    #[test_case("minecraft:light_blue_wall_banner" => Some(DyeColor::LightBlue); "Wall banner")]
+    #[test_case("minecraft:shield" => None; "Shield")]
+    #[test_case("minecraft:rainbow_banner" => None; "Unknown color")]
+    fn test_base_color_from_id(id: &str) -> Option<DyeColor> {
+        BannerDesign::base_color_from_id(id)
+    }
+
+    #[test]
+    fn test_from_item_legacy() {
+        let item = Item {
+            id: "minecraft:white_banner".to_string(),
+            tag: Some(HashMap::from([(
+                "BlockEntityTag".to_string(),
+                Tag::Compound(HashMap::from([(
+                    "Patterns".to_string(),
+                    Tag::List(List::from(vec![
+                        legacy_pattern("bs", 14),
+                        legacy_pattern("cre", 15),
+                    ])),
+                )])),
+            )])),
+            components: None,
+            count: 1,
+        };
+        assert_eq!(
+            BannerDesign::from_item(&item),
+            Some(BannerDesign {
+                base_color: Some(DyeColor::White),
+                patterns: vec![
+                    BannerLayer {
+                        pattern: "bs".to_string(),
+                        color: DyeColor::Red,
+                    },
+                    BannerLayer {
+                        pattern: "cre".to_string(),
+                        color: DyeColor::Black,
+                    },
+                ],
+            })
+        );
+    }
+
+    #[test]
+    fn test_from_item_components() {
+        let item = Item {
+            id: "minecraft:shield".to_string(),
+            tag: None,
+            components: Some(HashMap::from([
+                (
+                    "minecraft:base_color".to_string(),
+                    Tag::String("blue".to_string()),
+                ),
+                (
+                    "minecraft:banner_patterns".to_string(),
+                    Tag::List(List::from(vec![
+                        pattern("minecraft:stripe_bottom", "red"),
+                        pattern("minecraft:creeper", "black"),
+                    ])),
+                ),
+            ])),
+            count: 1,
+        };
+        assert_eq!(
+            BannerDesign::from_item(&item),
+            Some(BannerDesign {
+                base_color: Some(DyeColor::Blue),
+                patterns: vec![
+                    BannerLayer {
+                        pattern: "minecraft:stripe_bottom".to_string(),
+                        color: DyeColor::Red,
+                    },
+                    BannerLayer {
+                        pattern: "minecraft:creeper".to_string(),
+                        color: DyeColor::Black,
+                    },
+                ],
+            })
+        );
+    }
+
+    #[test_case(6 => false; "Loom limit")]
+    #[test_case(7 => true; "Above loom limit")]
+    #[test_case(0 => false; "No patterns")]
+    fn test_exceeds_loom_limit(pattern_count: usize) -> bool {
+        BannerDesign {
+            base_color: Some(DyeColor::White),
+            patterns: vec![
+                BannerLayer {
+                    pattern: "minecraft:stripe_bottom".to_string(),
+                    color: DyeColor::Red,
+                };
+                pattern_count
+            ],
+        }
+        .exceeds_loom_limit()
+    }
+
+    #[test]
+    fn test_from_item_not_a_banner() {
+        let item = Item {
+            id: "minecraft:dirt".to_string(),
+            tag: None,
+            components: None,
+            count: 1,
+        };
+        assert_eq!(BannerDesign::from_item(&item), None);
+    }
+
+    #[cfg(feature = "block_entity")]
+    #[test]
+    fn test_from_block_entity() {
+        use crate::data::block_entity::{Banner, BannerPattern, BlockEntity, BlockEntityType};
+        let block_entity = BlockEntity {
+            id: "minecraft:black_wall_banner".to_string(),
+            keep_packed: false,
+            x: 0,
+            y: 0,
+            z: 0,
+            entity_type: BlockEntityType::Banner(Banner {
+                custom_name: None,
+                patterns: Some(List::from(vec![
+                    BannerPattern {
+                        color: 0,
+                        pattern: "bs".to_string(),
+                    },
+                    BannerPattern {
+                        color: 1,
+                        pattern: "ts".to_string(),
+                    },
+                ])),
+            }),
+        };
+        assert_eq!(
+            BannerDesign::from_block_entity(&block_entity),
+            Some(BannerDesign {
+                base_color: Some(DyeColor::Black),
+                patterns: vec![
+                    BannerLayer {
+                        pattern: "bs".to_string(),
+                        color: DyeColor::White,
+                    },
+                    BannerLayer {
+                        pattern: "ts".to_string(),
+                        color: DyeColor::Orange,
+                    },
+                ],
+            })
+        );
+    }
+}
@@ -3,7 +3,9 @@ use std::collections::HashMap;
 use jbe::Builder;
 
 use crate::{
+    coords::BlockPos,
     data::{
+        book::WrittenBook,
         entity::Entity,
         item::{Item, ItemWithSlot},
         load::block_entity::*,
@@ -177,7 +179,8 @@ pub struct CommandBlock {
 
 #[derive(Debug, Builder, Clone, PartialEq)]
 pub struct Conduit {
-    pub target: Array<i32>,
+    /// The UUID of the hostile mob the conduit is attacking. Conduits without a target do not store it.
+    pub target: Option<Array<i32>>,
 }
 
 #[derive(Debug, Builder, Clone, PartialEq)]
@@ -204,9 +207,13 @@ pub struct EnchantingTable {
 
 #[derive(Debug, Builder, Clone, PartialEq)]
 pub struct EndGateway {
+    /// Ticks since the gateway was created.
     pub age: i64,
+    /// Players are teleported to the exact exit position instead of a safe spot near it.
+    #[builder({default: false})]
     pub exact_teleport: bool,
-    pub exit_portal: ExitPortal,
+    /// The position the gateway leads to. Gateways that were never entered have no exit.
+    pub exit_portal: Option<ExitPortal>,
 }
 
 #[derive(Debug, Builder, Clone, PartialEq)]
@@ -240,11 +247,20 @@ pub struct Hopper {
 
 #[derive(Debug, Builder, Clone, PartialEq)]
 pub struct Jigsaw {
+    /// The block state the jigsaw block is replaced with once the structure is generated.
     pub final_state: String,
+    /// Either `rollable` or `aligned`.
     pub joint: String,
+    /// The name of this jigsaw block. Other jigsaw blocks connect to it if their target matches.
     pub name: String,
+    /// The template pool structures attached to this jigsaw block are selected from.
     pub pool: String,
+    /// The name of the jigsaw block this one connects to.
     pub target: String,
+    /// Only present since 1.20.3.
+    pub placement_priority: Option<i32>,
+    /// Only present since 1.20.3.
+    pub selection_priority: Option<i32>,
 }
 
 #[derive(Debug, Builder, Clone, PartialEq)]
@@ -257,7 +273,9 @@ pub struct Jukebox {
 
 #[derive(Debug, Builder, Clone, PartialEq)]
 pub struct Lectern {
+    /// The book on the lectern.
     pub book: Option<Item>,
+    /// The page the book is opened at, starting at 0.
     pub page: Option<i32>,
 }
 
@@ -285,6 +303,45 @@ pub struct PotentialSpawn {
     pub data: HashMap<String, Tag>,
 }
 
+/// An entity a spawner can spawn and the weight it is picked with.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WeightedSpawn {
+    pub weight: i32,
+    pub entity: Entity,
+}
+
+impl Spawner {
+    /// The entities the spawner picks from with their weight.
+    /// Spawners without `SpawnPotentials` always spawn the entity of `SpawnData` with a weight of 1.
+    /// Entries without a valid entity are skipped.
+    pub fn weighted_spawns(&self) -> Vec<WeightedSpawn> {
+        match &self.spawn_potentials {
+            Some(spawn_potentials) => spawn_potentials
+                .iter()
+                .filter_map(|potential| {
+                    spawn_entity(&potential.data).map(|entity| WeightedSpawn {
+                        weight: potential.weight,
+                        entity,
+                    })
+                })
+                .collect(),
+            None => spawn_entity(&self.spawn_data)
+                .map(|entity| WeightedSpawn { weight: 1, entity })
+                .into_iter()
+                .collect(),
+        }
+    }
+}
+
+/// Parse the entity of spawn data. Since 1.18 the entity is stored in `entity`, before the spawn data was the entity itself.
+fn spawn_entity(spawn_data: &HashMap<String, Tag>) -> Option<Entity> {
+    match spawn_data.get("entity") {
+        Some(entity) => Entity::try_from(entity.clone()).ok(),
+        None if spawn_data.is_empty() => None,
+        None => Entity::try_from(spawn_data.clone()).ok(),
+    }
+}
+
 #[derive(Debug, Builder, Clone, PartialEq)]
 pub struct CustomSpawnRules {
     pub block_light_limit: i32,
@@ -308,6 +365,9 @@ pub struct PistonBlockState {
 
 #[derive(Debug, Builder, Clone, PartialEq)]
 pub struct ShulkerBox {
+    /// Color of the shulker box. This is not stored in the NBT data but derived from the block id.
+    /// `None` for the default (undyed) shulker box.
+    pub color: Option<DyeColor>,
     pub custom_name: Option<String>,
     pub items: Option<List<ItemWithSlot>>,
     pub lock: Option<String>,
@@ -370,13 +430,18 @@ pub struct SoulCampfire {
 
 #[derive(Debug, Builder, Clone, PartialEq)]
 pub struct StructureBlock {
+    /// The player who saved the structure.
     pub author: String,
     pub ignore_entities: bool,
     pub integrity: f32,
+    /// The function of a data structure block.
     pub metadata: String,
     pub mirror: String,
+    /// One of `SAVE`, `LOAD`, `CORNER` or `DATA`. See [`StructureBlock::structure_mode`].
     pub mode: String,
+    /// The name the structure is saved as or loaded from.
     pub name: String,
+    /// Offset of the structure relative to the structure block.
     pub pos_x: i32,
     pub pos_y: i32,
     pub pos_z: i32,
@@ -384,6 +449,7 @@ pub struct StructureBlock {
     pub rotation: String,
     pub seed: i64,
     pub show_bounding_box: bool,
+    /// Size of the structure in blocks.
     pub size_x: i32,
     pub size_y: i32,
     pub size_z: i32,
@@ -515,6 +581,32 @@ macro_rules! impl_CBEB_for_builder {
     };
 }
 
+macro_rules! impl_container {
+    ($ty:ty, $slot_count:literal) => {
+        impl Container for $ty {
+            fn items(&self) -> &[ItemWithSlot] {
+                self.items.as_deref().map(Vec::as_slice).unwrap_or_default()
+            }
+            fn slot_count(&self) -> usize {
+                $slot_count
+            }
+        }
+    };
+    ($ty:ty, $slot_count:literal, loot_table) => {
+        impl Container for $ty {
+            fn items(&self) -> &[ItemWithSlot] {
+                self.items.as_deref().map(Vec::as_slice).unwrap_or_default()
+            }
+            fn slot_count(&self) -> usize {
+                $slot_count
+            }
+            fn has_loot_table(&self) -> bool {
+                self.loot_table.is_some() || self.loot_table_seed.is_some()
+            }
+        }
+    };
+}
+
 impl_IBE_for_builder!(BarrelBuilder, Barrel);
 impl_IBE_for_builder!(ChestBuilder, Chest);
 impl_IBE_for_builder!(DispenserBuilder, Dispenser);
@@ -525,6 +617,208 @@ impl_IBE_for_builder!(TrappedChestBuilder, TrappedChest);
 impl_CBEB_for_builder!(BlastFurnaceBuilder, BlastFurnace);
 impl_CBEB_for_builder!(FurnaceBuilder, Furnace);
 impl_CBEB_for_builder!(SmokerBuilder, Smoker);
+impl_container!(Barrel, 27, loot_table);
+impl_container!(Chest, 27, loot_table);
+impl_container!(Dispenser, 9, loot_table);
+impl_container!(Dropper, 9, loot_table);
+impl_container!(Hopper, 5, loot_table);
+impl_container!(ShulkerBox, 27, loot_table);
+impl_container!(TrappedChest, 27, loot_table);
+impl_container!(BlastFurnace, 3);
+impl_container!(BrewingStand, 5);
+impl_container!(Furnace, 3);
+impl_container!(Smoker, 3);
+
+impl BlockEntity {
+    /// Get the block entity as a container if it stores items in slots.
+    pub fn as_container(&self) -> Option<&dyn Container> {
+        self.entity_type.as_container()
+    }
+
+    /// Convert the block entity into a container if it stores items in slots.
+    pub fn into_container(self) -> Option<Box<dyn Container>> {
+        self.entity_type.into_container()
+    }
+
+    /// The position of the block this block entity belongs to.
+    pub fn position(&self) -> BlockPos {
+        BlockPos {
+            x: self.x,
+            y: self.y,
+            z: self.z,
+        }
+    }
+}
+
+impl BlockEntityType {
+    /// Get the block entity as a container if it stores items in slots.
+    pub fn as_container(&self) -> Option<&dyn Container> {
+        let container: &dyn Container = match self {
+            Self::Barrel(container) => container,
+            Self::BlastFurnace(container) => container,
+            Self::BrewingStand(container) => container,
+            Self::Chest(container) => container,
+            Self::Dispenser(container) => container,
+            Self::Dropper(container) => container,
+            Self::Furnace(container) => container,
+            Self::Hopper(container) => container,
+            Self::ShulkerBox(container) => container,
+            Self::Smoker(container) => container,
+            Self::TrappedChest(container) => container,
+            _ => return None,
+        };
+        Some(container)
+    }
+
+    /// Convert the block entity into a container if it stores items in slots.
+    pub fn into_container(self) -> Option<Box<dyn Container>> {
+        let container: Box<dyn Container> = match self {
+            Self::Barrel(container) => Box::new(container),
+            Self::BlastFurnace(container) => Box::new(container),
+            Self::BrewingStand(container) => Box::new(container),
+            Self::Chest(container) => Box::new(container),
+            Self::Dispenser(container) => Box::new(container),
+            Self::Dropper(container) => Box::new(container),
+            Self::Furnace(container) => Box::new(container),
+            Self::Hopper(container) => Box::new(container),
+            Self::ShulkerBox(container) => Box::new(container),
+            Self::Smoker(container) => Box::new(container),
+            Self::TrappedChest(container) => Box::new(container),
+            _ => return None,
+        };
+        Some(container)
+    }
+}
+
+impl Beehive {
+    /// The number of bees a beehive or bee nest can hold in vanilla Minecraft.
+    pub const MAX_OCCUPANTS: usize = 3;
+
+    /// The number of bees inside of the hive.
+    pub fn occupant_count(&self) -> usize {
+        self.bees.as_ref().map_or(0, |bees| bees.len())
+    }
+
+    /// The entity data of all bees inside of the hive.
+    pub fn bee_entities(&self) -> impl Iterator<Item = &Entity> {
+        self.bees
+            .iter()
+            .flat_map(|bees| bees.iter())
+            .map(|bee| &bee.entity_data)
+    }
+}
+
+impl EndGateway {
+    /// The position the gateway leads to or `None` if no exit was generated yet.
+    /// Unless `exact_teleport` is set, players arrive at a safe spot near this position.
+    pub fn exit_position(&self) -> Option<BlockPos> {
+        self.exit_portal.as_ref().map(|exit| BlockPos {
+            x: exit.x,
+            y: exit.y,
+            z: exit.z,
+        })
+    }
+}
+
+impl Lectern {
+    /// The content of the book on the lectern if it is a written book.
+    /// Writable books that were not signed yet are not returned.
+    pub fn written_book(&self) -> Option<WrittenBook> {
+        self.book.as_ref().and_then(WrittenBook::from_item)
+    }
+
+    /// The plain text of the page the written book on the lectern is opened at.
+    pub fn current_page_text(&self) -> Option<String> {
+        let page = usize::try_from(self.page.unwrap_or_default()).ok()?;
+        self.written_book()
+            .and_then(|book| book.pages.into_iter().nth(page))
+    }
+}
+
+impl StructureBlock {
+    /// The mode of the structure block or `None` if the mode is unknown.
+    pub fn structure_mode(&self) -> Option<StructureBlockMode> {
+        StructureBlockMode::from_name(&self.mode)
+    }
+
+    /// Offset of the structure relative to the structure block.
+    pub fn offset(&self) -> BlockPos {
+        BlockPos {
+            x: self.pos_x,
+            y: self.pos_y,
+            z: self.pos_z,
+        }
+    }
+
+    /// The size of the structure in blocks along the x, y and z axis.
+    pub fn size(&self) -> (i32, i32, i32) {
+        (self.size_x, self.size_y, self.size_z)
+    }
+}
+
+/// The mode a structure block is in.
+/// [Minecraft Wiki](https://minecraft.fandom.com/wiki/Structure_Block#Modes)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StructureBlockMode {
+    /// Saves the blocks in its area as a structure.
+    Save,
+    /// Places a saved structure.
+    Load,
+    /// Marks a corner to detect the size of a structure.
+    Corner,
+    /// Marks a position for structure generation e.g. a chest with loot.
+    Data,
+}
+
+impl StructureBlockMode {
+    /// Get a mode by its name as stored in NBT e.g. `SAVE`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "SAVE" => Some(Self::Save),
+            "LOAD" => Some(Self::Load),
+            "CORNER" => Some(Self::Corner),
+            "DATA" => Some(Self::Data),
+            _ => None,
+        }
+    }
+}
+
+impl ShulkerBox {
+    /// Check if the given block or item id belongs to a shulker box of any color.
+    pub fn is_shulker_box_id(id: &str) -> bool {
+        id.starts_with("minecraft:") && id.ends_with("shulker_box")
+    }
+
+    /// Get the color of a shulker box from its block or item id.
+    /// Returns `None` for the default shulker box and for ids that are not shulker boxes.
+    pub fn color_from_id(id: &str) -> Option<DyeColor> {
+        id.strip_prefix("minecraft:")
+            .and_then(|id| id.strip_suffix("_shulker_box"))
+            .and_then(DyeColor::from_name)
+    }
+}
+
+/// A block entity that stores items in slots.
+pub trait Container {
+    /// The items stored in the container.
+    fn items(&self) -> &[ItemWithSlot];
+    /// The number of slots the container has in vanilla Minecraft.
+    fn slot_count(&self) -> usize;
+    /// Returns `true` if the content of the container is generated from a loot table once it is opened.
+    fn has_loot_table(&self) -> bool {
+        false
+    }
+    /// Items in slots a vanilla container of this type does not have.
+    /// Such items can only exist if the container was edited or created by an exploit.
+    fn out_of_range_items(&self) -> Vec<&ItemWithSlot> {
+        self.items()
+            .iter()
+            .filter(|item| {
+                usize::try_from(item.slot).map_or(true, |slot| slot >= self.slot_count())
+            })
+            .collect()
+    }
+}
 
 pub trait InventoryBlock {
     fn custom_name(&self) -> Option<&String>;
@@ -0,0 +1,190 @@
+//! Written books and their text.
+
+use std::collections::HashMap;
+
+use super::item::Item;
+use crate::nbt::Tag;
+
+/// A signed book.
+/// [Minecraft Wiki](https://minecraft.fandom.com/wiki/Written_Book#Item_data)
+#[derive(Debug, Clone, PartialEq)]
+pub struct WrittenBook {
+    pub title: String,
+    pub author: String,
+    /// 0 for the original, 1 for a copy of the original, 2 for a copy of a copy and 3 for a tattered book
+    pub generation: i32,
+    /// The plain text of every page. Formatting of text components is removed.
+    pub pages: Vec<String>,
+}
+
+impl WrittenBook {
+    /// The number of pages a book can have in vanilla Minecraft.
+    pub const MAX_PAGES: usize = 100;
+
+    /// Get the content of a written book.
+    /// The `minecraft:written_book_content` component takes precedence over the legacy item tag.
+    pub fn from_item(item: &Item) -> Option<Self> {
+        if item.id != "minecraft:written_book" {
+            return None;
+        }
+        let component = item
+            .components
+            .as_ref()
+            .and_then(|components| components.get("minecraft:written_book_content"));
+        if let Some(Tag::Compound(content)) = component {
+            return Some(Self::from_compound(content));
+        }
+        item.tag.as_ref().map(Self::from_compound)
+    }
+
+    /// Returns `true` if the book has more pages than a vanilla book can have.
+    /// Such books can only be created by editing the world or by an exploit.
+    pub fn exceeds_page_limit(&self) -> bool {
+        self.pages.len() > Self::MAX_PAGES
+    }
+
+    fn from_compound(content: &HashMap<String, Tag>) -> Self {
+        let title = match content.get("title") {
+            Some(Tag::String(title)) => title.clone(),
+            // Filterable text stores the unfiltered text in `raw`
+            Some(Tag::Compound(title)) => match title.get("raw") {
+                Some(Tag::String(title)) => title.clone(),
+                _ => String::new(),
+            },
+            _ => String::new(),
+        };
+        let author = match content.get("author") {
+            Some(Tag::String(author)) => author.clone(),
+            _ => String::new(),
+        };
+        let generation = match content.get("generation") {
+            Some(Tag::Int(generation)) => *generation,
+            _ => 0,
+        };
+        let pages = match content.get("pages") {
+            Some(Tag::List(pages)) => pages.iter().map(page_text).collect(),
+            _ => Vec::new(),
+        };
+        Self {
+            title,
+            author,
+            generation,
+            pages,
+        }
+    }
+}
+
+/// Get the plain text of a page.
+/// Pages are stored as JSON text components, as filterable text or as NBT text components.
+fn page_text(page: &Tag) -> String {
+    // Filterable text stores the unfiltered text in `raw`
+    let page = match page {
+        Tag::Compound(filterable) => filterable.get("raw").unwrap_or(page),
+        page => page,
+    };
+    super::text::plain_text(page)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use test_case::test_case;
+
+    use super::WrittenBook;
+    use crate::{
+        data::item::Item,
+        nbt::{List, Tag},
+    };
+
+    fn string(value: &str) -> Tag {
+        Tag::String(value.to_string())
+    }
+
+    #[test]
+    fn test_from_item_legacy() {
+        let item = Item {
+            id: "minecraft:written_book".to_string(),
+            tag: Some(HashMap::from([
+                ("title".to_string(), string("Coordinates")),
+                ("author".to_string(), string("Steve")),
+                ("generation".to_string(), Tag::Int(1)),
+                (
+                    "pages".to_string(),
+                    Tag::List(List::from(vec![
+                        string(
+                            r#"{"text":"Base at ","extra":[{"text":"100 64 -200","bold":true}]}"#,
+                        ),
+                        string(r#""Second page""#),
+                    ])),
+                ),
+            ])),
+            components: None,
+            count: 1,
+        };
+        assert_eq!(
+            WrittenBook::from_item(&item),
+            Some(WrittenBook {
+                title: "Coordinates".to_string(),
+                author: "Steve".to_string(),
+                generation: 1,
+                pages: vec!["Base at 100 64 -200".to_string(), "Second page".to_string()],
+            })
+        );
+    }
+
+    #[test]
+    fn test_from_item_components() {
+        let raw = |page: Tag| Tag::Compound(HashMap::from([("raw".to_string(), page)]));
+        let item = Item {
+            id: "minecraft:written_book".to_string(),
+            tag: None,
+            components: Some(HashMap::from([(
+                "minecraft:written_book_content".to_string(),
+                Tag::Compound(HashMap::from([
+                    ("title".to_string(), raw(string("Coordinates"))),
+                    ("author".to_string(), string("Steve")),
+                    (
+                        "pages".to_string(),
+                        Tag::List(List::from(vec![
+                            raw(string(r#"["Base at ",{"text":"100 64 -200"}]"#)),
+                            raw(Tag::Compound(HashMap::from([(
+                                "text".to_string(),
+                                string("Second page"),
+                            )]))),
+                        ])),
+                    ),
+                ])),
+            )])),
+            count: 1,
+        };
+        assert_eq!(
+            WrittenBook::from_item(&item),
+            Some(WrittenBook {
+                title: "Coordinates".to_string(),
+                author: "Steve".to_string(),
+                generation: 0,
+                pages: vec!["Base at 100 64 -200".to_string(), "Second page".to_string()],
+            })
+        );
+    }
+
+    #[test_case(string("{\"text\":\"unclosed") => "{\"text\":\"unclosed"; "Malformed JSON")]
+    #[test_case(string("plain text") => "plain text"; "Plain text")]
+    #[test_case(string("42") => "42"; "Number")]
+    fn test_page_text(page: Tag) -> String {
+        super::page_text(&page)
+    }
+
+    #[test_case(100 => false; "At limit")]
+    #[test_case(101 => true; "Above limit")]
+    fn test_exceeds_page_limit(pages: usize) -> bool {
+        WrittenBook {
+            title: String::new(),
+            author: String::new(),
+            generation: 0,
+            pages: vec![String::new(); pages],
+        }
+        .exceeds_page_limit()
+    }
+}
@@ -1,3 +1,5 @@
+#[cfg(feature = "chunk_section")]
+use std::cell::OnceCell;
 use std::collections::HashMap;
 
 use jbe::Builder;
@@ -6,6 +8,7 @@ use crate::nbt::{Array, List};
 
 #[cfg(feature = "block_entity")]
 use super::block_entity::BlockEntity;
+use super::entity::Entity;
 pub use super::load::chunk::*;
 
 #[derive(jbe::Builder, Debug, PartialEq)]
@@ -18,6 +21,9 @@ pub struct ChunkData {
     pub last_update: i64,
     #[cfg(feature = "chunk_section")]
     pub sections: List<Section>,
+    /// Entities stored inside of the chunk. Since 1.17 entities of fully generated chunks are stored in separate entity region files.
+    /// Use [`crate::entities_for_chunk`] to get the entities of a chunk regardless of where they are stored.
+    pub entities: Option<List<Entity>>,
     pub block_entities: Option<List<BlockEntity>>, /*#[get = "pub"]
                                                    carving_masks: Option<()>,
                                                    #[get = "pub"]
@@ -36,6 +42,97 @@ pub struct ChunkData {
                                                    post_processing: Vec<()>*/
 }
 
+impl ChunkData {
+    /// The name of the Minecraft release this was last saved with.
+    pub fn version_name(&self) -> Option<&'static str> {
+        crate::data_version::version_name(self.data_version)
+    }
+
+    /// A view over the blocks of all sections of the chunk.
+    /// Sections are decoded when a block inside of them is read for the first time.
+    #[cfg(feature = "chunk_section")]
+    pub fn blocks(&self) -> BlockGrid<'_> {
+        BlockGrid {
+            sections: self
+                .sections
+                .iter()
+                .map(|section| {
+                    (
+                        section.y,
+                        LazySection {
+                            block_states: &section.block_states,
+                            indices: OnceCell::new(),
+                        },
+                    )
+                })
+                .collect(),
+        }
+    }
+}
+
+/// The blocks of a chunk spanning the full height of the world. Created by [`ChunkData::blocks`].
+#[cfg(feature = "chunk_section")]
+#[derive(Debug)]
+pub struct BlockGrid<'a> {
+    sections: HashMap<i8, LazySection<'a>>,
+}
+
+#[cfg(feature = "chunk_section")]
+#[derive(Debug)]
+struct LazySection<'a> {
+    block_states: &'a BlockStates,
+    /// The palette index of every block. Sections with a single block state are never decoded.
+    indices: OnceCell<Vec<u16>>,
+}
+
+#[cfg(feature = "chunk_section")]
+impl<'a> BlockGrid<'a> {
+    /// Blocks per section along every axis.
+    pub const SECTION_SIZE: i32 = 16;
+
+    /// Get the block state at a position. `x` and `z` are relative to the chunk, `y` is the absolute height.
+    /// Returns `None` if the position is outside of the chunk or its section is not stored.
+    pub fn get(&self, x: i32, y: i32, z: i32) -> Option<&'a BlockState> {
+        let size = Self::SECTION_SIZE;
+        if !(0..size).contains(&x) || !(0..size).contains(&z) {
+            return None;
+        }
+        let section_y = i8::try_from(y.div_euclid(size)).ok()?;
+        let section = self.sections.get(&section_y)?;
+        let block_states: &'a BlockStates = section.block_states;
+        let palette = &block_states.palette;
+        if palette.len() == 1 {
+            return palette.first();
+        }
+        let indices = section
+            .indices
+            .get_or_init(|| decode_block_states(block_states));
+        let index = (y.rem_euclid(size) * size + z) * size + x;
+        let palette_index = indices.get(usize::try_from(index).ok()?)?;
+        palette.get(usize::from(*palette_index))
+    }
+}
+
+/// Unpack the palette indices of all blocks in a section.
+/// Since 1.16 indices do not span multiple longs.
+#[cfg(feature = "chunk_section")]
+fn decode_block_states(block_states: &BlockStates) -> Vec<u16> {
+    let Some(data) = &block_states.data else {
+        return Vec::new();
+    };
+    let max_index = block_states.palette.len().saturating_sub(1);
+    let bits = (usize::BITS - max_index.leading_zeros()).max(4);
+    let per_long = 64 / bits;
+    let mask = (1_u64 << bits) - 1;
+    data.iter()
+        .flat_map(|long| {
+            let long = *long as u64;
+            (0..per_long).map(move |i| ((long >> (i * bits)) & mask) as u16)
+        })
+        .take(4096)
+        .collect()
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum ChunkStatus {
     Empty,
@@ -0,0 +1,116 @@
+//! Colors used by dyed blocks and items.
+
+/// One of the 16 dye colors.
+/// [Minecraft Wiki](https://minecraft.fandom.com/wiki/Dye#Data_values)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DyeColor {
+    White,
+    Orange,
+    Magenta,
+    LightBlue,
+    Yellow,
+    Lime,
+    Pink,
+    Gray,
+    LightGray,
+    Cyan,
+    Purple,
+    Blue,
+    Brown,
+    Green,
+    Red,
+    Black,
+}
+
+/// All dye colors ordered by their numeric id.
+const DYE_COLORS: [DyeColor; 16] = [
+    DyeColor::White,
+    DyeColor::Orange,
+    DyeColor::Magenta,
+    DyeColor::LightBlue,
+    DyeColor::Yellow,
+    DyeColor::Lime,
+    DyeColor::Pink,
+    DyeColor::Gray,
+    DyeColor::LightGray,
+    DyeColor::Cyan,
+    DyeColor::Purple,
+    DyeColor::Blue,
+    DyeColor::Brown,
+    DyeColor::Green,
+    DyeColor::Red,
+    DyeColor::Black,
+];
+
+impl DyeColor {
+    /// Get a color by its numeric id as used by older versions.
+    pub fn from_id(id: i32) -> Option<Self> {
+        usize::try_from(id)
+            .ok()
+            .and_then(|id| DYE_COLORS.get(id))
+            .copied()
+    }
+
+    /// Get a color by its name e.g. `light_blue`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        DYE_COLORS.into_iter().find(|color| color.name() == name)
+    }
+
+    /// The numeric id of the color.
+    pub fn id(&self) -> i32 {
+        *self as i32
+    }
+
+    /// The name of the color as used in ids e.g. `light_blue`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::White => "white",
+            Self::Orange => "orange",
+            Self::Magenta => "magenta",
+            Self::LightBlue => "light_blue",
+            Self::Yellow => "yellow",
+            Self::Lime => "lime",
+            Self::Pink => "pink",
+            Self::Gray => "gray",
+            Self::LightGray => "light_gray",
+            Self::Cyan => "cyan",
+            Self::Purple => "purple",
+            Self::Blue => "blue",
+            Self::Brown => "brown",
+            Self::Green => "green",
+            Self::Red => "red",
+            Self::Black => "black",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DyeColor;
+    use test_case::test_case;
+
+    #[test_case(0 => Some(DyeColor::White); "White")]
+    #[test_case(3 => Some(DyeColor::LightBlue); "Light blue")]
+    #[test_case(15 => Some(DyeColor::Black); "Black")]
+    #[test_case(16 => None; "Too large")]
+    #[test_case(-1 => None; "Negative")]
+    fn test_from_id(id: i32) -> Option<DyeColor> {
+        DyeColor::from_id(id)
+    }
+
+    #[test_case("white" => Some(DyeColor::White); "White")]
+    #[test_case("light_gray" => Some(DyeColor::LightGray); "Light gray")]
+    #[test_case("rainbow" => None; "Unknown")]
+    fn test_from_name(name: &str) -> Option<DyeColor> {
+        DyeColor::from_name(name)
+    }
+
+    #[test]
+    fn test_id_roundtrip() {
+        for id in 0..16 {
+            let color = DyeColor::from_id(id).expect("Valid color id");
+            assert_eq!(color.id(), id);
+            assert_eq!(DyeColor::from_name(color.name()), Some(color));
+        }
+    }
+}
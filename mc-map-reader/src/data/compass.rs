@@ -0,0 +1,195 @@
+//! Compasses pointing to a lodestone.
+
+use std::collections::HashMap;
+
+use super::item::Item;
+use crate::{coords::BlockPos, nbt::Tag};
+
+/// A compass that was used on a lodestone.
+/// [Minecraft Wiki](https://minecraft.fandom.com/wiki/Compass#Item_data)
+#[derive(Debug, Clone, PartialEq)]
+pub struct LodestoneCompass {
+    /// The position of the lodestone. `None` if the compass lost its target and spins randomly.
+    pub position: Option<BlockPos>,
+    /// The dimension of the lodestone e.g. `minecraft:overworld`.
+    pub dimension: Option<String>,
+    /// The compass loses its target once the lodestone is removed.
+    /// Compasses that are not tracked keep pointing to the position.
+    pub tracked: bool,
+}
+
+impl LodestoneCompass {
+    /// Get the lodestone a compass points to.
+    /// The `minecraft:lodestone_tracker` component takes precedence over the legacy item tag.
+    /// Returns `None` for compasses that were never used on a lodestone.
+    pub fn from_item(item: &Item) -> Option<Self> {
+        if item.id != "minecraft:compass" {
+            return None;
+        }
+        let component = item
+            .components
+            .as_ref()
+            .and_then(|components| components.get("minecraft:lodestone_tracker"));
+        if let Some(Tag::Compound(tracker)) = component {
+            return Some(Self::from_component(tracker));
+        }
+        item.tag.as_ref().and_then(Self::from_legacy_tag)
+    }
+
+    fn from_component(tracker: &HashMap<String, Tag>) -> Self {
+        let target = match tracker.get("target") {
+            Some(Tag::Compound(target)) => Some(target),
+            _ => None,
+        };
+        let position = target
+            .and_then(|target| target.get("pos"))
+            .and_then(|pos| match pos {
+                Tag::IntArray(pos) => match pos[..] {
+                    [x, y, z] => Some(BlockPos { x, y, z }),
+                    _ => None,
+                },
+                _ => None,
+            });
+        let dimension = target
+            .and_then(|target| target.get("dimension"))
+            .and_then(|dimension| match dimension {
+                Tag::String(dimension) => Some(dimension.clone()),
+                _ => None,
+            });
+        let tracked = !matches!(tracker.get("tracked"), Some(Tag::Byte(0)));
+        Self {
+            position,
+            dimension,
+            tracked,
+        }
+    }
+
+    fn from_legacy_tag(tag: &HashMap<String, Tag>) -> Option<Self> {
+        let position = match tag.get("LodestonePos") {
+            Some(Tag::Compound(pos)) => match (pos.get("X"), pos.get("Y"), pos.get("Z")) {
+                (Some(Tag::Int(x)), Some(Tag::Int(y)), Some(Tag::Int(z))) => Some(BlockPos {
+                    x: *x,
+                    y: *y,
+                    z: *z,
+                }),
+                _ => None,
+            },
+            _ => None,
+        };
+        let dimension = match tag.get("LodestoneDimension") {
+            Some(Tag::String(dimension)) => Some(dimension.clone()),
+            _ => None,
+        };
+        let tracked = match tag.get("LodestoneTracked") {
+            Some(Tag::Byte(tracked)) => *tracked != 0,
+            _ => false,
+        };
+        if position.is_none() && dimension.is_none() && !tag.contains_key("LodestoneTracked") {
+            return None;
+        }
+        Some(Self {
+            position,
+            dimension,
+            tracked,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LodestoneCompass;
+    use crate::{
+        coords::BlockPos,
+        data::item::Item,
+        nbt::{Array, Tag},
+        test_util::compound,
+    };
+
+    fn compass(tag: Option<Tag>, components: Option<Tag>) -> Item {
+        let to_map = |tag: Tag| tag.get_as_map().expect("Tag is a compound");
+        Item {
+            id: "minecraft:compass".to_string(),
+            tag: tag.map(to_map),
+            components: components.map(to_map),
+            count: 1,
+        }
+    }
+
+    fn lodestone() -> LodestoneCompass {
+        LodestoneCompass {
+            position: Some(BlockPos {
+                x: -1520,
+                y: 64,
+                z: 3200,
+            }),
+            dimension: Some("minecraft:the_nether".to_string()),
+            tracked: true,
+        }
+    }
+
+    #[test]
+    fn test_from_item_legacy() {
+        let item = compass(
+            Some(compound(vec![
+                (
+                    "LodestonePos",
+                    compound(vec![
+                        ("X", Tag::Int(-1520)),
+                        ("Y", Tag::Int(64)),
+                        ("Z", Tag::Int(3200)),
+                    ]),
+                ),
+                (
+                    "LodestoneDimension",
+                    Tag::String("minecraft:the_nether".to_string()),
+                ),
+                ("LodestoneTracked", Tag::Byte(1)),
+            ])),
+            None,
+        );
+        assert_eq!(LodestoneCompass::from_item(&item), Some(lodestone()));
+    }
+
+    #[test]
+    fn test_from_item_component() {
+        let item = compass(
+            None,
+            Some(compound(vec![(
+                "minecraft:lodestone_tracker",
+                compound(vec![(
+                    "target",
+                    compound(vec![
+                        ("pos", Tag::IntArray(Array::from(vec![-1520, 64, 3200]))),
+                        ("dimension", Tag::String("minecraft:the_nether".to_string())),
+                    ]),
+                )]),
+            )])),
+        );
+        assert_eq!(LodestoneCompass::from_item(&item), Some(lodestone()));
+    }
+
+    #[test]
+    fn test_from_item_lost_target() {
+        let item = compass(
+            None,
+            Some(compound(vec![(
+                "minecraft:lodestone_tracker",
+                compound(vec![("tracked", Tag::Byte(1))]),
+            )])),
+        );
+        assert_eq!(
+            LodestoneCompass::from_item(&item),
+            Some(LodestoneCompass {
+                position: None,
+                dimension: None,
+                tracked: true,
+            })
+        );
+    }
+
+    #[test]
+    fn test_from_item_without_lodestone() {
+        let item = compass(Some(compound(vec![])), None);
+        assert_eq!(LodestoneCompass::from_item(&item), None);
+    }
+}
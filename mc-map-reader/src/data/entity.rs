@@ -2,7 +2,10 @@ use std::collections::HashMap;
 
 use jbe::Builder;
 
-use crate::nbt::{Array, List, Tag};
+use crate::{
+    coords::BlockPos,
+    nbt::{Array, List, Tag},
+};
 
 use super::item::Item;
 
@@ -39,6 +42,21 @@ pub struct Entity {
     pub uuid: Option<Array<i32>>,
 }
 
+impl Entity {
+    /// The custom name of the entity as plain text, e.g. set by a name tag.
+    /// Names are stored as JSON text. Names saved before 1.13 are plain strings and are returned as they are.
+    pub fn custom_name_text(&self) -> Option<String> {
+        self.custom_name
+            .as_deref()
+            .map(super::text::plain_text_of_json)
+    }
+
+    /// Returns `true` if the custom name is shown even if no player is looking at the entity.
+    pub fn is_custom_name_visible(&self) -> bool {
+        self.custom_name_visible.unwrap_or_default()
+    }
+}
+
 #[derive(Debug, Builder, PartialEq)]
 pub struct Mob {
     pub absorption_amount: Option<f32>,
@@ -47,6 +65,7 @@ pub struct Mob {
     pub armor_items: Option<List<Item>>,
     pub entity: Entity,
     pub attributes: Option<List<HashMap<String, Tag>>>,
+    /// The AI state of the mob. See [`Mob::memory_position`] for remembered positions.
     pub brain: Option<HashMap<String, Tag>>,
     pub can_pick_up_loot: Option<bool>,
     pub death_loot_table: Option<String>,
@@ -68,11 +87,188 @@ pub struct Mob {
     pub team: Option<String>,
 }
 
+impl Mob {
+    /// A position stored in the `Brain.memories` of the mob, e.g. `minecraft:job_site` of a villager.
+    /// Returns `None` if the mob does not remember the memory or the memory is not a position.
+    pub fn memory_position(&self, memory: &str) -> Option<GlobalPos> {
+        let Some(Tag::Compound(memories)) = self.brain.as_ref()?.get("memories") else {
+            return None;
+        };
+        let memory = match memories.get(memory)? {
+            Tag::Compound(memory) => memory,
+            _ => return None,
+        };
+        // Memories are wrapped in `value` since 1.16
+        let memory = match memory.get("value") {
+            Some(Tag::Compound(value)) => value,
+            _ => memory,
+        };
+        let position = match memory.get("pos") {
+            Some(Tag::IntArray(pos)) => match pos[..] {
+                [x, y, z] => BlockPos { x, y, z },
+                _ => return None,
+            },
+            _ => return None,
+        };
+        let dimension = match memory.get("dimension") {
+            Some(Tag::String(dimension)) => dimension.clone(),
+            _ => return None,
+        };
+        Some(GlobalPos {
+            position,
+            dimension,
+        })
+    }
+}
+
+/// A position in a specific dimension.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GlobalPos {
+    pub position: BlockPos,
+    /// The dimension e.g. `minecraft:overworld`.
+    pub dimension: String,
+}
+
+/// A dropped item lying on the ground. The id of the entity is `minecraft:item`.
+/// [Minecraft Wiki](https://minecraft.fandom.com/wiki/Item_(entity)#Data_values)
+#[derive(Debug, Builder, PartialEq)]
+pub struct ItemEntity {
+    pub entity: Entity,
+    pub item: Item,
+    /// Ticks the item has existed. Items despawn at an age of 6000, an age of -32768 prevents despawning.
+    #[builder({default: 0})]
+    pub age: i16,
+    /// Ticks until the item can be picked up. A delay of 32767 prevents picking the item up.
+    #[builder({default: 0})]
+    pub pickup_delay: i16,
+    #[builder({default: 5})]
+    pub health: i16,
+}
+
+impl ItemEntity {
+    /// The id of item entities.
+    pub const ID: &'static str = "minecraft:item";
+}
+
+/// A painting hanging on a block. The id of the entity is `minecraft:painting`.
+/// [Minecraft Wiki](https://minecraft.fandom.com/wiki/Painting#Entity_data)
+#[derive(Debug, Builder, PartialEq)]
+pub struct Painting {
+    pub entity: Entity,
+    /// The painting variant e.g. `minecraft:kebab`. Read from `Motive` in versions before 1.19.
+    pub variant: String,
+    /// The direction the painting faces. 0 is south, 1 is west, 2 is north and 3 is east.
+    pub facing: i8,
+    /// The block the painting is in
+    pub tile_position: Option<BlockPos>,
+}
+
+impl Painting {
+    /// The id of painting entities.
+    pub const ID: &'static str = "minecraft:painting";
+}
+
+/// A villager or zombie villager.
+/// [Minecraft Wiki](https://minecraft.fandom.com/wiki/Villager#Entity_data)
+#[derive(Debug, Builder, PartialEq)]
+pub struct Villager {
+    pub mob: Mob,
+    pub villager_data: VillagerData,
+    /// Experience towards the next level. Not used by zombie villagers.
+    pub xp: Option<i32>,
+}
+
+impl Villager {
+    /// The id of villager entities.
+    pub const ID: &'static str = "minecraft:villager";
+    /// The id of zombie villager entities.
+    pub const ZOMBIE_VILLAGER_ID: &'static str = "minecraft:zombie_villager";
+
+    /// The workstation the villager claimed. Villagers that lost their job site can not restock their trades.
+    pub fn job_site(&self) -> Option<GlobalPos> {
+        self.mob.memory_position("minecraft:job_site")
+    }
+
+    /// A workstation the villager is walking to but has not claimed yet.
+    pub fn potential_job_site(&self) -> Option<GlobalPos> {
+        self.mob.memory_position("minecraft:potential_job_site")
+    }
+
+    /// The bed the villager claimed.
+    pub fn home(&self) -> Option<GlobalPos> {
+        self.mob.memory_position("minecraft:home")
+    }
+
+    /// The bell the villager gathers at.
+    pub fn meeting_point(&self) -> Option<GlobalPos> {
+        self.mob.memory_position("minecraft:meeting_point")
+    }
+}
+
+/// The biome type, profession and level of a villager.
+#[derive(Debug, Builder, Clone, PartialEq)]
+pub struct VillagerData {
+    /// The biome variant e.g. `minecraft:plains`. Stored as `type`.
+    pub biome: String,
+    /// The profession e.g. `minecraft:librarian`. Legacy numeric professions are converted to their id.
+    pub profession: String,
+    /// The level from 1 (novice) to 5 (master)
+    #[builder({default: 1})]
+    pub level: i32,
+}
+
+impl VillagerData {
+    /// The highest level a villager can reach in vanilla Minecraft.
+    pub const MAX_LEVEL: i32 = 5;
+
+    /// Get a profession id by its numeric id as used before 1.14.
+    /// Blacksmiths are split into multiple professions since 1.14 and are converted to armorers.
+    pub fn profession_from_legacy_id(id: i32) -> Option<&'static str> {
+        let profession = match id {
+            0 => "minecraft:farmer",
+            1 => "minecraft:librarian",
+            2 => "minecraft:cleric",
+            3 => "minecraft:armorer",
+            4 => "minecraft:butcher",
+            5 => "minecraft:nitwit",
+            _ => return None,
+        };
+        Some(profession)
+    }
+}
+
+/// What a leashed mob is tied to.
+/// Read from `Leash` before 1.20.5 and from `leash` since then.
 #[derive(Debug, PartialEq)]
 pub enum Leash {
+    /// The UUID of the entity holding the leash e.g. a player.
     Entity(Array<i32>),
+    /// The position of the fence the leash is tied to.
     Position { x: i32, y: i32, z: i32 },
 }
+
+impl Leash {
+    /// The fence the leash is tied to.
+    /// A leash without a leash knot entity at this position is a ghost leash.
+    pub fn position(&self) -> Option<BlockPos> {
+        match self {
+            Self::Position { x, y, z } => Some(BlockPos {
+                x: *x,
+                y: *y,
+                z: *z,
+            }),
+            Self::Entity(_) => None,
+        }
+    }
+
+    /// The UUID of the entity holding the leash.
+    pub fn holder(&self) -> Option<&Array<i32>> {
+        match self {
+            Self::Entity(uuid) => Some(uuid),
+            Self::Position { .. } => None,
+        }
+    }
+}
 #[derive(Debug, Builder, PartialEq)]
 pub struct ActiveEffect {
     pub ambient: bool,
@@ -1,6 +1,6 @@
 //! Anvil save file.
 
-use crate::data::chunk::ChunkData;
+use crate::{coords::CHUNKS_PER_REGION, data::chunk::ChunkData};
 
 /// Anvil save file.
 /// [Minecraft Wiki](https://minecraft.fandom.com/wiki/Anvil_file_format)
@@ -17,6 +17,20 @@ impl AnvilSave {
     pub fn new(header: McRegionHeader, chunks: Vec<ChunkData>) -> Self {
         Self { header, chunks }
     }
+
+    /// Iterate over all generated chunks together with their x and z position inside of the region.
+    /// Chunks whose header entry has no offset are skipped.
+    pub fn iter_chunks(&self) -> impl Iterator<Item = (u8, u8, &ChunkData)> {
+        self.chunks.iter().filter_map(|chunk| {
+            let x = chunk.x_pos.rem_euclid(CHUNKS_PER_REGION);
+            let z = chunk.z_pos.rem_euclid(CHUNKS_PER_REGION);
+            let index = usize::try_from(z * CHUNKS_PER_REGION + x).ok()?;
+            self.header.chunks[index]
+                .as_ref()
+                .filter(|chunk_info| chunk_info.offset != 0)?;
+            Some((u8::try_from(x).ok()?, u8::try_from(z).ok()?, chunk))
+        })
+    }
 }
 
 const CHUNKS_PER_FILE: usize = 1024;
@@ -25,6 +39,8 @@ const CHUNK_OFFSETS_START: usize = 0;
 const CHUNK_OFFSETS_SIZE: usize = CHUNK_OFFSET_LENGTH * CHUNKS_PER_FILE;
 /// The size of the header in bytes.
 pub const MC_REGION_HEADER_SIZE: usize = 8192;
+/// The size of a sector in bytes. Chunks are aligned to sectors.
+pub const SECTOR_SIZE: usize = 4096;
 /// The message that is displayed when the header is invalid.
 pub const INVALID_HEADER_MESSAGE: &str = "Invalid Header size";
 
@@ -51,6 +67,15 @@ impl McRegionHeader {
     pub fn get_chunk_info(&self) -> &[Option<ChunkInfo>; CHUNKS_PER_FILE] {
         &self.chunks
     }
+
+    /// The number of generated chunks in the region.
+    pub fn chunk_count(&self) -> usize {
+        self.chunks
+            .iter()
+            .flatten()
+            .filter(|chunk_info| chunk_info.offset != 0)
+            .count()
+    }
 }
 
 impl From<[u8; MC_REGION_HEADER_SIZE]> for McRegionHeader {
@@ -137,6 +162,61 @@ mod tests {
         );
     }
 
+    fn chunk(x_pos: i32, z_pos: i32) -> ChunkData {
+        ChunkData {
+            data_version: 3465,
+            x_pos,
+            y_pos: -4,
+            z_pos,
+            status: crate::data::chunk::ChunkStatus::Full,
+            last_update: 0,
+            sections: crate::nbt::List::from(vec![]),
+            entities: None,
+            block_entities: None,
+        }
+    }
+
+    #[test]
+    fn test_iter_chunks() {
+        let mut chunks: [Option<ChunkInfo>; CHUNKS_PER_FILE] = (0..CHUNKS_PER_FILE)
+            .map(|_| None)
+            .collect::<Vec<_>>()
+            .try_into()
+            .unwrap();
+        let chunk_info = |offset| {
+            Some(ChunkInfo {
+                sector_count: 1,
+                offset,
+                timestamp: 0,
+            })
+        };
+        chunks[0] = chunk_info(2);
+        chunks[32 + 5] = chunk_info(3);
+        chunks[1023] = chunk_info(4);
+        // Slots without an offset have not been generated
+        chunks[2 * 32 + 2] = chunk_info(0);
+        let header = McRegionHeader { chunks };
+        assert_eq!(header.chunk_count(), 3);
+
+        let anvil_save = AnvilSave::new(
+            header,
+            vec![
+                chunk(-32, 32),
+                chunk(-27, 33),
+                chunk(-1, 63),
+                chunk(-30, 34),
+            ],
+        );
+        let chunks = anvil_save
+            .iter_chunks()
+            .map(|(x, z, chunk)| (x, z, chunk.x_pos, chunk.z_pos))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            chunks,
+            vec![(0, 0, -32, 32), (5, 1, -27, 33), (31, 31, -1, 63)]
+        );
+    }
+
     #[test]
     fn test_get_chunk_info() {
         let header = McRegionHeader {
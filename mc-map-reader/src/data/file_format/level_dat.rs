@@ -25,18 +25,23 @@ pub struct LevelDat {
     pub custom_boss_events: HashMap<String, CustomBossEvent>,
     pub data_packs: DataPacks,
     pub data_version: i32,
+    /// Time of day in ticks
     pub day_time: i64,
     pub difficulty: i8,
     pub difficulty_locked: bool,
     pub dimension_data: Option<HashMap<String, HashMap<String, Tag>>>,
+    /// Only present in worlds saved since 1.16. Use [`LevelDat::dragon_fight`] to get the dragon fight of any world.
+    pub dragon_fight: Option<DragonFight>,
     pub game_rules: HashMap<String, String>,
-    pub world_gen_settings: WorldGenSettings,
+    /// Only present in worlds created since 1.16
+    pub world_gen_settings: Option<WorldGenSettings>,
     pub game_type: i32,
     pub generator_name: Option<String>,
     pub generator_options: Option<HashMap<String, Tag>>,
     pub generator_version: Option<i32>,
     pub hardcore: bool,
     pub initialized: bool,
+    /// Unix time in milliseconds when the world was last played
     pub last_played: i64,
     pub level_name: String,
     #[builder({default: true})]
@@ -44,7 +49,7 @@ pub struct LevelDat {
     pub player: Option<Player>,
     pub raining: bool,
     pub rain_time: i32,
-    /// Not used
+    /// Seed of worlds created before 1.16. Use [`LevelDat::seed`] to get the seed of any world.
     pub random_seed: Option<i64>,
     /// Not used
     pub size_on_disk: Option<i64>,
@@ -53,15 +58,158 @@ pub struct LevelDat {
     pub spawn_z: i32,
     pub thundering: bool,
     pub thunder_time: i32,
+    /// Total age of the world in ticks
     pub time: i64,
     pub version: i32,
-    pub version_info: Version,
+    /// The game version the world was last saved with. Only present in worlds saved since 1.9.
+    pub version_info: Option<Version>,
     pub wandering_trader_id: Array<i32>,
     pub wandering_trader_spawn_chance: i32,
     pub wandering_trader_spawn_delay: i32,
     pub was_modded: bool,
 }
 
+impl LevelDat {
+    /// The world seed. The seed is read from `WorldGenSettings` and falls back to the legacy `RandomSeed`.
+    pub fn seed(&self) -> Option<i64> {
+        self.world_gen_settings
+            .as_ref()
+            .map(|world_gen_settings| world_gen_settings.seed)
+            .or(self.random_seed)
+    }
+
+    /// The name of the Minecraft release this was last saved with.
+    pub fn version_name(&self) -> Option<&'static str> {
+        crate::data_version::version_name(self.data_version)
+    }
+
+    /// The game version the world was last saved with, e.g. to get the exact name of a snapshot.
+    /// Worlds saved before 1.9 do not store the version.
+    pub fn game_version(&self) -> Option<&Version> {
+        self.version_info.as_ref()
+    }
+
+    /// The date and time when the world was last played.
+    #[cfg(feature = "chrono")]
+    pub fn last_played_datetime(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        chrono::DateTime::from_timestamp_millis(self.last_played)
+    }
+
+    /// Returns `true` if the world is locked to hard difficulty and players can not respawn.
+    pub fn is_hardcore(&self) -> bool {
+        self.hardcore
+    }
+
+    /// The difficulty of the world.
+    pub fn difficulty(&self) -> Difficulty {
+        Difficulty::from(self.difficulty)
+    }
+
+    /// The game mode new players join with.
+    pub fn default_gamemode(&self) -> GameMode {
+        GameMode::from(self.game_type)
+    }
+
+    /// The state of the fight against the ender dragon.
+    /// Falls back to the dimension data of the End used before 1.16.
+    pub fn dragon_fight(&self) -> Option<DragonFight> {
+        if let Some(dragon_fight) = &self.dragon_fight {
+            return Some(dragon_fight.clone());
+        }
+        self.dimension_data
+            .as_ref()?
+            .get("1")?
+            .get("DragonFight")
+            .cloned()
+            .and_then(|dragon_fight| DragonFight::try_from(dragon_fight).ok())
+    }
+
+    /// The game rules of the world.
+    pub fn game_rules(&self) -> GameRules<'_> {
+        GameRules::from(&self.game_rules)
+    }
+}
+
+/// Typed access to game rules. Minecraft stores the value of every game rule as a string.
+/// https://minecraft.fandom.com/wiki/Game_rule
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GameRules<'a>(&'a HashMap<String, String>);
+
+impl<'a> GameRules<'a> {
+    /// The raw value of a game rule. `None` if the game rule is not set.
+    pub fn get(&self, name: &str) -> Option<&'a str> {
+        self.0.get(name).map(String::as_str)
+    }
+
+    /// The value of a boolean game rule e.g. `keepInventory`.
+    /// `None` if the game rule is not set or not a boolean.
+    pub fn get_bool(&self, name: &str) -> Option<bool> {
+        match self.get(name)? {
+            "true" => Some(true),
+            "false" => Some(false),
+            _ => None,
+        }
+    }
+
+    /// The value of a numeric game rule e.g. `maxEntityCramming`.
+    /// `None` if the game rule is not set or not a number.
+    pub fn get_int(&self, name: &str) -> Option<i32> {
+        self.get(name)?.parse().ok()
+    }
+}
+
+impl<'a> From<&'a HashMap<String, String>> for GameRules<'a> {
+    fn from(game_rules: &'a HashMap<String, String>) -> Self {
+        Self(game_rules)
+    }
+}
+
+/// https://minecraft.fandom.com/wiki/Difficulty
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Difficulty {
+    Peaceful,
+    Easy,
+    Normal,
+    Hard,
+    /// A value not used by vanilla Minecraft
+    Unknown(i8),
+}
+
+impl From<i8> for Difficulty {
+    fn from(value: i8) -> Self {
+        match value {
+            0 => Self::Peaceful,
+            1 => Self::Easy,
+            2 => Self::Normal,
+            3 => Self::Hard,
+            _ => Self::Unknown(value),
+        }
+    }
+}
+
+/// https://minecraft.fandom.com/wiki/Game_mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameMode {
+    Survival,
+    Creative,
+    Adventure,
+    Spectator,
+    /// A value not used by vanilla Minecraft
+    Unknown(i32),
+}
+
+impl From<i32> for GameMode {
+    fn from(value: i32) -> Self {
+        match value {
+            0 => Self::Survival,
+            1 => Self::Creative,
+            2 => Self::Adventure,
+            3 => Self::Spectator,
+            _ => Self::Unknown(value),
+        }
+    }
+}
+
 /// https://minecraft.fandom.com/wiki/Java_Edition_level_format#level.dat_format
 #[derive(Debug, Builder, PartialEq)]
 pub struct CustomBossEvent {
@@ -93,10 +241,37 @@ pub struct WorldGenSettings {
     pub dimensions: HashMap<String, Dimension>,
 }
 
+/// The state of the fight against the ender dragon.
+/// https://minecraft.fandom.com/wiki/Java_Edition_level_format#level.dat_format
+#[derive(Debug, Builder, Clone, PartialEq)]
+pub struct DragonFight {
+    /// Angles of the End gateways that are created by the next dragon kills. Each kill removes one entry.
+    pub gateways: List<i32>,
+    pub dragon_killed: bool,
+    pub previously_killed: bool,
+    /// The UUID of the current dragon. Only stored in this format since 1.16.
+    pub dragon: Option<Array<i32>>,
+}
+
+impl DragonFight {
+    /// The number of End gateways created by killing the dragon in vanilla Minecraft.
+    pub const MAX_GATEWAYS: usize = 20;
+
+    /// The number of End gateways that can still be created by killing the dragon.
+    /// Once this reaches zero no new gateways to the outer End are generated.
+    pub fn gateways_remaining(&self) -> usize {
+        self.gateways.len()
+    }
+}
+
+/// The game version a world was saved with.
 #[derive(Debug, Builder, PartialEq)]
 pub struct Version {
+    /// The data version of the game
     pub id: i32,
+    /// The version name as shown in the launcher e.g. `1.21` or `24w14a`.
     pub name: String,
-    pub series: String,
+    /// `main` for the Java Edition. Only present since 1.18.
+    pub series: Option<String>,
     pub snapshot: bool,
 }
@@ -5,3 +5,4 @@ pub mod anvil;
 #[cfg(feature = "level_dat")]
 pub mod level_dat;
 pub mod player_dat;
+pub mod player_stats;
@@ -1,5 +1,10 @@
 //! The player.dat file format
 
+use std::{
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+};
+
 use jbe::Builder;
 
 use crate::{
@@ -7,7 +12,7 @@ use crate::{
         entity::{Entity, Mob},
         item::{Item, ItemWithSlot},
     },
-    nbt::{Array, List},
+    nbt::{Array, List, Tag},
 };
 
 /// Information about the player.
@@ -39,7 +44,10 @@ pub struct Player {
     pub root_vehicle: Option<RootVehicle>,
     pub score: i32,
     pub seen_credits: bool,
+    /// A copy of the item in the selected hotbar slot, written by the game when the player is saved.
+    /// The item itself is stored in the inventory. Use [`Player::held_item`] to look it up.
     pub selected_item: Option<Item>,
+    /// The selected hotbar slot (0-8).
     pub selected_item_slot: i32,
     pub shoulder_entity_left: Option<Entity>,
     pub shoulder_entity_right: Option<Entity>,
@@ -54,6 +62,139 @@ pub struct Player {
     pub xp_p: f32,
     pub xp_seed: i32,
     pub xp_total: i32,
+    /// Top level tags that are not read into any other field, e.g. tags added in newer versions of Minecraft.
+    pub extra: HashMap<String, Tag>,
+}
+
+impl Player {
+    /// The name of the Minecraft release this was last saved with.
+    pub fn version_name(&self) -> Option<&'static str> {
+        crate::data_version::version_name(self.data_version)
+    }
+
+    /// All items held by the player. This includes the inventory with armor and offhand slots, the ender chest and the selected item.
+    /// The selected item is only included if it is not already part of the inventory.
+    /// Shoulder entities do not carry items and are therefore not included.
+    /// If `include_contained_items` is set, items stored inside of other items (e.g. shulker boxes) are included as well.
+    pub fn all_items(&self, include_contained_items: bool) -> Vec<Cow<'_, Item>> {
+        let selected_item = self.selected_item.as_ref().filter(|_| {
+            !self
+                .inventory
+                .iter()
+                .any(|item| i32::from(item.slot) == self.selected_item_slot)
+        });
+        self.inventory
+            .iter()
+            .chain(self.ender_items.iter())
+            .map(|item| &item.item)
+            .chain(selected_item)
+            .fold(Vec::new(), |mut items, item| {
+                items.push(Cow::Borrowed(item));
+                if include_contained_items {
+                    push_contained_items(item, &mut items);
+                }
+                items
+            })
+    }
+
+    /// The item in the selected hotbar slot, i.e. the item the player was holding when the player was saved.
+    /// The item is looked up in the inventory using `selected_item_slot`. Returns `None` if the selected slot is empty.
+    pub fn held_item(&self) -> Option<&Item> {
+        self.inventory
+            .iter()
+            .find(|item| i32::from(item.slot) == self.selected_item_slot)
+            .map(|item| &item.item)
+    }
+
+    /// The spawn point of the player.
+    /// Some versions store all coordinates as zero together with an empty dimension if the player has no spawn point.
+    /// This combination is treated as unset and `None` is returned.
+    pub fn spawn_point(&self) -> Option<SpawnPoint> {
+        if self.spawn_dimension.is_empty()
+            && self.spawn_x == 0
+            && self.spawn_y == 0
+            && self.spawn_z == 0
+        {
+            return None;
+        }
+        Some(SpawnPoint {
+            x: self.spawn_x,
+            y: self.spawn_y,
+            z: self.spawn_z,
+            dimension: self.spawn_dimension.clone(),
+            forced: self.spawn_forced,
+        })
+    }
+
+    /// The health of the player in half hearts. Players without stored health have full health.
+    pub fn health(&self) -> f32 {
+        self.mob.health.unwrap_or(20.)
+    }
+
+    /// Extra health granted by absorption, e.g. from golden apples.
+    pub fn absorption_amount(&self) -> f32 {
+        self.mob.absorption_amount.unwrap_or_default()
+    }
+
+    /// Ticks the player still flashes red after being hurt.
+    pub fn hurt_time(&self) -> i16 {
+        self.mob.hurt_time.unwrap_or_default()
+    }
+
+    /// Ticks since the player died. This is 0 while the player is alive.
+    pub fn death_time(&self) -> i16 {
+        self.mob.death_time.unwrap_or_default()
+    }
+
+    /// Ticks of air left while under water. Players start with 300 ticks.
+    pub fn air(&self) -> i16 {
+        self.mob.entity.air.unwrap_or(300)
+    }
+
+    /// Returns `true` if the player has no health left.
+    pub fn is_dead(&self) -> bool {
+        self.health() <= 0.
+    }
+
+    /// The number of main inventory slots (hotbar included) holding an item.
+    /// Armor and offhand slots are not counted.
+    pub fn used_inventory_slots(&self) -> u32 {
+        let used_slots: HashSet<i8> = self
+            .inventory
+            .iter()
+            .map(|item| item.slot)
+            .filter(|slot| MAIN_INVENTORY_SLOTS.contains(slot))
+            .collect();
+        used_slots.len() as u32
+    }
+
+    /// The number of empty main inventory slots (hotbar included).
+    /// Armor and offhand slots are not counted.
+    pub fn empty_inventory_slots(&self) -> u32 {
+        MAIN_INVENTORY_SLOTS.len() as u32 - self.used_inventory_slots()
+    }
+}
+
+/// Slot ids of the hotbar (0-8) and the main inventory (9-35).
+/// Armor (100-103) and the offhand (-106) use ids outside of this range.
+const MAIN_INVENTORY_SLOTS: std::ops::RangeInclusive<i8> = 0..=35;
+
+/// The spawn point of a player, usually set by a bed or respawn anchor.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpawnPoint {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+    pub dimension: String,
+    /// The player spawns at this point even if the bed or respawn anchor is missing.
+    pub forced: bool,
+}
+
+fn push_contained_items(item: &Item, items: &mut Vec<Cow<'_, Item>>) {
+    item.contained_items().into_iter().for_each(|contained| {
+        push_contained_items(&contained.item, items);
+        items.push(Cow::Owned(contained.item));
+    });
 }
 
 #[derive(Debug, Builder, PartialEq)]
@@ -83,19 +224,95 @@ pub struct RecipeBook {
     pub is_smoker_gui_open: bool,
 }
 
+/// A recipe book gui of a crafting or smelting screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecipeBookGui {
+    Crafting,
+    Furnace,
+    BlastFurnace,
+    Smoker,
+}
+
+impl RecipeBook {
+    /// All recipe book guis that are currently open.
+    pub fn open_guis(&self) -> Vec<RecipeBookGui> {
+        [
+            (self.is_gui_open, RecipeBookGui::Crafting),
+            (self.is_furnace_gui_open, RecipeBookGui::Furnace),
+            (
+                self.is_blasting_furnace_gui_open,
+                RecipeBookGui::BlastFurnace,
+            ),
+            (self.is_smoker_gui_open, RecipeBookGui::Smoker),
+        ]
+        .into_iter()
+        .filter_map(|(open, gui)| open.then_some(gui))
+        .collect()
+    }
+}
+
+/// The vehicle the player was riding when the player was saved.
 #[derive(Debug, Builder, PartialEq)]
 pub struct RootVehicle {
+    /// The bottom-most vehicle. Other passengers of the vehicle are stored in its `passengers`.
     pub entity: Entity,
+    /// The UUID of the entity the player is directly riding.
     pub attach: Array<i32>,
 }
 
+impl RootVehicle {
+    /// The id of the bottom-most vehicle e.g. `minecraft:horse`, `minecraft:oak_boat` or `minecraft:minecart`.
+    pub fn vehicle_id(&self) -> Option<&str> {
+        self.entity.id.as_deref()
+    }
+
+    /// The entity the player is directly riding.
+    /// The vehicle and its passengers are searched for the UUID in `attach`.
+    /// If no entity has this UUID the bottom-most vehicle is returned.
+    pub fn mount(&self) -> &Entity {
+        find_entity_by_uuid(&self.entity, &self.attach).unwrap_or(&self.entity)
+    }
+}
+
+fn find_entity_by_uuid<'a>(entity: &'a Entity, uuid: &Array<i32>) -> Option<&'a Entity> {
+    if entity.uuid.as_ref() == Some(uuid) {
+        return Some(entity);
+    }
+    entity
+        .passengers
+        .iter()
+        .flat_map(|passengers| passengers.iter())
+        .find_map(|passenger| find_entity_by_uuid(passenger, uuid))
+}
+
+/// Tracks how often the player triggered sculk shriekers.
+/// [Minecraft Wiki](https://minecraft.fandom.com/wiki/Warden#Spawning)
 #[derive(Debug, Builder, PartialEq)]
 pub struct WardenSpawnTracker {
+    /// Ticks until a sculk shrieker can warn the player again
     pub cooldown_ticks: i32,
+    /// Ticks since the last warning. The warning level decreases after 12000 ticks without a warning.
     pub ticks_since_last_warning: i32,
     pub warning_level: i32,
 }
 
+impl WardenSpawnTracker {
+    /// The highest warning stage. The next warning at this stage summons a warden.
+    pub const MAX_WARNING_STAGE: u8 = 3;
+
+    /// The number of warnings the player received, clamped to `0..=3`.
+    pub fn warning_stage(&self) -> u8 {
+        self.warning_level
+            .clamp(0, i32::from(Self::MAX_WARNING_STAGE)) as u8
+    }
+
+    /// Returns `true` if the next sculk shrieker the player triggers summons a warden.
+    /// Shriekers triggered during the cooldown are ignored.
+    pub fn next_warning_summons_warden(&self) -> bool {
+        self.warning_stage() == Self::MAX_WARNING_STAGE && self.cooldown_ticks <= 0
+    }
+}
+
 #[derive(Debug, Builder, PartialEq)]
 pub struct PlayerAbilities {
     pub flying: bool,
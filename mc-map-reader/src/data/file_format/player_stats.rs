@@ -0,0 +1,199 @@
+//! The player statistics file format.
+//! Statistics are stored as JSON in `stats/<uuid>.json` of the save directory.
+
+use std::collections::HashMap;
+
+use serde_json::Value;
+use thiserror::Error;
+
+/// Statistics of a single player, grouped by category e.g. `minecraft:mined` and the id of the block, item or entity.
+/// [Minecraft Wiki](https://minecraft.fandom.com/wiki/Statistics#Statistic_types_and_names)
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct PlayerStats {
+    /// The version of the data. Files saved before 1.13 do not store it.
+    pub data_version: Option<i32>,
+    /// Category → id → value
+    pub stats: HashMap<String, HashMap<String, u64>>,
+}
+
+/// Errors that can occur while reading the statistics of a player.
+#[derive(Error, Debug, PartialEq)]
+pub enum PlayerStatsError {
+    /// The file has no `stats` object.
+    #[error("Missing stats object")]
+    MissingStats,
+    /// A category is not an object of ids.
+    #[error("Category {0} is not an object")]
+    InvalidCategory(String),
+    /// A value is not a non-negative integer.
+    #[error("Value of {0} {1} is not a valid number")]
+    InvalidValue(String, String),
+}
+
+impl PlayerStats {
+    /// Get a single statistic e.g. `get("minecraft:mined", "minecraft:stone")`.
+    /// The namespace of the category and id may be omitted. Missing statistics are 0.
+    pub fn get(&self, category: &str, id: &str) -> u64 {
+        self.stats
+            .get(namespaced(category).as_str())
+            .and_then(|category| category.get(namespaced(id).as_str()))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// All statistics of a category. The namespace may be omitted.
+    pub fn category(&self, category: &str) -> Option<&HashMap<String, u64>> {
+        self.stats.get(namespaced(category).as_str())
+    }
+
+    /// How often the player mined a block.
+    pub fn mined(&self, id: &str) -> u64 {
+        self.get("minecraft:mined", id)
+    }
+
+    /// How often the player used an item.
+    pub fn used(&self, id: &str) -> u64 {
+        self.get("minecraft:used", id)
+    }
+
+    /// How often the player picked up an item.
+    pub fn picked_up(&self, id: &str) -> u64 {
+        self.get("minecraft:picked_up", id)
+    }
+
+    /// A statistic of the `minecraft:custom` category e.g. `minecraft:deaths`.
+    pub fn custom(&self, id: &str) -> u64 {
+        self.get("minecraft:custom", id)
+    }
+
+    /// The time the player has played in ticks.
+    /// Before 1.17 the statistic was called `minecraft:play_one_minute` even though it counted ticks.
+    pub fn playtime_ticks(&self) -> u64 {
+        self.custom("minecraft:play_time")
+            .max(self.custom("minecraft:play_one_minute"))
+    }
+
+    /// Add the statistics of another player to this one, e.g. to get the totals of a server.
+    /// The newer data version is kept.
+    pub fn merge(&mut self, other: &PlayerStats) {
+        self.data_version = self.data_version.max(other.data_version);
+        other.stats.iter().for_each(|(category, values)| {
+            let category = self.stats.entry(category.clone()).or_default();
+            values.iter().for_each(|(id, value)| {
+                let total = category.entry(id.clone()).or_default();
+                *total = total.saturating_add(*value);
+            });
+        });
+    }
+}
+
+impl TryFrom<Value> for PlayerStats {
+    type Error = PlayerStatsError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        let data_version = value
+            .get("DataVersion")
+            .and_then(Value::as_i64)
+            .and_then(|data_version| i32::try_from(data_version).ok());
+        let Some(Value::Object(categories)) = value.get("stats") else {
+            return Err(PlayerStatsError::MissingStats);
+        };
+        let stats = categories
+            .iter()
+            .map(|(category, values)| {
+                let Value::Object(values) = values else {
+                    return Err(PlayerStatsError::InvalidCategory(category.clone()));
+                };
+                let values = values
+                    .iter()
+                    .map(|(id, value)| {
+                        value.as_u64().map(|value| (id.clone(), value)).ok_or_else(|| {
+                            PlayerStatsError::InvalidValue(category.clone(), id.clone())
+                        })
+                    })
+                    .collect::<Result<_, _>>()?;
+                Ok((category.clone(), values))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            data_version,
+            stats,
+        })
+    }
+}
+
+fn namespaced(id: &str) -> String {
+    if id.contains(':') {
+        id.to_string()
+    } else {
+        format!("minecraft:{id}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use test_case::test_case;
+
+    use super::{PlayerStats, PlayerStatsError};
+
+    fn stats() -> PlayerStats {
+        PlayerStats::try_from(json!({
+            "stats": {
+                "minecraft:mined": { "minecraft:stone": 120, "minecraft:diamond_ore": 4 },
+                "minecraft:used": { "minecraft:diamond_pickaxe": 124 },
+                "minecraft:custom": { "minecraft:play_time": 72000, "minecraft:deaths": 2 }
+            },
+            "DataVersion": 3953
+        }))
+        .expect("Error parsing stats")
+    }
+
+    #[test]
+    fn test_parse_player_stats() {
+        let stats = stats();
+        assert_eq!(stats.data_version, Some(3953));
+        assert_eq!(stats.mined("minecraft:diamond_ore"), 4);
+        assert_eq!(stats.mined("stone"), 120);
+        assert_eq!(stats.used("minecraft:diamond_pickaxe"), 124);
+        assert_eq!(stats.custom("minecraft:deaths"), 2);
+        assert_eq!(stats.playtime_ticks(), 72000);
+        assert_eq!(stats.picked_up("minecraft:diamond"), 0);
+        assert_eq!(stats.category("custom").map(|custom| custom.len()), Some(2));
+    }
+
+    #[test]
+    fn test_playtime_ticks_before_1_17() {
+        let stats = PlayerStats::try_from(json!({
+            "stats": { "minecraft:custom": { "minecraft:play_one_minute": 1200 } }
+        }))
+        .expect("Error parsing stats");
+        assert_eq!(stats.data_version, None);
+        assert_eq!(stats.playtime_ticks(), 1200);
+    }
+
+    #[test_case(json!({}) => PlayerStatsError::MissingStats; "Missing stats")]
+    #[test_case(json!({ "stats": { "minecraft:mined": 1 } }) => PlayerStatsError::InvalidCategory("minecraft:mined".to_string()); "Invalid category")]
+    #[test_case(json!({ "stats": { "minecraft:mined": { "minecraft:stone": -1 } } }) => PlayerStatsError::InvalidValue("minecraft:mined".to_string(), "minecraft:stone".to_string()); "Negative value")]
+    fn test_parse_invalid_player_stats(value: serde_json::Value) -> PlayerStatsError {
+        PlayerStats::try_from(value).expect_err("Stats should be invalid")
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut total = stats();
+        let other = PlayerStats::try_from(json!({
+            "stats": {
+                "minecraft:mined": { "minecraft:stone": 30 },
+                "minecraft:killed": { "minecraft:zombie": 5 }
+            },
+            "DataVersion": 4189
+        }))
+        .expect("Error parsing stats");
+        total.merge(&other);
+        assert_eq!(total.data_version, Some(4189));
+        assert_eq!(total.mined("minecraft:stone"), 150);
+        assert_eq!(total.mined("minecraft:diamond_ore"), 4);
+        assert_eq!(total.get("killed", "zombie"), 5);
+    }
+}
@@ -0,0 +1,356 @@
+//! Firework rockets and firework stars.
+
+use std::collections::HashMap;
+
+use super::item::Item;
+use crate::nbt::Tag;
+
+/// Shapes of firework explosions in the order of their legacy numeric ids.
+const SHAPES: [(FireworkShape, &str); 5] = [
+    (FireworkShape::SmallBall, "small_ball"),
+    (FireworkShape::LargeBall, "large_ball"),
+    (FireworkShape::Star, "star"),
+    (FireworkShape::Creeper, "creeper"),
+    (FireworkShape::Burst, "burst"),
+];
+
+/// The content of a firework rocket.
+/// [Minecraft Wiki](https://minecraft.fandom.com/wiki/Firework_Rocket#Item_data)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Firework {
+    /// The flight duration as set by the amount of gunpowder used to craft the rocket
+    pub flight_duration: i8,
+    pub explosions: Vec<FireworkExplosion>,
+}
+
+/// A single explosion of a firework rocket or firework star.
+/// [Minecraft Wiki](https://minecraft.fandom.com/wiki/Firework_Star#Item_data)
+#[derive(Debug, Clone, PartialEq)]
+pub struct FireworkExplosion {
+    pub shape: FireworkShape,
+    /// RGB colors of the explosion
+    pub colors: Vec<i32>,
+    /// RGB colors the explosion fades to
+    pub fade_colors: Vec<i32>,
+    pub trail: bool,
+    pub flicker: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FireworkShape {
+    SmallBall,
+    LargeBall,
+    Star,
+    Creeper,
+    Burst,
+}
+
+impl Firework {
+    /// The highest flight duration that can be crafted in vanilla Minecraft.
+    pub const MAX_FLIGHT_DURATION: i8 = 3;
+
+    /// Get the content of a firework rocket.
+    /// The `minecraft:fireworks` component takes precedence over the legacy item tag.
+    pub fn from_item(item: &Item) -> Option<Self> {
+        if item.id != "minecraft:firework_rocket" {
+            return None;
+        }
+        let component = item
+            .components
+            .as_ref()
+            .and_then(|components| components.get("minecraft:fireworks"));
+        if let Some(Tag::Compound(fireworks)) = component {
+            return Some(Self::from_compound(
+                fireworks,
+                "flight_duration",
+                "explosions",
+            ));
+        }
+        let legacy = item
+            .tag
+            .as_ref()
+            .and_then(|tag| match tag.get("Fireworks") {
+                Some(Tag::Compound(fireworks)) => Some(fireworks),
+                _ => None,
+            });
+        Some(match legacy {
+            Some(fireworks) => Self::from_compound(fireworks, "Flight", "Explosions"),
+            None => Self {
+                flight_duration: 0,
+                explosions: Vec::new(),
+            },
+        })
+    }
+
+    /// Returns `true` if the flight duration can not be crafted in vanilla Minecraft.
+    /// Such rockets are usually created by editing NBT data.
+    pub fn exceeds_flight_limit(&self) -> bool {
+        !(0..=Self::MAX_FLIGHT_DURATION).contains(&self.flight_duration)
+    }
+
+    fn from_compound(
+        fireworks: &HashMap<String, Tag>,
+        flight_key: &str,
+        explosions_key: &str,
+    ) -> Self {
+        let flight_duration = match fireworks.get(flight_key) {
+            Some(Tag::Byte(flight_duration)) => *flight_duration,
+            _ => 0,
+        };
+        let explosions = match fireworks.get(explosions_key) {
+            Some(Tag::List(explosions)) => explosions
+                .iter()
+                .filter_map(FireworkExplosion::from_tag)
+                .collect(),
+            _ => Vec::new(),
+        };
+        Self {
+            flight_duration,
+            explosions,
+        }
+    }
+}
+
+impl FireworkExplosion {
+    /// Get the explosion of a firework star.
+    /// The `minecraft:firework_explosion` component takes precedence over the legacy item tag.
+    pub fn from_item(item: &Item) -> Option<Self> {
+        if item.id != "minecraft:firework_star" {
+            return None;
+        }
+        item.components
+            .as_ref()
+            .and_then(|components| components.get("minecraft:firework_explosion"))
+            .or_else(|| item.tag.as_ref().and_then(|tag| tag.get("Explosion")))
+            .and_then(Self::from_tag)
+    }
+
+    /// Parse an explosion stored with numeric shapes and capitalized keys or with named shapes and lowercase keys.
+    fn from_tag(tag: &Tag) -> Option<Self> {
+        let Tag::Compound(explosion) = tag else {
+            return None;
+        };
+        let shape = match explosion.get("Type").or_else(|| explosion.get("shape")) {
+            Some(Tag::Byte(id)) => FireworkShape::from_id(*id)?,
+            Some(Tag::String(name)) => FireworkShape::from_name(name)?,
+            // Explosions without a type are small balls
+            None => FireworkShape::SmallBall,
+            _ => return None,
+        };
+        let colors = |legacy_key: &str, key: &str| match explosion
+            .get(legacy_key)
+            .or_else(|| explosion.get(key))
+        {
+            Some(Tag::IntArray(colors)) => colors.to_vec(),
+            _ => Vec::new(),
+        };
+        let flag = |legacy_key: &str, key: &str| {
+            matches!(
+                explosion.get(legacy_key).or_else(|| explosion.get(key)),
+                Some(Tag::Byte(value)) if *value != 0
+            )
+        };
+        Some(Self {
+            shape,
+            colors: colors("Colors", "colors"),
+            fade_colors: colors("FadeColors", "fade_colors"),
+            trail: flag("Trail", "has_trail"),
+            flicker: flag("Flicker", "has_twinkle"),
+        })
+    }
+}
+
+impl FireworkShape {
+    /// Get a shape by its legacy numeric id.
+    pub fn from_id(id: i8) -> Option<Self> {
+        usize::try_from(id)
+            .ok()
+            .and_then(|id| SHAPES.get(id))
+            .map(|(shape, _)| *shape)
+    }
+
+    /// Get a shape by its name e.g. `large_ball`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        SHAPES
+            .iter()
+            .find(|(_, shape_name)| *shape_name == name)
+            .map(|(shape, _)| *shape)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use test_case::test_case;
+
+    use super::{Firework, FireworkExplosion, FireworkShape};
+    use crate::{
+        data::item::Item,
+        nbt::{Array, List, Tag},
+        test_util::compound,
+    };
+
+    fn firework_item(
+        tag: Option<HashMap<String, Tag>>,
+        components: Option<HashMap<String, Tag>>,
+    ) -> Item {
+        Item {
+            id: "minecraft:firework_rocket".to_string(),
+            tag,
+            components,
+            count: 1,
+        }
+    }
+
+    fn explosions() -> Vec<FireworkExplosion> {
+        vec![
+            FireworkExplosion {
+                shape: FireworkShape::Creeper,
+                colors: vec![0x00ff00],
+                fade_colors: vec![],
+                trail: true,
+                flicker: false,
+            },
+            FireworkExplosion {
+                shape: FireworkShape::LargeBall,
+                colors: vec![0xff0000, 0x0000ff],
+                fade_colors: vec![0xffffff],
+                trail: false,
+                flicker: true,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_from_item_legacy() {
+        let item = firework_item(
+            Some(HashMap::from([(
+                "Fireworks".to_string(),
+                compound(vec![
+                    ("Flight", Tag::Byte(2)),
+                    (
+                        "Explosions",
+                        Tag::List(List::from(vec![
+                            compound(vec![
+                                ("Type", Tag::Byte(3)),
+                                ("Colors", Tag::IntArray(Array::from(vec![0x00ff00]))),
+                                ("Trail", Tag::Byte(1)),
+                            ]),
+                            compound(vec![
+                                ("Type", Tag::Byte(1)),
+                                (
+                                    "Colors",
+                                    Tag::IntArray(Array::from(vec![0xff0000, 0x0000ff])),
+                                ),
+                                ("FadeColors", Tag::IntArray(Array::from(vec![0xffffff]))),
+                                ("Flicker", Tag::Byte(1)),
+                            ]),
+                        ])),
+                    ),
+                ]),
+            )])),
+            None,
+        );
+        assert_eq!(
+            Firework::from_item(&item),
+            Some(Firework {
+                flight_duration: 2,
+                explosions: explosions(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_from_item_components() {
+        let item = firework_item(
+            None,
+            Some(HashMap::from([(
+                "minecraft:fireworks".to_string(),
+                compound(vec![
+                    ("flight_duration", Tag::Byte(2)),
+                    (
+                        "explosions",
+                        Tag::List(List::from(vec![
+                            compound(vec![
+                                ("shape", Tag::String("creeper".to_string())),
+                                ("colors", Tag::IntArray(Array::from(vec![0x00ff00]))),
+                                ("has_trail", Tag::Byte(1)),
+                            ]),
+                            compound(vec![
+                                ("shape", Tag::String("large_ball".to_string())),
+                                (
+                                    "colors",
+                                    Tag::IntArray(Array::from(vec![0xff0000, 0x0000ff])),
+                                ),
+                                ("fade_colors", Tag::IntArray(Array::from(vec![0xffffff]))),
+                                ("has_twinkle", Tag::Byte(1)),
+                            ]),
+                        ])),
+                    ),
+                ]),
+            )])),
+        );
+        assert_eq!(
+            Firework::from_item(&item),
+            Some(Firework {
+                flight_duration: 2,
+                explosions: explosions(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_from_item_without_explosions() {
+        let item = firework_item(
+            Some(HashMap::from([(
+                "Fireworks".to_string(),
+                compound(vec![
+                    ("Flight", Tag::Byte(1)),
+                    ("Explosions", Tag::List(List::from(vec![]))),
+                ]),
+            )])),
+            None,
+        );
+        assert_eq!(
+            Firework::from_item(&item),
+            Some(Firework {
+                flight_duration: 1,
+                explosions: vec![],
+            })
+        );
+    }
+
+    #[test]
+    fn test_firework_star() {
+        let item = Item {
+            id: "minecraft:firework_star".to_string(),
+            tag: Some(HashMap::from([(
+                "Explosion".to_string(),
+                compound(vec![
+                    ("Type", Tag::Byte(3)),
+                    ("Colors", Tag::IntArray(Array::from(vec![0x00ff00]))),
+                    ("Trail", Tag::Byte(1)),
+                ]),
+            )])),
+            components: None,
+            count: 1,
+        };
+        assert_eq!(
+            FireworkExplosion::from_item(&item),
+            explosions().into_iter().next()
+        );
+    }
+
+    #[test_case(1 => false; "Crafted")]
+    #[test_case(3 => false; "At limit")]
+    #[test_case(127 => true; "Crash firework")]
+    #[test_case(-1 => true; "Negative")]
+    fn test_exceeds_flight_limit(flight_duration: i8) -> bool {
+        Firework {
+            flight_duration,
+            explosions: Vec::new(),
+        }
+        .exceeds_flight_limit()
+    }
+}
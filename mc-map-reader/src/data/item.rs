@@ -1,8 +1,9 @@
-use std::collections::HashMap;
+use std::{borrow::Cow, collections::HashMap, hash::Hasher};
 
 use jbe::Builder;
 
-use crate::nbt::Tag;
+use super::trim::ArmorTrim;
+use crate::nbt::{List, Tag};
 
 /// Representation of an item.
 /// [Minecraft Wiki](https://minecraft.fandom.com/wiki/Player.dat_format#Item_structure)
@@ -11,10 +12,447 @@ pub struct Item {
     /// Internal item ID
     pub id: String,
     pub tag: Option<HashMap<String, Tag>>,
+    /// Data components used instead of `tag` since 1.20.5
+    pub components: Option<HashMap<String, Tag>>,
     /// Stack size
     pub count: i8,
 }
 
+impl Item {
+    /// Items stored inside of this item, e.g. the content of a shulker box.
+    /// The `minecraft:container` component takes precedence over the legacy `BlockEntityTag`.
+    pub fn contained_items(&self) -> Vec<ItemWithSlot> {
+        let container = self
+            .components
+            .as_ref()
+            .and_then(|components| components.get("minecraft:container"));
+        if let Some(Tag::List(container)) = container {
+            return container
+                .iter()
+                .filter_map(ItemWithSlot::from_container_entry)
+                .collect();
+        }
+        self.tag
+            .as_ref()
+            .and_then(|tag| tag.get("BlockEntityTag"))
+            .cloned()
+            .and_then(|block_entity_tag| block_entity_tag.get_as_map().ok())
+            .and_then(|mut block_entity_tag| block_entity_tag.remove("Items"))
+            .and_then(|items| List::<ItemWithSlot>::try_from(items).ok())
+            .map(List::take)
+            .unwrap_or_default()
+    }
+
+    /// The value of a data component e.g. `minecraft:fire_resistant`. The namespace may be omitted.
+    /// Items saved before 1.20.5 fall back to the equivalent key of the item tag for components that
+    /// replaced a tag, e.g. `Unbreakable` for `minecraft:unbreakable` or `display.color` for `minecraft:dyed_color`.
+    /// The legacy value is returned unchanged.
+    pub fn component(&self, name: &str) -> Option<&Tag> {
+        let name = if name.contains(':') {
+            Cow::Borrowed(name)
+        } else {
+            Cow::Owned(format!("minecraft:{name}"))
+        };
+        if let Some(component) = self
+            .components
+            .as_ref()
+            .and_then(|components| components.get(name.as_ref()))
+        {
+            return Some(component);
+        }
+        let (legacy_key, nested_keys) = LEGACY_TAG_KEYS
+            .iter()
+            .find(|(component, _)| *component == name)
+            .and_then(|(_, keys)| keys.split_first())?;
+        let value = self.tag.as_ref()?.get(*legacy_key)?;
+        nested_keys
+            .iter()
+            .try_fold(value, |value, key| match value {
+                Tag::Compound(value) => value.get(*key),
+                _ => None,
+            })
+    }
+
+    /// Returns `true` if the item has a data component. See [`Item::component`].
+    pub fn has_component(&self, name: &str) -> bool {
+        self.component(name).is_some()
+    }
+
+    /// Items stored inside of a bundle.
+    /// The `minecraft:bundle_contents` component takes precedence over the legacy `Items` tag.
+    pub fn bundle_contents(&self) -> Vec<Item> {
+        let contents = self
+            .components
+            .as_ref()
+            .and_then(|components| components.get("minecraft:bundle_contents"));
+        if let Some(Tag::List(contents)) = contents {
+            return contents.iter().filter_map(Item::from_item_stack).collect();
+        }
+        self.tag
+            .as_ref()
+            .and_then(|tag| tag.get("Items"))
+            .cloned()
+            .and_then(|items| List::<Item>::try_from(items).ok())
+            .map(List::take)
+            .unwrap_or_default()
+    }
+
+    /// The damage of the item. A higher value means lower durability.
+    pub fn damage(&self) -> Option<i32> {
+        self.get_int("minecraft:damage", "Damage")
+    }
+
+    /// The custom model data used by resource packs.
+    pub fn custom_model_data(&self) -> Option<i32> {
+        self.get_int("minecraft:custom_model_data", "CustomModelData")
+    }
+
+    /// The custom name of the item as plain text, e.g. set in an anvil.
+    /// The `minecraft:custom_name` component takes precedence over the legacy `display.Name` tag.
+    pub fn custom_name(&self) -> Option<String> {
+        let component = self
+            .components
+            .as_ref()
+            .and_then(|components| components.get("minecraft:custom_name"));
+        if let Some(name) = component {
+            return Some(super::text::plain_text(name));
+        }
+        match self.tag.as_ref()?.get("display")? {
+            Tag::Compound(display) => display.get("Name").map(super::text::plain_text),
+            _ => None,
+        }
+    }
+
+    /// The enchantments of the item as pairs of enchantment id and level, sorted by id.
+    /// Stored enchantments of enchanted books are not included.
+    /// Numeric enchantment ids used before 1.13 are ignored.
+    pub fn enchantments(&self) -> Vec<(String, i32)> {
+        let component = self
+            .components
+            .as_ref()
+            .and_then(|components| components.get("minecraft:enchantments"));
+        let mut enchantments = match component {
+            // Levels are wrapped in `levels` until 1.21.4
+            Some(Tag::Compound(enchantments)) => {
+                let levels = match enchantments.get("levels") {
+                    Some(Tag::Compound(levels)) => levels,
+                    _ => enchantments,
+                };
+                levels
+                    .iter()
+                    .filter_map(|(id, level)| match level {
+                        Tag::Int(level) => Some((id.clone(), *level)),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>()
+            }
+            _ => match self.tag.as_ref().and_then(|tag| tag.get("Enchantments")) {
+                Some(Tag::List(enchantments)) => enchantments
+                    .iter()
+                    .filter_map(|enchantment| {
+                        let Tag::Compound(enchantment) = enchantment else {
+                            return None;
+                        };
+                        let Some(Tag::String(id)) = enchantment.get("id") else {
+                            return None;
+                        };
+                        let level = match enchantment.get("lvl")? {
+                            Tag::Short(level) => i32::from(*level),
+                            Tag::Int(level) => *level,
+                            _ => return None,
+                        };
+                        Some((id.clone(), level))
+                    })
+                    .collect(),
+                _ => Vec::new(),
+            },
+        };
+        enchantments.sort();
+        enchantments
+    }
+
+    /// Returns `true` if the item never loses durability.
+    /// Unbreakable items can not be obtained in survival.
+    pub fn is_unbreakable(&self) -> bool {
+        if let Some(components) = &self.components {
+            if components.contains_key("minecraft:unbreakable") {
+                return true;
+            }
+        }
+        matches!(
+            self.tag.as_ref().and_then(|tag| tag.get("Unbreakable")),
+            Some(Tag::Byte(unbreakable)) if *unbreakable != 0
+        )
+    }
+
+    /// Forces the enchantment glint to be shown (`true`) or hidden (`false`).
+    /// `None` if the glint depends on the enchantments of the item. Only stored as a component since 1.20.5.
+    pub fn enchantment_glint_override(&self) -> Option<bool> {
+        match self
+            .components
+            .as_ref()?
+            .get("minecraft:enchantment_glint_override")?
+        {
+            Tag::Byte(glint) => Some(*glint != 0),
+            _ => None,
+        }
+    }
+
+    /// The amplifier of the Bad Omen effect given by an ominous bottle, starting at 0 for Bad Omen I.
+    /// `None` for items without the `minecraft:ominous_bottle_amplifier` component. Vanilla bottles have an
+    /// amplifier of at most [`MAX_OMINOUS_BOTTLE_AMPLIFIER`].
+    pub fn ominous_bottle_amplifier(&self) -> Option<i32> {
+        match self
+            .components
+            .as_ref()?
+            .get("minecraft:ominous_bottle_amplifier")?
+        {
+            Tag::Int(amplifier) => Some(*amplifier),
+            _ => None,
+        }
+    }
+
+    /// Blocks this item can be placed on in adventure mode. Block tags start with `#`.
+    /// Survival items never carry this restriction.
+    pub fn can_place_on(&self) -> Vec<String> {
+        self.adventure_mode_blocks("minecraft:can_place_on", "CanPlaceOn")
+    }
+
+    /// Blocks this item can break in adventure mode. Block tags start with `#`.
+    /// Survival items never carry this restriction.
+    pub fn can_destroy(&self) -> Vec<String> {
+        self.adventure_mode_blocks("minecraft:can_break", "CanDestroy")
+    }
+
+    /// The trim of a piece of armor.
+    pub fn armor_trim(&self) -> Option<ArmorTrim> {
+        ArmorTrim::from_item(self)
+    }
+
+    /// A stable hash of the item which can be used to find identical items in different places.
+    /// Two items share a fingerprint if they have the same id and identical `tag` and `components`.
+    /// The id is normalized to include the `minecraft` namespace.
+    /// The stack size is not part of the fingerprint, so split stacks of a duped item still match.
+    /// The hash does not depend on the order of compound entries and does not change between runs or builds.
+    pub fn fingerprint(&self) -> u64 {
+        let mut hasher = Fnv1aHasher::default();
+        let id = if self.id.contains(':') {
+            self.id.clone()
+        } else {
+            format!("minecraft:{}", self.id)
+        };
+        hash_str(&id, &mut hasher);
+        for compound in [&self.tag, &self.components] {
+            match compound {
+                Some(compound) => {
+                    hasher.write_u8(1);
+                    hash_compound(compound, &mut hasher);
+                }
+                None => hasher.write_u8(0),
+            }
+        }
+        hasher.finish()
+    }
+
+    /// Parse an item stack as stored in data components since 1.20.5.
+    /// The count is optional and defaults to 1.
+    fn from_item_stack(tag: &Tag) -> Option<Self> {
+        let Tag::Compound(item) = tag else {
+            return None;
+        };
+        let Some(Tag::String(id)) = item.get("id") else {
+            return None;
+        };
+        let count = match item.get("count") {
+            Some(Tag::Int(count)) => i8::try_from(*count).unwrap_or(i8::MAX),
+            Some(Tag::Byte(count)) => *count,
+            _ => 1,
+        };
+        let components = match item.get("components") {
+            Some(Tag::Compound(components)) => Some(components.clone()),
+            _ => None,
+        };
+        Some(Self {
+            id: id.clone(),
+            tag: None,
+            components,
+            count,
+        })
+    }
+
+    /// Read the block ids of an adventure mode predicate from the data components or fall back to the legacy
+    /// list of block ids in the item tag.
+    fn adventure_mode_blocks(&self, component: &str, tag: &str) -> Vec<String> {
+        let component = self
+            .components
+            .as_ref()
+            .and_then(|components| components.get(component));
+        if let Some(component) = component {
+            // A single predicate, a list of predicates or a list of predicates wrapped in `predicates`
+            let predicates = match component {
+                Tag::Compound(predicate) => match predicate.get("predicates") {
+                    Some(Tag::List(predicates)) => predicates.iter().collect(),
+                    _ => vec![component],
+                },
+                Tag::List(predicates) => predicates.iter().collect(),
+                _ => Vec::new(),
+            };
+            return predicates
+                .into_iter()
+                .flat_map(|predicate| match predicate {
+                    Tag::Compound(predicate) => strings(predicate.get("blocks")),
+                    _ => Vec::new(),
+                })
+                .collect();
+        }
+        strings(self.tag.as_ref().and_then(|item_tag| item_tag.get(tag)))
+    }
+
+    /// Get an integer from the data components or fall back to the legacy item tag.
+    fn get_int(&self, component: &str, tag: &str) -> Option<i32> {
+        self.components
+            .as_ref()
+            .and_then(|components| components.get(component))
+            .or_else(|| self.tag.as_ref().and_then(|item_tag| item_tag.get(tag)))
+            .and_then(|value| i32::try_from(value.clone()).ok())
+    }
+}
+
+/// The highest amplifier an ominous bottle can have in vanilla, Bad Omen V.
+pub const MAX_OMINOUS_BOTTLE_AMPLIFIER: i32 = 4;
+
+/// Data components and the path of the item tag key they replaced in 1.20.5.
+const LEGACY_TAG_KEYS: &[(&str, &[&str])] = &[
+    ("minecraft:can_break", &["CanDestroy"]),
+    ("minecraft:can_place_on", &["CanPlaceOn"]),
+    ("minecraft:custom_model_data", &["CustomModelData"]),
+    ("minecraft:damage", &["Damage"]),
+    ("minecraft:dyed_color", &["display", "color"]),
+    ("minecraft:enchantments", &["Enchantments"]),
+    ("minecraft:repair_cost", &["RepairCost"]),
+    ("minecraft:stored_enchantments", &["StoredEnchantments"]),
+    ("minecraft:trim", &["Trim"]),
+    ("minecraft:unbreakable", &["Unbreakable"]),
+];
+
+/// A single string or the strings of a list.
+fn strings(tag: Option<&Tag>) -> Vec<String> {
+    match tag {
+        Some(Tag::String(value)) => vec![value.clone()],
+        Some(Tag::List(values)) => values
+            .iter()
+            .filter_map(|value| match value {
+                Tag::String(value) => Some(value.clone()),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// 64 bit FNV-1a hash. Unlike `DefaultHasher` the output is guaranteed to stay the same.
+struct Fnv1aHasher(u64);
+
+impl Default for Fnv1aHasher {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for Fnv1aHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+}
+
+fn hash_str(value: &str, hasher: &mut impl Hasher) {
+    hasher.write(&(value.len() as u64).to_le_bytes());
+    hasher.write(value.as_bytes());
+}
+
+/// Hash the entries of a compound sorted by key.
+fn hash_compound(compound: &HashMap<String, Tag>, hasher: &mut impl Hasher) {
+    let mut entries = compound.iter().collect::<Vec<_>>();
+    entries.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+    hasher.write(&(entries.len() as u64).to_le_bytes());
+    for (key, value) in entries {
+        hash_str(key, hasher);
+        hash_tag(value, hasher);
+    }
+}
+
+/// Hash a tag including its type. All integers are written little endian.
+fn hash_tag(tag: &Tag, hasher: &mut impl Hasher) {
+    match tag {
+        Tag::End => hasher.write_u8(0),
+        Tag::Byte(value) => {
+            hasher.write_u8(1);
+            hasher.write(&value.to_le_bytes());
+        }
+        Tag::Short(value) => {
+            hasher.write_u8(2);
+            hasher.write(&value.to_le_bytes());
+        }
+        Tag::Int(value) => {
+            hasher.write_u8(3);
+            hasher.write(&value.to_le_bytes());
+        }
+        Tag::Long(value) => {
+            hasher.write_u8(4);
+            hasher.write(&value.to_le_bytes());
+        }
+        Tag::Float(value) => {
+            hasher.write_u8(5);
+            hasher.write(&value.to_bits().to_le_bytes());
+        }
+        Tag::Double(value) => {
+            hasher.write_u8(6);
+            hasher.write(&value.to_bits().to_le_bytes());
+        }
+        Tag::ByteArray(values) => {
+            hasher.write_u8(7);
+            hasher.write(&(values.len() as u64).to_le_bytes());
+            values
+                .iter()
+                .for_each(|value| hasher.write(&value.to_le_bytes()));
+        }
+        Tag::String(value) => {
+            hasher.write_u8(8);
+            hash_str(value, hasher);
+        }
+        Tag::List(values) => {
+            hasher.write_u8(9);
+            hasher.write(&(values.len() as u64).to_le_bytes());
+            values.iter().for_each(|value| hash_tag(value, hasher));
+        }
+        Tag::Compound(compound) => {
+            hasher.write_u8(10);
+            hash_compound(compound, hasher);
+        }
+        Tag::IntArray(values) => {
+            hasher.write_u8(11);
+            hasher.write(&(values.len() as u64).to_le_bytes());
+            values
+                .iter()
+                .for_each(|value| hasher.write(&value.to_le_bytes()));
+        }
+        Tag::LongArray(values) => {
+            hasher.write_u8(12);
+            hasher.write(&(values.len() as u64).to_le_bytes());
+            values
+                .iter()
+                .for_each(|value| hasher.write(&value.to_le_bytes()));
+        }
+    }
+}
+
 /// Representation of an item inside a slot. This type is used if something takes more than one item.
 #[derive(Debug, Builder, Clone, PartialEq)]
 pub struct ItemWithSlot {
@@ -23,3 +461,19 @@ pub struct ItemWithSlot {
     /// Item
     pub item: Item,
 }
+
+impl ItemWithSlot {
+    /// Parse an entry of the `minecraft:container` component.
+    fn from_container_entry(tag: &Tag) -> Option<Self> {
+        let Tag::Compound(entry) = tag else {
+            return None;
+        };
+        let Some(Tag::Int(slot)) = entry.get("slot") else {
+            return None;
+        };
+        Some(Self {
+            slot: i8::try_from(*slot).ok()?,
+            item: Item::from_item_stack(entry.get("item")?)?,
+        })
+    }
+}
@@ -115,7 +115,7 @@ mod_try_from_tag!(
         "TrackOutput" => set_track_output test(1i8 => track_output = true; CommandBlockBuilderError::UnsetTrackOutput),
     ],
     Conduit: [
-        "Target" => set_target test(Array::from(vec![10_i32,32]) => target = Array::from(vec![10_i32,32]); ConduitBuilderError::UnsetTarget),
+        "Target" => set_target test(Array::from(vec![10_i32,32]) => target = Some(Array::from(vec![10_i32,32]))),
     ],
     Dispenser: parse_inventory_block_entity ? [ ItemWithSlot, ],
     Dropper: parse_inventory_block_entity ? [ ItemWithSlot, ],
@@ -124,8 +124,8 @@ mod_try_from_tag!(
     ],
     EndGateway: [
         "Age" => set_age test(1i64 => age = 1; EndGatewayBuilderError::UnsetAge),
-        "ExactTeleport" => set_exact_teleport test(1i8 => exact_teleport = true; EndGatewayBuilderError::UnsetExactTeleport),
-        "ExitPortal" => set_exit_portal test(ExitPortal_test_data_provider() => exit_portal = ExitPortal_test_result(); EndGatewayBuilderError::UnsetExitPortal),
+        "ExactTeleport" => set_exact_teleport test(1i8 => exact_teleport = true),
+        "ExitPortal" => set_exit_portal test(ExitPortal_test_data_provider() => exit_portal = Some(ExitPortal_test_result())),
     ] ? [
         ExitPortal,
     ],
@@ -142,6 +142,8 @@ mod_try_from_tag!(
         "name" => set_name test("name".to_string() => name = "name".to_string(); JigsawBuilderError::UnsetName),
         "pool" => set_pool test("pool".to_string() => pool = "pool".to_string(); JigsawBuilderError::UnsetPool),
         "target" => set_target test("target".to_string() => target = "target".to_string(); JigsawBuilderError::UnsetTarget),
+        "placement_priority" => set_placement_priority test(1i32 => placement_priority = Some(1)),
+        "selection_priority" => set_selection_priority test(1i32 => selection_priority = Some(1)),
     ],
     Jukebox: [
         "IsPlaying" => set_is_playing test(1i8 => is_playing = true; JukeboxBuilderError::UnsetIsPlaying),
@@ -369,8 +371,12 @@ fn parse_block_entity(
             .try_into()
             .map(BlockEntityType::Piston)
             .map_err(|e| FieldError::new(ENTITY_TYPE_KEY, e))?,
-        "minecraft:shulker_box" => nbt_data
+        id if ShulkerBox::is_shulker_box_id(id) => nbt_data
             .try_into()
+            .map(|shulker_box: ShulkerBox| ShulkerBox {
+                color: ShulkerBox::color_from_id(id),
+                ..shulker_box
+            })
             .map(BlockEntityType::ShulkerBox)
             .map_err(|e| FieldError::new(ENTITY_TYPE_KEY, e))?,
         "minecraft:sign" => nbt_data
@@ -478,7 +484,7 @@ mod tests {
         parse_block_entity, parse_inventory_block_entity, BlastFurnaceBuilder, BlockEntityError,
     };
     use crate::{
-        data::{block_entity::*, load::block_entity::parse_hopper},
+        data::{block_entity::*, color::DyeColor, load::block_entity::parse_hopper},
         nbt::*,
     };
     use std::collections::HashMap;
@@ -679,6 +685,386 @@ mod tests {
         Ok(())
     }
 
+    #[test_case("minecraft:shulker_box" => None; "Default")]
+    #[test_case("minecraft:red_shulker_box" => Some(DyeColor::Red); "Red")]
+    #[test_case("minecraft:light_blue_shulker_box" => Some(DyeColor::LightBlue); "Light blue")]
+    fn test_parse_shulker_box(id: &str) -> Option<DyeColor> {
+        let items = List::from(vec![
+            crate::data::load::item::tests::item_with_slot_test_data_provider().into(),
+            crate::test_util::with(
+                crate::data::load::item::tests::item_with_slot_test_data_provider(),
+                "Slot",
+                Tag::Byte(5),
+            )
+            .into(),
+        ]);
+        let nbt_data = crate::test_util::with(
+            crate::test_util::with(
+                block_entity_test_data_provider(),
+                "id",
+                id.to_string().into(),
+            ),
+            "Items",
+            Tag::List(items),
+        );
+        let block_entity = BlockEntity::try_from(nbt_data).expect("Error parsing shulker box");
+        assert_eq!(block_entity.id, id);
+        let BlockEntityType::ShulkerBox(shulker_box) = block_entity.entity_type else {
+            panic!("Expected shulker box");
+        };
+        let items = shulker_box.items.expect("Shulker box has no items");
+        assert_eq!(
+            items.iter().map(|item| item.slot).collect::<Vec<_>>(),
+            vec![0, 5]
+        );
+        assert!(items
+            .iter()
+            .all(|item| item.item == crate::data::load::item::macro_tests::Item_test_result()));
+        shulker_box.color
+    }
+
+    #[test_case("minecraft:barrel", inventory_block_test_data_provider() => Some(27); "Barrel")]
+    #[test_case("minecraft:blast_furnace", cooking_block_test_data_provider() => Some(3); "Blast furnace")]
+    #[test_case("minecraft:brewing_stand", BrewingStand_test_data_provider() => Some(5); "Brewing stand")]
+    #[test_case("minecraft:chest", inventory_block_test_data_provider() => Some(27); "Chest")]
+    #[test_case("minecraft:dispenser", inventory_block_test_data_provider() => Some(9); "Dispenser")]
+    #[test_case("minecraft:dropper", inventory_block_test_data_provider() => Some(9); "Dropper")]
+    #[test_case("minecraft:furnace", cooking_block_test_data_provider() => Some(3); "Furnace")]
+    #[test_case("minecraft:hopper", hopper_test_data_provider() => Some(5); "Hopper")]
+    #[test_case("minecraft:shulker_box", inventory_block_test_data_provider() => Some(27); "Shulker box")]
+    #[test_case("minecraft:smoker", cooking_block_test_data_provider() => Some(3); "Smoker")]
+    #[test_case("minecraft:trapped_chest", inventory_block_test_data_provider() => Some(27); "Trapped chest")]
+    #[test_case("minecraft:beacon", Beacon_test_data_provider() => None; "Beacon")]
+    fn test_container_slot_count(id: &str, data: HashMap<String, Tag>) -> Option<usize> {
+        let nbt_data = crate::test_util::merge(
+            crate::test_util::with(
+                block_entity_test_data_provider(),
+                "id",
+                id.to_string().into(),
+            ),
+            data,
+        );
+        let block_entity = BlockEntity::try_from(nbt_data).expect("Error parsing block entity");
+        block_entity
+            .as_container()
+            .map(|container| container.slot_count())
+    }
+
+    #[test_case(&[0, 26] => Vec::<i8>::new(); "In range")]
+    #[test_case(&[0, 27, 40] => vec![27, 40]; "Out of range")]
+    #[test_case(&[-1] => vec![-1]; "Negative slot")]
+    fn test_container_out_of_range_items(slots: &[i8]) -> Vec<i8> {
+        let items = slots
+            .iter()
+            .map(|slot| {
+                crate::test_util::with(
+                    crate::data::load::item::tests::item_with_slot_test_data_provider(),
+                    "Slot",
+                    Tag::Byte(*slot),
+                )
+                .into()
+            })
+            .collect::<List<Tag>>();
+        let nbt_data = crate::test_util::with(
+            crate::test_util::with(
+                block_entity_test_data_provider(),
+                "id",
+                "minecraft:chest".to_string().into(),
+            ),
+            "Items",
+            Tag::List(items),
+        );
+        let block_entity = BlockEntity::try_from(nbt_data).expect("Error parsing chest");
+        block_entity
+            .as_container()
+            .expect("Chest is a container")
+            .out_of_range_items()
+            .into_iter()
+            .map(|item| item.slot)
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_beehive_with_bees() {
+        let bee = |name: &str| {
+            Tag::Compound(HashMap::from_iter([
+                (
+                    "EntityData".to_string(),
+                    Tag::Compound(HashMap::from_iter([
+                        ("id".to_string(), Tag::String("minecraft:bee".to_string())),
+                        ("CustomName".to_string(), Tag::String(name.to_string())),
+                    ])),
+                ),
+                ("MinOccupationTicks".to_string(), Tag::Int(600)),
+                ("TicksInHive".to_string(), Tag::Int(20)),
+            ]))
+        };
+        let nbt_data = HashMap::from_iter([
+            (
+                "Bees".to_string(),
+                Tag::List(List::from(vec![bee("Buzz"), bee("Bumble")])),
+            ),
+            (
+                "FlowerPos".to_string(),
+                Tag::Compound(HashMap::from_iter([
+                    ("X".to_string(), Tag::Int(1)),
+                    ("Y".to_string(), Tag::Int(64)),
+                    ("Z".to_string(), Tag::Int(-3)),
+                ])),
+            ),
+        ]);
+        let beehive = Beehive::try_from(nbt_data).expect("Error parsing beehive");
+        assert_eq!(beehive.occupant_count(), 2);
+        assert_eq!(
+            beehive
+                .bee_entities()
+                .map(|bee| (bee.id.as_deref(), bee.custom_name.as_deref()))
+                .collect::<Vec<_>>(),
+            vec![
+                (Some("minecraft:bee"), Some("Buzz")),
+                (Some("minecraft:bee"), Some("Bumble"))
+            ]
+        );
+        assert_eq!(beehive.flower_pos, Some(FlowerPos { x: 1, y: 64, z: -3 }));
+    }
+
+    #[test]
+    fn test_beehive_without_bees() {
+        let beehive = Beehive::try_from(HashMap::new()).expect("Error parsing beehive");
+        assert_eq!(beehive.occupant_count(), 0);
+        assert_eq!(beehive.bee_entities().count(), 0);
+    }
+
+    #[test]
+    fn test_end_gateway_exit_position() {
+        let nbt_data = HashMap::from_iter([
+            ("x".to_string(), Tag::Int(100)),
+            ("y".to_string(), Tag::Int(75)),
+            ("z".to_string(), Tag::Int(0)),
+            (
+                "id".to_string(),
+                Tag::String("minecraft:end_gateway".to_string()),
+            ),
+            ("Age".to_string(), Tag::Long(4200)),
+            ("ExactTeleport".to_string(), Tag::Byte(1)),
+            (
+                "ExitPortal".to_string(),
+                Tag::Compound(HashMap::from_iter([
+                    ("X".to_string(), Tag::Int(1024)),
+                    ("Y".to_string(), Tag::Int(70)),
+                    ("Z".to_string(), Tag::Int(-512)),
+                ])),
+            ),
+        ]);
+        let block_entity = BlockEntity::try_from(nbt_data).expect("Error parsing end gateway");
+        let BlockEntityType::EndGateway(gateway) = block_entity.entity_type else {
+            panic!("Expected end gateway");
+        };
+        assert!(gateway.exact_teleport);
+        assert_eq!(
+            gateway.exit_position(),
+            Some(crate::coords::BlockPos {
+                x: 1024,
+                y: 70,
+                z: -512
+            })
+        );
+    }
+
+    #[test]
+    fn test_end_gateway_without_exit() {
+        let nbt_data = HashMap::from_iter([("Age".to_string(), Tag::Long(20))]);
+        let gateway = EndGateway::try_from(nbt_data).expect("Error parsing end gateway");
+        assert!(!gateway.exact_teleport);
+        assert_eq!(gateway.exit_position(), None);
+    }
+
+    #[test]
+    fn test_block_entity_position() {
+        let nbt_data = HashMap::from_iter([
+            ("x".to_string(), Tag::Int(-12)),
+            ("y".to_string(), Tag::Int(64)),
+            ("z".to_string(), Tag::Int(345)),
+            ("id".to_string(), Tag::String("minecraft:chest".to_string())),
+        ]);
+        let block_entity = BlockEntity::try_from(nbt_data).expect("Error parsing chest");
+        assert_eq!(
+            block_entity.position(),
+            crate::coords::BlockPos {
+                x: -12,
+                y: 64,
+                z: 345
+            }
+        );
+    }
+
+    #[test_case(Some(1) => Some("Second page".to_string()); "Second page")]
+    #[test_case(None => Some("First page".to_string()); "Without page")]
+    #[test_case(Some(2) => None; "Page out of range")]
+    fn test_lectern_with_written_book(page: Option<i32>) -> Option<String> {
+        let book = HashMap::from_iter(
+            [
+                ("id", Tag::String("minecraft:written_book".to_string())),
+                ("Count", Tag::Byte(1)),
+                (
+                    "tag",
+                    Tag::Compound(HashMap::from_iter(
+                        [
+                            ("title", Tag::String("Rules".to_string())),
+                            ("author", Tag::String("Admin".to_string())),
+                            (
+                                "pages",
+                                Tag::List(List::from(vec![
+                                    Tag::String(r#"{"text":"First page"}"#.to_string()),
+                                    Tag::String(r#"{"text":"Second page"}"#.to_string()),
+                                ])),
+                            ),
+                        ]
+                        .map(|(k, v)| (k.to_string(), v)),
+                    )),
+                ),
+            ]
+            .map(|(k, v)| (k.to_string(), v)),
+        );
+        let mut nbt_data = crate::test_util::with(
+            crate::test_util::with(
+                block_entity_test_data_provider(),
+                "id",
+                "minecraft:lectern".to_string().into(),
+            ),
+            "Book",
+            Tag::Compound(book),
+        );
+        if let Some(page) = page {
+            nbt_data.insert("Page".to_string(), Tag::Int(page));
+        }
+        let block_entity = BlockEntity::try_from(nbt_data).expect("Error parsing lectern");
+        let BlockEntityType::Lectern(lectern) = block_entity.entity_type else {
+            panic!("Expected lectern");
+        };
+        let book = lectern
+            .written_book()
+            .expect("Lectern holds a written book");
+        assert_eq!(book.title, "Rules");
+        assert_eq!(book.pages.len(), 2);
+        lectern.current_page_text()
+    }
+
+    #[test]
+    fn test_conduit_without_target() {
+        let conduit = Conduit::try_from(HashMap::new()).expect("Error parsing conduit");
+        assert_eq!(conduit.target, None);
+    }
+
+    #[test]
+    fn test_structure_block_save_mode() {
+        let nbt_data = crate::test_util::merge(
+            crate::test_util::with(
+                block_entity_test_data_provider(),
+                "id",
+                "minecraft:structure_block".to_string().into(),
+            ),
+            HashMap::from_iter(
+                [
+                    ("mode", Tag::String("SAVE".to_string())),
+                    ("name", Tag::String("minecraft:village/house".to_string())),
+                    ("posX", Tag::Int(0)),
+                    ("posY", Tag::Int(1)),
+                    ("posZ", Tag::Int(-2)),
+                    ("sizeX", Tag::Int(7)),
+                    ("sizeY", Tag::Int(5)),
+                    ("sizeZ", Tag::Int(9)),
+                ]
+                .map(|(k, v)| (k.to_string(), v)),
+            ),
+        );
+        let nbt_data = crate::test_util::merge(StructureBlock_test_data_provider(), nbt_data);
+        let block_entity = BlockEntity::try_from(nbt_data).expect("Error parsing structure block");
+        let BlockEntityType::StructureBlock(structure_block) = block_entity.entity_type else {
+            panic!("Expected structure block");
+        };
+        assert_eq!(
+            structure_block.structure_mode(),
+            Some(StructureBlockMode::Save)
+        );
+        assert_eq!(structure_block.name, "minecraft:village/house");
+        assert_eq!(structure_block.size(), (7, 5, 9));
+        assert_eq!(
+            structure_block.offset(),
+            crate::coords::BlockPos { x: 0, y: 1, z: -2 }
+        );
+    }
+
+    fn spawn_entry(weight: i32, id: &str) -> Tag {
+        let entity = HashMap::from_iter([("id".to_string(), Tag::String(id.to_string()))]);
+        Tag::Compound(HashMap::from_iter([
+            ("weight".to_string(), Tag::Int(weight)),
+            (
+                "data".to_string(),
+                Tag::Compound(HashMap::from_iter([(
+                    "entity".to_string(),
+                    Tag::Compound(entity),
+                )])),
+            ),
+        ]))
+    }
+
+    #[test]
+    fn test_spawner_weighted_spawns() {
+        let nbt_data = crate::test_util::with(
+            Spawner_test_data_provider(),
+            "SpawnPotentials",
+            Tag::List(List::from(vec![
+                spawn_entry(3, "minecraft:zombie"),
+                spawn_entry(1, "minecraft:skeleton"),
+            ])),
+        );
+        let spawner = Spawner::try_from(nbt_data).expect("Error parsing spawner");
+        let spawns = spawner
+            .weighted_spawns()
+            .into_iter()
+            .map(|spawn| (spawn.weight, spawn.entity.id))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            spawns,
+            vec![
+                (3, Some("minecraft:zombie".to_string())),
+                (1, Some("minecraft:skeleton".to_string())),
+            ]
+        );
+    }
+
+    #[test_case(HashMap::new() => Vec::<(i32, Option<String>)>::new(); "Empty")]
+    #[test_case(HashMap::from_iter([(
+        "entity".to_string(),
+        Tag::Compound(HashMap::from_iter([("id".to_string(), Tag::String("minecraft:pig".to_string()))])),
+    )]) => vec![(1, Some("minecraft:pig".to_string()))]; "Entity")]
+    #[test_case(HashMap::from_iter([
+        ("id".to_string(), Tag::String("minecraft:pig".to_string())),
+    ]) => vec![(1, Some("minecraft:pig".to_string()))]; "Legacy")]
+    fn test_spawner_weighted_spawns_from_spawn_data(
+        spawn_data: HashMap<String, Tag>,
+    ) -> Vec<(i32, Option<String>)> {
+        let nbt_data = crate::test_util::with(
+            crate::test_util::without(&Spawner_test_data_provider, "SpawnPotentials"),
+            "SpawnData",
+            Tag::Compound(spawn_data),
+        );
+        Spawner::try_from(nbt_data)
+            .expect("Error parsing spawner")
+            .weighted_spawns()
+            .into_iter()
+            .map(|spawn| (spawn.weight, spawn.entity.id))
+            .collect()
+    }
+
+    #[test_case("SAVE" => Some(StructureBlockMode::Save); "Save")]
+    #[test_case("DATA" => Some(StructureBlockMode::Data); "Data")]
+    #[test_case("save" => None; "Lowercase")]
+    fn test_structure_block_mode_from_name(name: &str) -> Option<StructureBlockMode> {
+        StructureBlockMode::from_name(name)
+    }
+
     fn block_entity_test_data_provider() -> HashMap<String, Tag> {
         HashMap::from_iter([
             ("x".to_string(), Tag::Int(123)),
@@ -745,6 +1131,7 @@ mod tests {
         loot_table_seed: Some(123)
     }); "Dropper")]
     #[test_case(ShulkerBoxBuilder::default() => Ok(ShulkerBox {
+        color: None,
         custom_name: Some("test".to_string()),
         items: Some(List::from(vec![])),
         lock: Some("test".to_string()),
@@ -3,7 +3,9 @@ use thiserror::Error;
 use crate::{
     compression::{self, decompress},
     data::chunk::*,
+    data::entity::Entity,
     data::file_format::anvil::ChunkInfo,
+    nbt::{List, Tag},
 };
 
 #[cfg(feature = "block_entity")]
@@ -30,6 +32,20 @@ pub enum LoadChunkDataError {
 
 /// Load chunk data from a region file.
 pub fn load_chunk(raw: &[u8], chunk_info: &ChunkInfo) -> Result<ChunkData, LoadChunkDataError> {
+    let tag = load_chunk_nbt(raw, chunk_info)?;
+    let chunk_data = tag.try_into()?;
+    Ok(chunk_data)
+}
+
+/// Load the NBT data of a chunk from a region file without interpreting it.
+pub fn load_chunk_nbt(raw: &[u8], chunk_info: &ChunkInfo) -> Result<Tag, LoadChunkDataError> {
+    let data = decompress_chunk(raw, chunk_info)?;
+    let tag = crate::nbt::parse(data.as_slice()).map_err(ChunkDataError::Nbt)?;
+    Ok(tag)
+}
+
+/// Decompress the data of a chunk from a region file. The result is the uncompressed NBT data of the chunk.
+pub fn decompress_chunk(raw: &[u8], chunk_info: &ChunkInfo) -> Result<Vec<u8>, LoadChunkDataError> {
     let offset = ((chunk_info.offset - 2) * CHUNK_ALIGNMENT) as usize;
     let chunk_data = &raw[offset..];
     if chunk_data.len() < 6 {
@@ -42,16 +58,15 @@ pub fn load_chunk(raw: &[u8], chunk_info: &ChunkInfo) -> Result<ChunkData, LoadC
     );
     let compression = chunk_data[4].into();
 
-    if chunk_data.len() < chunk_len as usize || chunk_len < 5 {
+    // The length includes the compression byte but not the length itself
+    let chunk_end = chunk_len as usize + 4;
+    if chunk_data.len() < chunk_end || chunk_len == 0 {
         return Err(LoadChunkDataError::ChunkDataLengthError);
     }
 
-    let data = &chunk_data[5..chunk_len as usize];
+    let data = &chunk_data[5..chunk_end];
 
-    let data = decompress(data, &compression).map_err(LoadChunkDataError::Compression)?;
-    let tag = crate::nbt::parse(data.as_slice()).map_err(ChunkDataError::Nbt)?;
-    let chunk_data = tag.try_into()?;
-    Ok(chunk_data)
+    decompress(data, &compression).map_err(LoadChunkDataError::Compression)
 }
 
 mod_try_from_tag!(ChunkData: [
@@ -62,6 +77,7 @@ mod_try_from_tag!(ChunkData: [
     "Status" => set_status test(crate::nbt::Tag::String("empty".to_string()) => status = ChunkStatus::Empty),
     "LastUpdate" => set_last_update test(crate::nbt::Tag::Long(5) => last_update = 5),
     if feature = "chunk_section" "sections" => set_sections test(crate::nbt::Tag::List(crate::nbt::List::from(vec![])) => sections = crate::nbt::List::from(vec![])),
+    "entities" => set_entities_lenient test(crate::nbt::Tag::List(crate::nbt::List::from(vec![])) => entities = Some(crate::nbt::List::from(vec![]))),
     if feature = "block_entity" "block_entities" => set_block_entities test(crate::nbt::Tag::List(crate::nbt::List::from(vec![])) => block_entities = Some(crate::nbt::List::from(vec![]))),
 ] ? [
     ChunkStatus,
@@ -123,6 +139,36 @@ if feature = "chunk_section" BlockState: [
 );
 try_from_tag!(error ChunkStatus => []);
 
+/// The entities stored inside of a chunk.
+/// Entities that can not be parsed are skipped, so that a single broken entity does not fail the whole chunk.
+struct LenientEntities(List<Entity>);
+
+impl TryFrom<Tag> for LenientEntities {
+    type Error = crate::nbt::Error;
+
+    fn try_from(value: Tag) -> Result<Self, Self::Error> {
+        let entities = value
+            .get_as_list()?
+            .take()
+            .into_iter()
+            .filter_map(|entity| match Entity::try_from(entity) {
+                Ok(entity) => Some(entity),
+                Err(err) => {
+                    log::warn!("Skipping entity that can not be parsed: {err}");
+                    None
+                }
+            })
+            .collect();
+        Ok(Self(entities))
+    }
+}
+
+impl ChunkDataBuilder {
+    fn set_entities_lenient(&mut self, entities: LenientEntities) {
+        self.set_entities(entities.0)
+    }
+}
+
 impl TryFrom<crate::nbt::Tag> for ChunkStatus {
     type Error = ChunkStatusError;
 
@@ -152,17 +198,19 @@ impl TryFrom<crate::nbt::Tag> for ChunkStatus {
 }
 
 #[cfg(test)]
-mod tests {
+pub mod tests {
     use crate::{
         data::{
-            chunk::{ChunkData, ChunkStatus},
+            chunk::{Biomes, BlockState, BlockStates, ChunkData, ChunkStatus, Section},
             file_format::anvil::ChunkInfo,
         },
-        nbt::Tag,
+        nbt::{List, Tag},
     };
     use test_case::test_case;
 
-    use super::{load_chunk, ChunkStatusError, LoadChunkDataError};
+    use super::{
+        load_chunk, macro_tests::ChunkData_test_data_provider, ChunkStatusError, LoadChunkDataError,
+    };
 
     #[test_case(Tag::String("empty".to_string()) => Ok(ChunkStatus::Empty); "empty")]
     #[test_case(Tag::String("structure_starts".to_string()) => Ok(ChunkStatus::StructureStarts); "structure_starts")]
@@ -186,8 +234,12 @@ mod tests {
     #[test_case(&[] => Err(LoadChunkDataError::ChunkDataLengthError); "empty")]
     #[test_case(&[0, 0, 0, 0] => Err(LoadChunkDataError::ChunkDataLengthError); "missing compression type")]
     #[test_case(&[0, 0, 0, 0, 0] => Err(LoadChunkDataError::ChunkDataLengthError); "no sectors")]
-    #[test_case(&[0, 0, 0, 1, 0, 0] => Err(LoadChunkDataError::ChunkDataLengthError); "length to small")]
-    #[test_case(&[0, 0, 0, 6, 1, 1] => Err(LoadChunkDataError::Compression(
+    #[test_case(&[0, 0, 0, 0, 0, 0] => Err(LoadChunkDataError::ChunkDataLengthError); "length to small")]
+    #[test_case(&[0, 0, 0, 3, 1, 1] => Err(LoadChunkDataError::ChunkDataLengthError); "length exceeds data")]
+    #[test_case(&[0, 0, 0, 2, 4, 1] => Err(LoadChunkDataError::Compression(
+        crate::compression::Error::UnknownCompression(4)
+    )); "Unknown compression")]
+    #[test_case(&[0, 0, 0, 2, 1, 1] => Err(LoadChunkDataError::Compression(
         crate::compression::Error::Io(std::io::Error::new(std::io::ErrorKind::UnexpectedEof, "failed to fill whole buffer"))
     )); "Invalid data")]
     #[test_case(&valid_chunk_data() => Ok(ChunkData {
@@ -198,6 +250,7 @@ mod tests {
         status: ChunkStatus::Full,
         last_update: 10,
         sections: crate::nbt::List::from(vec![]),
+        entities: None,
         block_entities: None
     }); "Success")]
     fn test_load_chunk_status(raw: &[u8]) -> Result<ChunkData, LoadChunkDataError> {
@@ -211,7 +264,7 @@ mod tests {
         )
     }
 
-    fn valid_chunk_data() -> Vec<u8> {
+    pub fn valid_chunk_data() -> Vec<u8> {
         const INT_ID: u8 = 3;
         const LONG_ID: u8 = 4;
         const STRING_ID: u8 = 8;
@@ -245,8 +298,194 @@ mod tests {
         push_str(&mut data, "sections");
         data.push(COMPOUND_ID);
         data.extend(0_i32.to_be_bytes());
-        let data_len = (data.len() as u32).to_be_bytes();
+        let data_len = (data.len() as u32 - 4).to_be_bytes();
         data[0..4].copy_from_slice(&data_len);
         data
     }
+
+    /// Replace the uncompressed data of [`valid_chunk_data`] with compressed data.
+    fn compressed_chunk_data(compression: u8) -> Vec<u8> {
+        use std::io::Write;
+        let nbt = &valid_chunk_data()[5..];
+        let mut compressed = Vec::new();
+        match compression {
+            1 => {
+                let mut encoder =
+                    libflate::gzip::Encoder::new(&mut compressed).expect("Error creating encoder");
+                encoder.write_all(nbt).expect("Error compressing chunk");
+                encoder
+                    .finish()
+                    .into_result()
+                    .expect("Error compressing chunk");
+            }
+            2 => {
+                let mut encoder =
+                    libflate::zlib::Encoder::new(&mut compressed).expect("Error creating encoder");
+                encoder.write_all(nbt).expect("Error compressing chunk");
+                encoder
+                    .finish()
+                    .into_result()
+                    .expect("Error compressing chunk");
+            }
+            _ => compressed.extend(nbt),
+        }
+        let mut data = Vec::new();
+        data.extend((compressed.len() as u32 + 1).to_be_bytes());
+        data.push(compression);
+        data.extend(compressed);
+        data
+    }
+
+    fn section(y: i8, palette: Vec<String>, indices: Option<Vec<u16>>) -> Section {
+        // Every index uses at least 4 bits and indices do not span multiple longs
+        let bits = (u16::BITS - (palette.len() as u16 - 1).leading_zeros()).max(4);
+        let data = indices.map(|indices| {
+            indices
+                .chunks((64 / bits) as usize)
+                .map(|chunk| {
+                    chunk.iter().enumerate().fold(0_u64, |long, (i, index)| {
+                        long | (u64::from(*index) << (i as u32 * bits))
+                    }) as i64
+                })
+                .collect::<Vec<_>>()
+                .into()
+        });
+        Section {
+            y,
+            block_states: BlockStates {
+                palette: palette
+                    .into_iter()
+                    .map(|name| BlockState {
+                        name,
+                        properties: None,
+                    })
+                    .collect::<Vec<_>>()
+                    .into(),
+                data,
+            },
+            biomes: Biomes {
+                palette: List::from(vec!["minecraft:plains".to_string()]),
+                data: None,
+            },
+            block_light: None,
+            sky_light: None,
+        }
+    }
+
+    /// A chunk with an air only section at the bottom of the world, a section of stone containing a single diamond ore
+    /// and a section with 17 block states which needs 5 bits per block.
+    fn chunk_with_blocks() -> ChunkData {
+        let mut ore = vec![0; 4096];
+        ore[(2 * 16 + 3) * 16 + 1] = 1;
+        ChunkData {
+            data_version: 3953,
+            x_pos: 0,
+            y_pos: -4,
+            z_pos: 0,
+            status: ChunkStatus::Full,
+            last_update: 0,
+            sections: List::from(vec![
+                section(-4, vec!["minecraft:air".to_string()], None),
+                section(
+                    0,
+                    vec![
+                        "minecraft:stone".to_string(),
+                        "minecraft:diamond_ore".to_string(),
+                    ],
+                    Some(ore),
+                ),
+                section(
+                    1,
+                    (0..17).map(|i| format!("minecraft:block_{i}")).collect(),
+                    Some((0..4096).map(|i| i % 17).collect()),
+                ),
+            ]),
+            entities: None,
+            block_entities: None,
+        }
+    }
+
+    #[test_case(0, -64, 0 => Some("minecraft:air".to_string()); "Bottom of air only section")]
+    #[test_case(15, -49, 15 => Some("minecraft:air".to_string()); "Top of air only section")]
+    #[test_case(1, 2, 3 => Some("minecraft:diamond_ore".to_string()); "Single block in section")]
+    #[test_case(3, 2, 1 => Some("minecraft:stone".to_string()); "Swapped x and z")]
+    #[test_case(0, 16, 1 => Some("minecraft:block_16".to_string()); "Five bits per block")]
+    #[test_case(15, 31, 15 => Some("minecraft:block_15".to_string()); "Last block of section")]
+    #[test_case(0, -32, 0 => None; "Section not stored")]
+    #[test_case(16, 0, 0 => None; "Outside of chunk")]
+    #[test_case(0, 0, -1 => None; "Negative relative coordinate")]
+    fn test_block_grid_get(x: i32, y: i32, z: i32) -> Option<String> {
+        chunk_with_blocks()
+            .blocks()
+            .get(x, y, z)
+            .map(|block| block.name.clone())
+    }
+
+    #[test]
+    fn test_block_grid_random_access() {
+        let chunk = chunk_with_blocks();
+        let blocks = chunk.blocks();
+        let names = [(1, 2, 3), (0, 20, 0), (1, 2, 3), (0, -60, 0), (2, 2, 3)]
+            .map(|(x, y, z)| blocks.get(x, y, z).map(|block| block.name.as_str()));
+        assert_eq!(
+            names,
+            [
+                Some("minecraft:diamond_ore"),
+                // (4 * 16) * 16 = 1024 = 60 * 17 + 4
+                Some("minecraft:block_4"),
+                Some("minecraft:diamond_ore"),
+                Some("minecraft:air"),
+                Some("minecraft:stone"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_skip_invalid_entities() {
+        let entity = |id: &str, fire: Tag| {
+            Tag::Compound(std::collections::HashMap::from_iter([
+                ("id".to_string(), Tag::String(id.to_string())),
+                ("Fire".to_string(), fire),
+            ]))
+        };
+        let mut nbt_data = ChunkData_test_data_provider();
+        nbt_data.insert(
+            "entities".to_string(),
+            Tag::List(List::from(vec![
+                entity("minecraft:pig", Tag::Short(0)),
+                entity("minecraft:cow", Tag::String("broken".to_string())),
+                Tag::Int(1),
+                entity("minecraft:sheep", Tag::Short(0)),
+            ])),
+        );
+        let chunk = ChunkData::try_from(nbt_data).expect("Invalid entities are skipped");
+        let ids = chunk
+            .entities
+            .expect("Entities are set")
+            .iter()
+            .map(|entity| entity.id.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            ids,
+            vec![
+                Some("minecraft:pig".to_string()),
+                Some("minecraft:sheep".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_load_chunk_gzip_and_zlib() {
+        let chunk_info = ChunkInfo {
+            offset: 2,
+            sector_count: 1,
+            timestamp: 0,
+        };
+        let gzip = load_chunk(&compressed_chunk_data(1), &chunk_info);
+        let zlib = load_chunk(&compressed_chunk_data(2), &chunk_info);
+        let uncompressed = load_chunk(&compressed_chunk_data(3), &chunk_info);
+        assert!(gzip.is_ok());
+        assert_eq!(gzip, zlib);
+        assert_eq!(gzip, uncompressed);
+    }
 }
@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 
 use crate::{
+    coords::BlockPos,
     data::entity::*,
     data::{load::item::ItemError, FieldError},
     nbt::*,
@@ -37,6 +38,18 @@ Mob: parse_mob ? [
     Item,
     Leash,
 ],
+ItemEntity: parse_item_entity ? [
+    Entity,
+    Item,
+],
+Painting: parse_painting ? [
+    Entity,
+],
+Villager: parse_villager ? [
+    Mob,
+    VillagerData,
+],
+VillagerData: parse_villager_data,
 ActiveEffect: [
     "Ambient" => set_ambient test(1i8 => ambient = true),
     "Amplifier" => set_amplifier test(1i8 => amplifier = 1),
@@ -47,7 +60,20 @@ ActiveEffect: [
 ],
 });
 try_from_tag!(enum Leash => parse_leash);
+
 fn parse_mob(builder: &mut MobBuilder, mut nbt_data: HashMap<String, Tag>) -> Result<(), MobError> {
+    add_mob_data_to_builder(builder, &mut nbt_data)
+}
+/// Parse the keys of [`Mob`] and its [`Entity`] and leave every other key in `nbt_data`.
+pub(crate) fn take_mob_from_nbt(nbt_data: &mut HashMap<String, Tag>) -> Result<Mob, MobError> {
+    let mut builder = MobBuilder::default();
+    add_mob_data_to_builder(&mut builder, nbt_data)?;
+    Ok(builder.try_build()?)
+}
+fn add_mob_data_to_builder(
+    builder: &mut MobBuilder,
+    nbt_data: &mut HashMap<String, Tag>,
+) -> Result<(), MobError> {
     add_data_to_builder!(builder, nbt_data => [
         "AbsorptionAmount": set_absorption_amount,
         "ActiveEffects": set_active_effects,
@@ -74,6 +100,70 @@ fn parse_mob(builder: &mut MobBuilder, mut nbt_data: HashMap<String, Tag>) -> Re
         "SleepingZ": set_sleeping_z,
         "Team": set_team,
     ]);
+    // Since 1.20.5 a leash tied to a fence is stored as the position of the fence
+    match nbt_data.remove("leash") {
+        Some(Tag::IntArray(position)) => match position[..] {
+            [x, y, z] => {
+                builder.set_leash(Leash::Position { x, y, z });
+            }
+            _ => return Err(FieldError::new("leash", crate::nbt::Error::InvalidValue).into()),
+        },
+        Some(leash) => {
+            builder.set_leash(leash.try_into().map_err(|e| FieldError::new("leash", e))?);
+        }
+        None => {}
+    }
+    builder.set_entity(
+        Entity::take_from_nbt(nbt_data).map_err(|e| FieldError::new("<internal> entity", e))?,
+    );
+    Ok(())
+}
+fn parse_item_entity(
+    builder: &mut ItemEntityBuilder,
+    mut nbt_data: HashMap<String, Tag>,
+) -> Result<(), ItemEntityError> {
+    add_data_to_builder!(builder, nbt_data => [
+        "Item": set_item,
+        "Age": set_age,
+        "PickupDelay": set_pickup_delay,
+        "Health": set_health,
+    ]);
+    builder.set_entity(
+        nbt_data
+            .try_into()
+            .map_err(|e| FieldError::new("<internal> entity", e))?,
+    );
+    Ok(())
+}
+fn parse_painting(
+    builder: &mut PaintingBuilder,
+    mut nbt_data: HashMap<String, Tag>,
+) -> Result<(), PaintingError> {
+    // Legacy keys are added first so that they are overwritten by their modern equivalent
+    add_data_to_builder!(builder, nbt_data => [
+        "Motive": set_variant,
+        "variant": set_variant,
+        "Facing": set_facing,
+        "facing": set_facing,
+    ]);
+    let tile_position = match (
+        nbt_data.remove("TileX"),
+        nbt_data.remove("TileY"),
+        nbt_data.remove("TileZ"),
+        nbt_data.remove("block_pos"),
+    ) {
+        (_, _, _, Some(Tag::IntArray(position))) => match position[..] {
+            [x, y, z] => Some(BlockPos { x, y, z }),
+            _ => return Err(FieldError::new("block_pos", crate::nbt::Error::InvalidValue).into()),
+        },
+        (Some(Tag::Int(x)), Some(Tag::Int(y)), Some(Tag::Int(z)), None) => {
+            Some(BlockPos { x, y, z })
+        }
+        _ => None,
+    };
+    if let Some(tile_position) = tile_position {
+        builder.set_tile_position(tile_position);
+    }
     builder.set_entity(
         nbt_data
             .try_into()
@@ -81,6 +171,57 @@ fn parse_mob(builder: &mut MobBuilder, mut nbt_data: HashMap<String, Tag>) -> Re
     );
     Ok(())
 }
+fn parse_villager(
+    builder: &mut VillagerBuilder,
+    mut nbt_data: HashMap<String, Tag>,
+) -> Result<(), VillagerError> {
+    // Villagers saved before 1.14 store their profession in the entity itself
+    if let Some(Tag::Int(profession)) = nbt_data.remove("Profession") {
+        let profession = VillagerData::profession_from_legacy_id(profession)
+            .ok_or_else(|| FieldError::new("Profession", crate::nbt::Error::InvalidValue))?;
+        let level = match nbt_data.remove("CareerLevel") {
+            Some(Tag::Int(level)) => level,
+            _ => 1,
+        };
+        builder.set_villager_data(VillagerData {
+            biome: "minecraft:plains".to_string(),
+            profession: profession.to_string(),
+            level,
+        });
+    }
+    add_data_to_builder!(builder, nbt_data => [
+        "VillagerData": set_villager_data,
+        "Xp": set_xp,
+    ]);
+    builder.set_mob(
+        nbt_data
+            .try_into()
+            .map_err(|e| FieldError::new("<internal> mob", e))?,
+    );
+    Ok(())
+}
+fn parse_villager_data(
+    builder: &mut VillagerDataBuilder,
+    mut nbt_data: HashMap<String, Tag>,
+) -> Result<(), VillagerDataError> {
+    add_data_to_builder!(builder, nbt_data => [
+        "type": set_biome,
+        "level": set_level,
+    ]);
+    match nbt_data.remove("profession") {
+        Some(Tag::String(profession)) => builder.set_profession(profession),
+        Some(Tag::Int(profession)) => builder.set_profession(
+            VillagerData::profession_from_legacy_id(profession)
+                .ok_or_else(|| FieldError::new("profession", crate::nbt::Error::InvalidValue))?
+                .to_string(),
+        ),
+        Some(_) => {
+            return Err(FieldError::new("profession", crate::nbt::Error::InvalidValue).into())
+        }
+        None => {}
+    }
+    Ok(())
+}
 fn parse_leash(mut nbt_data: HashMap<String, Tag>) -> Result<Leash, LeashError> {
     if let Some(Tag::IntArray(uuid)) = nbt_data.remove("UUID") {
         return Ok(Leash::Entity(uuid));
@@ -160,6 +301,295 @@ pub mod tests {
         data
     }
 
+    #[test]
+    fn test_parse_item_entity() {
+        let nbt_data = HashMap::from_iter([
+            ("id".to_string(), Tag::String(ItemEntity::ID.to_string())),
+            (
+                "Item".to_string(),
+                Tag::Compound(HashMap::from_iter([
+                    (
+                        "id".to_string(),
+                        Tag::String("minecraft:cobblestone".to_string()),
+                    ),
+                    ("Count".to_string(), Tag::Byte(64)),
+                ])),
+            ),
+            ("Age".to_string(), Tag::Short(1200)),
+            ("PickupDelay".to_string(), Tag::Short(10)),
+        ]);
+        let item_entity = ItemEntity::try_from(nbt_data).expect("Error parsing item entity");
+        assert_eq!(item_entity.entity.id.as_deref(), Some(ItemEntity::ID));
+        assert_eq!(item_entity.item.id, "minecraft:cobblestone");
+        assert_eq!(item_entity.item.count, 64);
+        assert_eq!(item_entity.age, 1200);
+        assert_eq!(item_entity.pickup_delay, 10);
+        assert_eq!(item_entity.health, 5);
+    }
+
+    #[test]
+    fn test_parse_item_entity_without_item() {
+        let nbt_data =
+            HashMap::from_iter([("id".to_string(), Tag::String(ItemEntity::ID.to_string()))]);
+        assert_eq!(
+            ItemEntity::try_from(nbt_data),
+            Err(ItemEntityError::Builder(ItemEntityBuilderError::UnsetItem))
+        );
+    }
+
+    #[test]
+    fn test_parse_painting() {
+        let nbt_data = HashMap::from_iter([
+            ("id".to_string(), Tag::String(Painting::ID.to_string())),
+            (
+                "variant".to_string(),
+                Tag::String("minecraft:kebab".to_string()),
+            ),
+            ("facing".to_string(), Tag::Byte(2)),
+            (
+                "block_pos".to_string(),
+                Tag::IntArray(Array::from(vec![10, 64, -20])),
+            ),
+        ]);
+        let painting = Painting::try_from(nbt_data).expect("Error parsing painting");
+        assert_eq!(painting.entity.id.as_deref(), Some(Painting::ID));
+        assert_eq!(painting.variant, "minecraft:kebab");
+        assert_eq!(painting.facing, 2);
+        assert_eq!(
+            painting.tile_position,
+            Some(BlockPos {
+                x: 10,
+                y: 64,
+                z: -20
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_legacy_painting() {
+        let nbt_data = HashMap::from_iter([
+            ("id".to_string(), Tag::String(Painting::ID.to_string())),
+            (
+                "Motive".to_string(),
+                Tag::String("minecraft:wanderer".to_string()),
+            ),
+            ("Facing".to_string(), Tag::Byte(3)),
+            ("TileX".to_string(), Tag::Int(1)),
+            ("TileY".to_string(), Tag::Int(2)),
+            ("TileZ".to_string(), Tag::Int(3)),
+        ]);
+        let painting = Painting::try_from(nbt_data).expect("Error parsing painting");
+        assert_eq!(painting.variant, "minecraft:wanderer");
+        assert_eq!(painting.facing, 3);
+        assert_eq!(painting.tile_position, Some(BlockPos { x: 1, y: 2, z: 3 }));
+    }
+
+    #[test]
+    fn test_parse_master_librarian() {
+        let nbt_data = HashMap::from_iter([
+            ("id".to_string(), Tag::String(Villager::ID.to_string())),
+            (
+                "VillagerData".to_string(),
+                Tag::Compound(HashMap::from_iter([
+                    (
+                        "type".to_string(),
+                        Tag::String("minecraft:taiga".to_string()),
+                    ),
+                    (
+                        "profession".to_string(),
+                        Tag::String("minecraft:librarian".to_string()),
+                    ),
+                    ("level".to_string(), Tag::Int(5)),
+                ])),
+            ),
+            ("Xp".to_string(), Tag::Int(250)),
+        ]);
+        let villager = Villager::try_from(nbt_data).expect("Error parsing villager");
+        assert_eq!(villager.mob.entity.id.as_deref(), Some(Villager::ID));
+        assert_eq!(
+            villager.villager_data,
+            VillagerData {
+                biome: "minecraft:taiga".to_string(),
+                profession: "minecraft:librarian".to_string(),
+                level: VillagerData::MAX_LEVEL,
+            }
+        );
+        assert_eq!(villager.xp, Some(250));
+    }
+
+    fn memory(pos: [i32; 3], dimension: &str) -> Tag {
+        Tag::Compound(HashMap::from_iter([
+            ("pos".to_string(), Tag::IntArray(pos.to_vec().into())),
+            ("dimension".to_string(), Tag::String(dimension.to_string())),
+        ]))
+    }
+
+    #[test]
+    fn test_villager_memories() {
+        let memories = HashMap::from_iter([
+            (
+                "minecraft:job_site".to_string(),
+                Tag::Compound(HashMap::from_iter([(
+                    "value".to_string(),
+                    memory([10, 64, -20], "minecraft:overworld"),
+                )])),
+            ),
+            // Memories saved before 1.16 are not wrapped in `value`
+            (
+                "minecraft:home".to_string(),
+                memory([12, 65, -18], "minecraft:overworld"),
+            ),
+        ]);
+        let nbt_data = HashMap::from_iter([
+            ("id".to_string(), Tag::String(Villager::ID.to_string())),
+            ("Profession".to_string(), Tag::Int(1)),
+            (
+                "Brain".to_string(),
+                Tag::Compound(HashMap::from_iter([(
+                    "memories".to_string(),
+                    Tag::Compound(memories),
+                )])),
+            ),
+        ]);
+        let villager = Villager::try_from(nbt_data).expect("Error parsing villager");
+        assert_eq!(
+            villager.job_site(),
+            Some(GlobalPos {
+                position: BlockPos {
+                    x: 10,
+                    y: 64,
+                    z: -20
+                },
+                dimension: "minecraft:overworld".to_string(),
+            })
+        );
+        assert_eq!(
+            villager.home().map(|home| home.position),
+            Some(BlockPos {
+                x: 12,
+                y: 65,
+                z: -18
+            })
+        );
+        assert_eq!(villager.meeting_point(), None);
+    }
+
+    #[test]
+    fn test_villager_without_brain() {
+        let nbt_data = HashMap::from_iter([
+            ("id".to_string(), Tag::String(Villager::ID.to_string())),
+            ("Profession".to_string(), Tag::Int(1)),
+        ]);
+        let villager = Villager::try_from(nbt_data).expect("Error parsing villager");
+        assert_eq!(villager.job_site(), None);
+    }
+
+    #[test_case(Tag::Int(1) => Ok("minecraft:librarian".to_string()); "Legacy numeric profession")]
+    #[test_case(Tag::Int(42) => Err(VillagerDataError::NbtField(FieldError::new("profession", crate::nbt::Error::InvalidValue))); "Unknown numeric profession")]
+    fn test_parse_villager_data_profession(profession: Tag) -> Result<String, VillagerDataError> {
+        let nbt_data = HashMap::from_iter([
+            (
+                "type".to_string(),
+                Tag::String("minecraft:plains".to_string()),
+            ),
+            ("profession".to_string(), profession),
+        ]);
+        VillagerData::try_from(nbt_data).map(|villager_data| villager_data.profession)
+    }
+
+    #[test]
+    fn test_parse_legacy_villager() {
+        let nbt_data = HashMap::from_iter([
+            ("id".to_string(), Tag::String(Villager::ID.to_string())),
+            ("Profession".to_string(), Tag::Int(1)),
+            ("CareerLevel".to_string(), Tag::Int(3)),
+        ]);
+        let villager = Villager::try_from(nbt_data).expect("Error parsing villager");
+        assert_eq!(villager.villager_data.profession, "minecraft:librarian");
+        assert_eq!(villager.villager_data.level, 3);
+    }
+
+    #[test_case(r#"{"text":"Sir ","extra":[{"text":"Barks","color":"gold"}]}"# => Some("Sir Barks".to_string()); "JSON text")]
+    #[test_case(r#""Wolfgang""# => Some("Wolfgang".to_string()); "JSON string")]
+    #[test_case("Dinnerbone" => Some("Dinnerbone".to_string()); "Legacy plain string")]
+    fn test_custom_name_text(custom_name: &str) -> Option<String> {
+        let nbt_data = HashMap::from_iter([
+            ("id".to_string(), Tag::String("minecraft:wolf".to_string())),
+            (
+                "CustomName".to_string(),
+                Tag::String(custom_name.to_string()),
+            ),
+        ]);
+        let entity = Entity::try_from(nbt_data).expect("Error parsing entity");
+        entity.custom_name_text()
+    }
+
+    #[test_case(Some(1) => true; "Visible")]
+    #[test_case(Some(0) => false; "Hidden")]
+    #[test_case(None => false; "Unset")]
+    fn test_is_custom_name_visible(custom_name_visible: Option<i8>) -> bool {
+        let nbt_data = custom_name_visible
+            .map(|visible| ("CustomNameVisible".to_string(), Tag::Byte(visible)))
+            .into_iter()
+            .collect::<HashMap<_, _>>();
+        Entity::try_from(nbt_data)
+            .expect("Error parsing entity")
+            .is_custom_name_visible()
+    }
+
+    #[test_case("Leash", Tag::Compound(HashMap::from_iter([
+        ("X".to_string(), Tag::Int(4)),
+        ("Y".to_string(), Tag::Int(64)),
+        ("Z".to_string(), Tag::Int(-7)),
+    ])) => Some(BlockPos { x: 4, y: 64, z: -7 }); "Legacy fence position")]
+    #[test_case("leash", Tag::IntArray(Array::from(vec![4, 64, -7])) => Some(BlockPos { x: 4, y: 64, z: -7 }); "Fence position")]
+    #[test_case("leash", Tag::Compound(HashMap::from_iter([(
+        "UUID".to_string(),
+        Tag::IntArray(Array::from(vec![1, 2, 3, 4])),
+    )])) => None; "Held by entity")]
+    fn test_leash_position(key: &str, leash: Tag) -> Option<BlockPos> {
+        let nbt_data = crate::test_util::with(
+            crate::test_util::without(&mob_test_data_provider, "Leash"),
+            key,
+            leash,
+        );
+        let mob = Mob::try_from(nbt_data).expect("Error parsing mob");
+        mob.leash.as_ref().and_then(Leash::position)
+    }
+
+    #[test]
+    fn test_leash_holder() {
+        let nbt_data = crate::test_util::with(
+            crate::test_util::without(&mob_test_data_provider, "Leash"),
+            "leash",
+            Tag::Compound(HashMap::from_iter([(
+                "UUID".to_string(),
+                Tag::IntArray(Array::from(vec![5, 6, 7, 8])),
+            )])),
+        );
+        let mob = Mob::try_from(nbt_data).expect("Error parsing mob");
+        assert_eq!(
+            mob.leash.as_ref().and_then(Leash::holder),
+            Some(&Array::from(vec![5, 6, 7, 8]))
+        );
+    }
+
+    #[test]
+    fn test_invalid_leash_position() {
+        let nbt_data = crate::test_util::with(
+            crate::test_util::without(&mob_test_data_provider, "Leash"),
+            "leash",
+            Tag::IntArray(Array::from(vec![4, 64])),
+        );
+        assert_eq!(
+            Mob::try_from(nbt_data),
+            Err(MobError::NbtField(FieldError::new(
+                "leash",
+                crate::nbt::Error::InvalidValue
+            )))
+        );
+    }
+
     pub fn mob_test_data_provider() -> HashMap<String, Tag> {
         let mut map = HashMap::from_iter(
             [
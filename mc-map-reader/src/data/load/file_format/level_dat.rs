@@ -28,18 +28,28 @@ mod_try_from_tag!(
         "Difficulty" => set_difficulty test(1i8 => difficulty = 1),
         "DifficultyLocked" => set_difficulty_locked test(1i8 => difficulty_locked = true),
         "DimensionData" => set_dimension_data test(std::collections::HashMap::new() => dimension_data = Some(std::collections::HashMap::new())),
+        "DragonFight" => set_dragon_fight test(std::collections::HashMap::from_iter([
+            ("Gateways".to_string(), crate::nbt::Tag::from(crate::nbt::List::from(vec![crate::nbt::Tag::Int(1)]))),
+            ("DragonKilled".to_string(), 1i8.into()),
+            ("PreviouslyKilled".to_string(), 1i8.into()),
+        ]) => dragon_fight = Some(DragonFight {
+            gateways: crate::nbt::List::from(vec![1]),
+            dragon_killed: true,
+            previously_killed: true,
+            dragon: None,
+        })),
         "GameRules" => set_game_rules test(std::collections::HashMap::new() => game_rules = std::collections::HashMap::new()),
         "WorldGenSettings" => set_world_gen_settings test(std::collections::HashMap::from_iter([
             ("bonus_chest".to_string(), 1i8.into()),
             ("dimensions".to_string(), std::collections::HashMap::new().into()),
             ("seed".to_string(), 1i64.into()),
             ("generate_features".to_string(),1i8.into()),
-        ]) => world_gen_settings = WorldGenSettings {
+        ]) => world_gen_settings = Some(WorldGenSettings {
             bonus_chest: true,
             dimensions: std::collections::HashMap::new(),
             seed: 1,
             generate_features: true,
-        }),
+        })),
         "GameType" => set_game_type test(1i32 => game_type = 1),
         "generatorName" => set_generator_name test("Test".to_string() => generator_name = Some("Test".to_string())),
         "generatorOptions" => set_generator_options test(std::collections::HashMap::new() => generator_options = Some(std::collections::HashMap::new())),
@@ -66,12 +76,12 @@ mod_try_from_tag!(
             ("Name".to_string(), "name".to_string().into()),
             ("Series".to_string(), "ser".to_string().into()),
             ("Snapshot".to_string(), 1i8.into()),
-        ]) => version_info = crate::data::file_format::level_dat::Version {
+        ]) => version_info = Some(crate::data::file_format::level_dat::Version {
             id: 1,
             name: "name".to_string(),
-            series: "ser".to_string(),
+            series: Some("ser".to_string()),
             snapshot: true
-        }),
+        })),
         "WanderingTraderId" => set_wandering_trader_id test(crate::nbt::Array::<i32>::from(vec![]) => wandering_trader_id = crate::nbt::Array::from(vec![])),
         "WanderingTraderSpawnChance" => set_wandering_trader_spawn_chance test(1i32 => wandering_trader_spawn_chance = 1),
         "WanderingTraderSpawnDelay" => set_wandering_trader_spawn_delay test(1i32 => wandering_trader_spawn_delay = 1),
@@ -79,6 +89,7 @@ mod_try_from_tag!(
     ] ? [
         CustomBossEvent,
         DataPacks,
+        DragonFight,
         WorldGenSettings,
         Player,
         Version,
@@ -99,6 +110,12 @@ mod_try_from_tag!(
         "Disabled" => set_disabled test(crate::nbt::List::from(vec![]) => disabled = crate::nbt::List::from(vec![])),
         "Enabled" => set_enabled test(crate::nbt::List::from(vec![]) => enabled = crate::nbt::List::from(vec![])),
     ],
+    DragonFight: [
+        "Gateways" => set_gateways test(crate::nbt::List::<crate::nbt::Tag>::from(vec![]) => gateways = crate::nbt::List::from(vec![])),
+        "DragonKilled" => set_dragon_killed test(1i8 => dragon_killed = true),
+        "PreviouslyKilled" => set_previously_killed test(1i8 => previously_killed = true),
+        "Dragon" => set_dragon test(crate::nbt::Array::<i32>::from(vec![1, 2, 3, 4]) => dragon = Some(crate::nbt::Array::from(vec![1, 2, 3, 4]))),
+    ],
     WorldGenSettings: [
         "bonus_chest" => set_bonus_chest test(1i8 => bonus_chest = true),
         "dimensions" => set_dimensions test(std::collections::HashMap::new() => dimensions = std::collections::HashMap::new()),
@@ -110,7 +127,194 @@ mod_try_from_tag!(
     Version: [
         "Id" => set_id test(1i32 => id = 1),
         "Name" => set_name test("name".to_string() => name = "name".to_string()),
-        "Series" => set_series test("ser".to_string() => series = "ser".to_string()),
+        "Series" => set_series test("ser".to_string() => series = Some("ser".to_string())),
         "Snapshot" => set_snapshot test(1i8 => snapshot = true),
     ],
 );
+
+#[cfg(test)]
+mod tests {
+    use super::macro_tests::LevelDat_test_data_provider;
+    use crate::{
+        data::file_format::level_dat::{Difficulty, GameMode, LevelDat, Version},
+        nbt::Tag,
+    };
+    use std::collections::HashMap;
+    use test_case::test_case;
+
+    #[test_case(&["RandomSeed"] => Some(1); "Modern layout")]
+    #[test_case(&["WorldGenSettings"] => Some(2); "Legacy layout")]
+    #[test_case(&[] => Some(1); "Both layouts")]
+    #[test_case(&["WorldGenSettings", "RandomSeed"] => None; "No seed")]
+    fn test_seed(remove: &[&str]) -> Option<i64> {
+        let mut data =
+            crate::test_util::with(LevelDat_test_data_provider(), "RandomSeed", 2i64.into());
+        remove.iter().for_each(|key| {
+            data.remove(*key);
+        });
+        LevelDat::try_from(data)
+            .expect("Error parsing level.dat")
+            .seed()
+    }
+
+    #[test_case(0 => Difficulty::Peaceful; "Peaceful")]
+    #[test_case(1 => Difficulty::Easy; "Easy")]
+    #[test_case(2 => Difficulty::Normal; "Normal")]
+    #[test_case(3 => Difficulty::Hard; "Hard")]
+    #[test_case(4 => Difficulty::Unknown(4); "Out of range")]
+    #[test_case(-1 => Difficulty::Unknown(-1); "Negative")]
+    fn test_difficulty(difficulty: i8) -> Difficulty {
+        let data = crate::test_util::with(
+            LevelDat_test_data_provider(),
+            "Difficulty",
+            difficulty.into(),
+        );
+        LevelDat::try_from(data)
+            .expect("Error parsing level.dat")
+            .difficulty()
+    }
+
+    #[test_case(0 => GameMode::Survival; "Survival")]
+    #[test_case(1 => GameMode::Creative; "Creative")]
+    #[test_case(2 => GameMode::Adventure; "Adventure")]
+    #[test_case(3 => GameMode::Spectator; "Spectator")]
+    #[test_case(4 => GameMode::Unknown(4); "Out of range")]
+    fn test_default_gamemode(game_type: i32) -> GameMode {
+        let data =
+            crate::test_util::with(LevelDat_test_data_provider(), "GameType", game_type.into());
+        LevelDat::try_from(data)
+            .expect("Error parsing level.dat")
+            .default_gamemode()
+    }
+
+    #[test_case(0 => false; "Survival world")]
+    #[test_case(1 => true; "Hardcore world")]
+    fn test_is_hardcore(hardcore: i8) -> bool {
+        let data =
+            crate::test_util::with(LevelDat_test_data_provider(), "hardcore", hardcore.into());
+        LevelDat::try_from(data)
+            .expect("Error parsing level.dat")
+            .is_hardcore()
+    }
+
+    fn dragon_fight(gateways: &[i32]) -> Tag {
+        Tag::Compound(HashMap::from_iter([
+            (
+                "Gateways".to_string(),
+                Tag::List(crate::nbt::List::from(
+                    gateways.iter().copied().map(Tag::Int).collect::<Vec<_>>(),
+                )),
+            ),
+            ("DragonKilled".to_string(), Tag::Byte(1)),
+            ("PreviouslyKilled".to_string(), Tag::Byte(1)),
+        ]))
+    }
+
+    #[test_case(&[0, 18, 9, 27] => Some(4); "Four gateways left")]
+    #[test_case(&[] => Some(0); "No gateways left")]
+    fn test_gateways_remaining(gateways: &[i32]) -> Option<usize> {
+        let data = crate::test_util::with(
+            LevelDat_test_data_provider(),
+            "DragonFight",
+            dragon_fight(gateways),
+        );
+        LevelDat::try_from(data)
+            .expect("Error parsing level.dat")
+            .dragon_fight()
+            .map(|dragon_fight| dragon_fight.gateways_remaining())
+    }
+
+    #[test]
+    fn test_legacy_dragon_fight() {
+        let mut data = crate::test_util::with(
+            LevelDat_test_data_provider(),
+            "DimensionData",
+            Tag::Compound(HashMap::from_iter([(
+                "1".to_string(),
+                Tag::Compound(HashMap::from_iter([(
+                    "DragonFight".to_string(),
+                    dragon_fight(&[3, 5]),
+                )])),
+            )])),
+        );
+        data.remove("DragonFight");
+        let level_dat = LevelDat::try_from(data).expect("Error parsing level.dat");
+        assert_eq!(level_dat.dragon_fight, None);
+        let dragon_fight = level_dat.dragon_fight().expect("Legacy dragon fight");
+        assert_eq!(dragon_fight.gateways_remaining(), 2);
+        assert!(dragon_fight.dragon_killed);
+    }
+
+    #[test]
+    fn test_game_rules() {
+        let game_rules = HashMap::from_iter([
+            ("keepInventory".to_string(), Tag::String("true".to_string())),
+            (
+                "doEntityDrops".to_string(),
+                Tag::String("false".to_string()),
+            ),
+            (
+                "maxEntityCramming".to_string(),
+                Tag::String("24".to_string()),
+            ),
+        ]);
+        let data = crate::test_util::with(
+            LevelDat_test_data_provider(),
+            "GameRules",
+            Tag::Compound(game_rules),
+        );
+        let level_dat = LevelDat::try_from(data).expect("Error parsing level.dat");
+        let game_rules = level_dat.game_rules();
+        assert_eq!(game_rules.get("keepInventory"), Some("true"));
+        assert_eq!(game_rules.get_bool("keepInventory"), Some(true));
+        assert_eq!(game_rules.get_bool("doEntityDrops"), Some(false));
+        assert_eq!(game_rules.get_int("maxEntityCramming"), Some(24));
+        assert_eq!(game_rules.get_int("keepInventory"), None);
+        assert_eq!(game_rules.get_bool("maxEntityCramming"), None);
+        assert_eq!(game_rules.get_bool("doDaylightCycle"), None);
+    }
+
+    #[test_case(3953, "1.21", 0, Version { id: 3953, name: "1.21".to_string(), series: Some("main".to_string()), snapshot: false }; "Release")]
+    #[test_case(3832, "24w14a", 1, Version { id: 3832, name: "24w14a".to_string(), series: Some("main".to_string()), snapshot: true }; "Snapshot")]
+    fn test_game_version(id: i32, name: &str, snapshot: i8, expected: Version) {
+        let version = HashMap::from_iter([
+            ("Id".to_string(), Tag::Int(id)),
+            ("Name".to_string(), Tag::String(name.to_string())),
+            ("Series".to_string(), Tag::String("main".to_string())),
+            ("Snapshot".to_string(), Tag::Byte(snapshot)),
+        ]);
+        let data = crate::test_util::with(
+            LevelDat_test_data_provider(),
+            "Version",
+            Tag::Compound(version),
+        );
+        let level_dat = LevelDat::try_from(data).expect("Error parsing level.dat");
+        assert_eq!(level_dat.game_version(), Some(&expected));
+    }
+
+    #[test]
+    fn test_game_version_missing() {
+        let mut data = LevelDat_test_data_provider();
+        data.remove("Version");
+        let level_dat = LevelDat::try_from(data).expect("Error parsing level.dat");
+        assert_eq!(level_dat.game_version(), None);
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_last_played_datetime() {
+        let data = crate::test_util::with(
+            LevelDat_test_data_provider(),
+            "LastPlayed",
+            1_700_000_000_000i64.into(),
+        );
+        let level_dat = LevelDat::try_from(data).expect("Error parsing level.dat");
+        assert_eq!(level_dat.last_played, 1_700_000_000_000);
+        assert_eq!(
+            level_dat
+                .last_played_datetime()
+                .map(|date| date.to_rfc3339()),
+            Some("2023-11-14T22:13:20+00:00".to_string())
+        );
+    }
+}
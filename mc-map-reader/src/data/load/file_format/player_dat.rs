@@ -4,7 +4,7 @@ use crate::{
     data::{
         file_format::player_dat::*,
         load::{
-            entity::{EntityError, MobError},
+            entity::{take_mob_from_nbt, EntityError, MobError},
             item::{ItemError, ItemWithSlotError},
         },
         FieldError,
@@ -106,10 +106,10 @@ fn parse_player(
         "XpTotal": set_xp_total,
     ]);
     builder.set_mob(
-        nbt_data
-            .try_into()
-            .map_err(|e| FieldError::new("<internal> mob", e))?,
+        take_mob_from_nbt(&mut nbt_data).map_err(|e| FieldError::new("<internal> mob", e))?,
     );
+    // Every key left was not read into the player or its mob
+    builder.set_extra(nbt_data);
     Ok(())
 }
 
@@ -120,7 +120,9 @@ pub mod tests {
 
     use crate::{
         data::{
-            file_format::player_dat::Player,
+            file_format::player_dat::{
+                Player, RecipeBook, RecipeBookGui, RootVehicle, SpawnPoint, WardenSpawnTracker,
+            },
             load::{
                 entity::{
                     macro_tests::{Entity_test_data_provider, Entity_test_result},
@@ -129,8 +131,9 @@ pub mod tests {
                 item::macro_tests::{Item_test_data_provider, Item_test_result},
             },
         },
-        nbt::{List, Tag},
+        nbt::{Array, List, Tag},
     };
+    use test_case::test_case;
 
     #[test]
     fn test_parse_player() {
@@ -140,6 +143,299 @@ pub mod tests {
         )
     }
 
+    #[test]
+    fn test_parse_player_extra() {
+        let mut nbt_data = player_test_data_provider();
+        nbt_data.insert("isFiltering".to_string(), Tag::Byte(1));
+        nbt_data.insert(
+            "clientsideChat".to_string(),
+            Tag::String("enabled".to_string()),
+        );
+        let player = Player::try_from(nbt_data).expect("Error parsing player");
+        assert_eq!(
+            player.extra,
+            HashMap::from_iter([
+                ("isFiltering".to_string(), Tag::Byte(1)),
+                (
+                    "clientsideChat".to_string(),
+                    Tag::String("enabled".to_string())
+                ),
+            ])
+        );
+        assert_eq!(player.mob, mob_test_result());
+    }
+
+    #[test_case("Air", Tag::Short(1) => false; "Entity key")]
+    #[test_case("Health", Tag::Float(20.) => false; "Mob key")]
+    #[test_case("leash", Tag::IntArray(Array::from(vec![1, 2, 3])) => false; "Leash position")]
+    #[test_case("isFiltering", Tag::Byte(1) => true; "Unknown key")]
+    fn test_parse_player_extra_key(key: &str, value: Tag) -> bool {
+        let mut nbt_data = player_test_data_provider();
+        nbt_data.insert(key.to_string(), value);
+        let player = Player::try_from(nbt_data).expect("Error parsing player");
+        player.extra.contains_key(key)
+    }
+
+    #[test_case(0, false => vec!["minecraft:shulker_box", "minecraft:ender_pearl"]; "Selected item in inventory")]
+    #[test_case(5, false => vec!["minecraft:shulker_box", "minecraft:ender_pearl", "minecraft:stick"]; "Selected item not in inventory")]
+    #[test_case(0, true => vec!["minecraft:shulker_box", "minecraft:diamond", "minecraft:ender_pearl"]; "Include contained items")]
+    fn test_all_items(selected_item_slot: i32, include_contained_items: bool) -> Vec<String> {
+        let item = |id: &str, tag| crate::data::item::Item {
+            id: id.to_string(),
+            tag,
+            components: None,
+            count: 1,
+        };
+        let shulker_box_tag = HashMap::from_iter([(
+            "BlockEntityTag".to_string(),
+            Tag::Compound(HashMap::from_iter([(
+                "Items".to_string(),
+                Tag::List(List::from(vec![Tag::Compound(HashMap::from_iter([
+                    ("Slot".to_string(), Tag::Byte(0)),
+                    (
+                        "id".to_string(),
+                        Tag::String("minecraft:diamond".to_string()),
+                    ),
+                    ("Count".to_string(), Tag::Byte(64)),
+                ]))])),
+            )])),
+        )]);
+        let player = Player {
+            inventory: List::from(vec![crate::data::item::ItemWithSlot {
+                slot: 0,
+                item: item("minecraft:shulker_box", Some(shulker_box_tag)),
+            }]),
+            ender_items: List::from(vec![crate::data::item::ItemWithSlot {
+                slot: 0,
+                item: item("minecraft:ender_pearl", None),
+            }]),
+            selected_item: Some(item("minecraft:stick", None)),
+            selected_item_slot,
+            ..player_test_result()
+        };
+        player
+            .all_items(include_contained_items)
+            .into_iter()
+            .map(|item| item.id.clone())
+            .collect()
+    }
+
+    #[test_case(3 => Some("minecraft:diamond_sword".to_string()); "Holding item")]
+    #[test_case(4 => None; "Empty slot")]
+    fn test_held_item(selected_item_slot: i32) -> Option<String> {
+        let item = |slot, id: &str| crate::data::item::ItemWithSlot {
+            slot,
+            item: crate::data::item::Item {
+                id: id.to_string(),
+                tag: None,
+                components: None,
+                count: 1,
+            },
+        };
+        let player = Player {
+            inventory: List::from(vec![
+                item(0, "minecraft:torch"),
+                item(3, "minecraft:diamond_sword"),
+                item(12, "minecraft:cobblestone"),
+            ]),
+            selected_item_slot,
+            ..player_test_result()
+        };
+        player.held_item().map(|item| item.id.clone())
+    }
+
+    #[test]
+    fn test_root_vehicle_horse() {
+        let root_vehicle = RootVehicle::try_from(HashMap::from_iter([
+            (
+                "Entity".to_string(),
+                Tag::Compound(HashMap::from_iter([
+                    ("id".to_string(), Tag::String("minecraft:horse".to_string())),
+                    (
+                        "UUID".to_string(),
+                        Tag::IntArray(Array::from(vec![1, 2, 3, 4])),
+                    ),
+                ])),
+            ),
+            (
+                "Attach".to_string(),
+                Tag::IntArray(Array::from(vec![1, 2, 3, 4])),
+            ),
+        ]))
+        .expect("Error parsing root vehicle");
+        assert_eq!(root_vehicle.vehicle_id(), Some("minecraft:horse"));
+        assert_eq!(root_vehicle.mount().id.as_deref(), Some("minecraft:horse"));
+    }
+
+    #[test]
+    fn test_root_vehicle_passenger_chain() {
+        let root_vehicle = RootVehicle::try_from(HashMap::from_iter([
+            (
+                "Entity".to_string(),
+                Tag::Compound(HashMap::from_iter([
+                    (
+                        "id".to_string(),
+                        Tag::String("minecraft:oak_boat".to_string()),
+                    ),
+                    (
+                        "UUID".to_string(),
+                        Tag::IntArray(Array::from(vec![1, 2, 3, 4])),
+                    ),
+                    (
+                        "Passengers".to_string(),
+                        Tag::List(List::from(vec![Tag::Compound(HashMap::from_iter([
+                            ("id".to_string(), Tag::String("minecraft:pig".to_string())),
+                            (
+                                "UUID".to_string(),
+                                Tag::IntArray(Array::from(vec![5, 6, 7, 8])),
+                            ),
+                        ]))])),
+                    ),
+                ])),
+            ),
+            (
+                "Attach".to_string(),
+                Tag::IntArray(Array::from(vec![5, 6, 7, 8])),
+            ),
+        ]))
+        .expect("Error parsing root vehicle");
+        assert_eq!(root_vehicle.vehicle_id(), Some("minecraft:oak_boat"));
+        assert_eq!(root_vehicle.mount().id.as_deref(), Some("minecraft:pig"));
+    }
+
+    #[test]
+    fn test_spawn_point() {
+        assert_eq!(
+            player_test_result().spawn_point(),
+            Some(SpawnPoint {
+                x: 12,
+                y: 75,
+                z: 13,
+                dimension: "minecraft:overworld".to_string(),
+                forced: true,
+            })
+        );
+    }
+
+    #[test]
+    fn test_unset_spawn_point() {
+        let player = Player {
+            spawn_x: 0,
+            spawn_y: 0,
+            spawn_z: 0,
+            spawn_dimension: String::new(),
+            spawn_forced: false,
+            ..player_test_result()
+        };
+        assert_eq!(player.spawn_point(), None);
+    }
+
+    #[test]
+    fn test_health_stats() {
+        let mut player = player_test_result();
+        player.mob.health = Some(7.5);
+        player.mob.absorption_amount = Some(4.);
+        player.mob.hurt_time = Some(10);
+        player.mob.entity.air = Some(120);
+        assert_eq!(player.health(), 7.5);
+        assert_eq!(player.absorption_amount(), 4.);
+        assert_eq!(player.hurt_time(), 10);
+        assert_eq!(player.death_time(), 0);
+        assert_eq!(player.air(), 120);
+        assert!(!player.is_dead());
+    }
+
+    #[test]
+    fn test_health_stats_unset() {
+        let mut player = player_test_result();
+        player.mob.health = None;
+        player.mob.absorption_amount = None;
+        player.mob.hurt_time = None;
+        player.mob.death_time = None;
+        player.mob.entity.air = None;
+        assert_eq!(player.health(), 20.);
+        assert_eq!(player.absorption_amount(), 0.);
+        assert_eq!(player.hurt_time(), 0);
+        assert_eq!(player.death_time(), 0);
+        assert_eq!(player.air(), 300);
+    }
+
+    #[test]
+    fn test_inventory_slots() {
+        let item = |slot| crate::data::item::ItemWithSlot {
+            slot,
+            item: crate::data::item::Item {
+                id: "minecraft:diamond".to_string(),
+                tag: None,
+                components: None,
+                count: 64,
+            },
+        };
+        let player = Player {
+            // Hotbar, main inventory, armor and offhand
+            inventory: List::from(vec![
+                item(0),
+                item(8),
+                item(9),
+                item(35),
+                item(100),
+                item(103),
+                item(-106),
+            ]),
+            ..player_test_result()
+        };
+        assert_eq!(player.used_inventory_slots(), 4);
+        assert_eq!(player.empty_inventory_slots(), 32);
+    }
+
+    #[test]
+    fn test_open_recipe_book_guis() {
+        let recipe_book = RecipeBook {
+            is_gui_open: true,
+            is_furnace_gui_open: false,
+            is_blasting_furnace_gui_open: true,
+            is_smoker_gui_open: false,
+            ..RecipeBook_test_result()
+        };
+        assert_eq!(
+            recipe_book.open_guis(),
+            vec![RecipeBookGui::Crafting, RecipeBookGui::BlastFurnace]
+        );
+    }
+
+    #[test_case(-1 => 0; "Negative")]
+    #[test_case(0 => 0; "No warnings")]
+    #[test_case(2 => 2; "Two warnings")]
+    #[test_case(3 => 3; "Highest stage")]
+    #[test_case(4 => 3; "Warden summoned")]
+    #[test_case(100 => 3; "Out of range")]
+    fn test_warning_stage(warning_level: i32) -> u8 {
+        WardenSpawnTracker {
+            warning_level,
+            ..WardenSpawnTracker_test_result()
+        }
+        .warning_stage()
+    }
+
+    #[test_case(3, 0 => true; "Highest stage")]
+    #[test_case(7, 0 => true; "Out of range")]
+    #[test_case(3, 200 => false; "Cooldown")]
+    #[test_case(2, 0 => false; "Low stage")]
+    fn test_next_warning_summons_warden(warning_level: i32, cooldown_ticks: i32) -> bool {
+        WardenSpawnTracker {
+            warning_level,
+            cooldown_ticks,
+            ..WardenSpawnTracker_test_result()
+        }
+        .next_warning_summons_warden()
+    }
+
+    #[test]
+    fn test_is_dead() {
+        let player = player_test_result();
+        assert!(player.is_dead());
+    }
+
     pub fn player_test_data_provider() -> HashMap<String, Tag> {
         let mut map: HashMap<String, Tag> = [
             ("abilities", PlayerAbilities_test_data_provider().into()),
@@ -227,6 +523,7 @@ pub mod tests {
             xp_p: 45.,
             xp_seed: 96,
             xp_total: 12,
+            extra: HashMap::new(),
             mob: mob_test_result(),
         }
     }
@@ -8,12 +8,21 @@ use crate::{
 mod_try_from_tag!(
     Item: [
         "Count" => set_count test(10_i8 => count = 10; ItemBuilderError::UnsetCount),
+        "count" => set_count_int,
         "id" => set_id test("test_id".to_string() => id = "test_id".to_string(); ItemBuilderError::UnsetId),
         "tag" => set_tag test(HashMap::new() => tag = Some(HashMap::new())),
+        "components" => set_components test(HashMap::new() => components = Some(HashMap::new())),
     ],
     ItemWithSlot: parse_item_with_slot ? [ Item, ],
 );
 
+impl ItemBuilder {
+    /// Since 1.20.5 the count is stored as an int. Counts outside of the range of the legacy byte are clamped.
+    fn set_count_int(&mut self, count: i32) {
+        self.set_count(count.clamp(i8::MIN.into(), i8::MAX.into()) as i8);
+    }
+}
+
 fn parse_item_with_slot(
     builder: &mut ItemWithSlotBuilder,
     mut nbt_data: HashMap<String, Tag>,
@@ -32,6 +41,7 @@ fn parse_item_with_slot(
 #[cfg(test)]
 pub mod tests {
     use super::{macro_tests::*, *};
+    use crate::test_util::compound;
     use test_case::test_case;
 
     #[test_case(vec![
@@ -45,6 +55,7 @@ pub mod tests {
             count: 10,
             id: "test_id".to_string(),
             tag: Some(HashMap::new()),
+            components: None,
         },
     }); "Success")]
     #[test_case(vec![
@@ -72,6 +83,7 @@ pub mod tests {
             count: 10,
             id: "test_id".to_string(),
             tag: None,
+            components: None,
         },
     }); "Success without tag")]
     fn test_parse_item_with_slot(
@@ -83,6 +95,316 @@ pub mod tests {
         nbt_data.try_into()
     }
 
+    #[test_case(Tag::Int(1) => Ok(1); "Single item")]
+    #[test_case(Tag::Int(99) => Ok(99); "Modded stack size")]
+    #[test_case(Tag::Int(1000) => Ok(i8::MAX); "Clamped")]
+    #[test_case(Tag::Byte(1) => Err(ItemError::NbtField(FieldError::new("count", crate::nbt::Error::InvalidValue))); "Wrong type")]
+    fn test_parse_component_item_count(count: Tag) -> Result<i8, ItemError> {
+        Item::try_from(compound(vec![
+            ("id", Tag::String("minecraft:diamond_sword".to_string())),
+            ("count", count),
+            (
+                "components",
+                compound(vec![("minecraft:damage", Tag::Int(12))]),
+            ),
+        ]))
+        .map(|item| item.count)
+    }
+
+    #[test]
+    fn test_parse_component_item_with_slot() {
+        let item = ItemWithSlot::try_from(compound(vec![
+            ("Slot", Tag::Byte(3)),
+            ("id", Tag::String("minecraft:diamond_sword".to_string())),
+            ("count", Tag::Int(1)),
+            (
+                "components",
+                compound(vec![("minecraft:damage", Tag::Int(12))]),
+            ),
+        ]))
+        .expect("Component item is valid");
+        assert_eq!(item.slot, 3);
+        assert_eq!(item.item.count, 1);
+        assert_eq!(item.item.damage(), Some(12));
+    }
+
+    fn item_with_data(tag: Option<(&str, Tag)>, components: Option<(&str, Tag)>) -> Item {
+        let to_map = |(key, value): (&str, Tag)| HashMap::from_iter([(key.to_string(), value)]);
+        Item {
+            tag: tag.map(to_map),
+            components: components.map(to_map),
+            ..Item_test_result()
+        }
+    }
+
+    #[test_case(None, None => None; "Not set")]
+    #[test_case(Some(("Damage", Tag::Int(12))), None => Some(12); "Legacy tag")]
+    #[test_case(None, Some(("minecraft:damage", Tag::Int(12))) => Some(12); "Component")]
+    #[test_case(Some(("Damage", Tag::Int(3))), Some(("minecraft:damage", Tag::Int(12))) => Some(12); "Component takes precedence")]
+    fn test_damage(tag: Option<(&str, Tag)>, components: Option<(&str, Tag)>) -> Option<i32> {
+        item_with_data(tag, components).damage()
+    }
+
+    #[test_case(None, None => None; "Not set")]
+    #[test_case(Some(("CustomModelData", Tag::Int(7))), None => Some(7); "Legacy tag")]
+    #[test_case(None, Some(("minecraft:custom_model_data", Tag::Int(7))) => Some(7); "Component")]
+    fn test_custom_model_data(
+        tag: Option<(&str, Tag)>,
+        components: Option<(&str, Tag)>,
+    ) -> Option<i32> {
+        item_with_data(tag, components).custom_model_data()
+    }
+
+    #[test_case(None, None => None; "Not set")]
+    #[test_case(Some(("display", compound(vec![("Name", Tag::String(r#"{"text":"Excalibur"}"#.to_string()))]))), None => Some("Excalibur".to_string()); "Legacy tag")]
+    #[test_case(None, Some(("minecraft:custom_name", Tag::String(r#"{"text":"Excalibur"}"#.to_string()))) => Some("Excalibur".to_string()); "Component")]
+    #[test_case(None, Some(("minecraft:custom_name", compound(vec![("text", Tag::String("Excalibur".to_string()))]))) => Some("Excalibur".to_string()); "NBT component")]
+    fn test_custom_name(
+        tag: Option<(&str, Tag)>,
+        components: Option<(&str, Tag)>,
+    ) -> Option<String> {
+        item_with_data(tag, components).custom_name()
+    }
+
+    #[test_case(None, None => Vec::<(String, i32)>::new(); "Not set")]
+    #[test_case(Some(("Enchantments", Tag::List(crate::nbt::List::from(vec![
+        compound(vec![("id", Tag::String("minecraft:sharpness".to_string())), ("lvl", Tag::Short(5))]),
+        compound(vec![("id", Tag::String("minecraft:looting".to_string())), ("lvl", Tag::Short(3))]),
+    ])))), None => vec![("minecraft:looting".to_string(), 3), ("minecraft:sharpness".to_string(), 5)]; "Legacy tag")]
+    #[test_case(None, Some(("minecraft:enchantments", compound(vec![("levels", compound(vec![
+        ("minecraft:sharpness", Tag::Int(5)),
+    ]))]))) => vec![("minecraft:sharpness".to_string(), 5)]; "Component with levels")]
+    #[test_case(None, Some(("minecraft:enchantments", compound(vec![
+        ("minecraft:sharpness", Tag::Int(5)),
+    ]))) => vec![("minecraft:sharpness".to_string(), 5)]; "Component")]
+    fn test_enchantments(
+        tag: Option<(&str, Tag)>,
+        components: Option<(&str, Tag)>,
+    ) -> Vec<(String, i32)> {
+        item_with_data(tag, components).enchantments()
+    }
+
+    fn block_list(blocks: &[&str]) -> Tag {
+        Tag::List(crate::nbt::List::from(
+            blocks
+                .iter()
+                .map(|block| Tag::String(block.to_string()))
+                .collect::<Vec<_>>(),
+        ))
+    }
+
+    #[test_case(None, None => Vec::<String>::new(); "Not set")]
+    #[test_case(Some(("CanPlaceOn", block_list(&["minecraft:stone", "minecraft:dirt"]))), None => vec!["minecraft:stone", "minecraft:dirt"]; "Legacy tag")]
+    #[test_case(None, Some(("minecraft:can_place_on", compound(vec![
+        ("predicates", Tag::List(crate::nbt::List::from(vec![
+            compound(vec![("blocks", Tag::String("minecraft:stone".to_string()))]),
+            compound(vec![("blocks", block_list(&["minecraft:dirt", "#minecraft:logs"]))]),
+        ]))),
+    ]))) => vec!["minecraft:stone", "minecraft:dirt", "#minecraft:logs"]; "Component")]
+    #[test_case(None, Some(("minecraft:can_place_on", compound(vec![
+        ("blocks", Tag::String("minecraft:stone".to_string())),
+    ]))) => vec!["minecraft:stone"]; "Single predicate")]
+    #[test_case(Some(("CanDestroy", block_list(&["minecraft:stone"]))), None => Vec::<String>::new(); "Other list")]
+    fn test_can_place_on(tag: Option<(&str, Tag)>, components: Option<(&str, Tag)>) -> Vec<String> {
+        item_with_data(tag, components).can_place_on()
+    }
+
+    #[test_case(Some(("CanDestroy", block_list(&["minecraft:glass"]))), None => vec!["minecraft:glass"]; "Legacy tag")]
+    #[test_case(None, Some(("minecraft:can_break", Tag::List(crate::nbt::List::from(vec![
+        compound(vec![("blocks", Tag::String("minecraft:glass".to_string()))]),
+    ])))) => vec!["minecraft:glass"]; "Component list")]
+    fn test_can_destroy(tag: Option<(&str, Tag)>, components: Option<(&str, Tag)>) -> Vec<String> {
+        item_with_data(tag, components).can_destroy()
+    }
+
+    #[test_case(None, None => false; "Not set")]
+    #[test_case(Some(("Unbreakable", Tag::Byte(1))), None => true; "Legacy tag")]
+    #[test_case(Some(("Unbreakable", Tag::Byte(0))), None => false; "Legacy tag disabled")]
+    #[test_case(None, Some(("minecraft:unbreakable", Tag::Compound(HashMap::new()))) => true; "Component")]
+    fn test_is_unbreakable(tag: Option<(&str, Tag)>, components: Option<(&str, Tag)>) -> bool {
+        item_with_data(tag, components).is_unbreakable()
+    }
+
+    #[test_case(None, None, "fire_resistant" => None; "Not set")]
+    #[test_case(None, Some(("minecraft:fire_resistant", Tag::Compound(HashMap::new()))), "minecraft:fire_resistant" => Some(Tag::Compound(HashMap::new())); "Component")]
+    #[test_case(None, Some(("minecraft:fire_resistant", Tag::Compound(HashMap::new()))), "fire_resistant" => Some(Tag::Compound(HashMap::new())); "Without namespace")]
+    #[test_case(None, Some(("minecraft:fire_resistant", Tag::Compound(HashMap::new()))), "minecraft:glider" => None; "Other component")]
+    #[test_case(Some(("Unbreakable", Tag::Byte(1))), None, "unbreakable" => Some(Tag::Byte(1)); "Legacy tag")]
+    #[test_case(Some(("Unbreakable", Tag::Byte(1))), Some(("minecraft:unbreakable", Tag::Compound(HashMap::new()))), "unbreakable" => Some(Tag::Compound(HashMap::new())); "Component takes precedence")]
+    #[test_case(Some(("display", Tag::Compound(HashMap::from_iter([("color".to_string(), Tag::Int(16711680))])))), None, "dyed_color" => Some(Tag::Int(16711680)); "Nested legacy tag")]
+    #[test_case(Some(("display", Tag::Compound(HashMap::from_iter([("Name".to_string(), Tag::String(r#"{"text":"Sword"}"#.to_string()))])))), None, "dyed_color" => None; "Renamed legacy item is not dyed")]
+    fn test_component(
+        tag: Option<(&str, Tag)>,
+        components: Option<(&str, Tag)>,
+        name: &str,
+    ) -> Option<Tag> {
+        let item = item_with_data(tag, components);
+        assert_eq!(item.has_component(name), item.component(name).is_some());
+        item.component(name).cloned()
+    }
+
+    #[test_case(None => None; "Not set")]
+    #[test_case(Some(Tag::Byte(1)) => Some(true); "Forced")]
+    #[test_case(Some(Tag::Byte(0)) => Some(false); "Hidden")]
+    fn test_enchantment_glint_override(glint: Option<Tag>) -> Option<bool> {
+        Item {
+            components: glint.map(|glint| {
+                HashMap::from_iter([("minecraft:enchantment_glint_override".to_string(), glint)])
+            }),
+            ..Item_test_result()
+        }
+        .enchantment_glint_override()
+    }
+
+    #[test_case(None => None; "Not set")]
+    #[test_case(Some(Tag::Int(4)) => Some(4); "Bad Omen V")]
+    #[test_case(Some(Tag::Int(5)) => Some(5); "Edited")]
+    #[test_case(Some(Tag::Byte(1)) => None; "Wrong type")]
+    fn test_ominous_bottle_amplifier(amplifier: Option<Tag>) -> Option<i32> {
+        Item {
+            id: "minecraft:ominous_bottle".to_string(),
+            components: amplifier.map(|amplifier| {
+                HashMap::from_iter([("minecraft:ominous_bottle_amplifier".to_string(), amplifier)])
+            }),
+            ..Item_test_result()
+        }
+        .ominous_bottle_amplifier()
+    }
+
+    #[test]
+    fn test_contained_items_from_container_component() {
+        let entry = |slot: i32, id: &str, count: Option<i32>| {
+            let mut item = HashMap::from([("id".to_string(), Tag::String(id.to_string()))]);
+            if let Some(count) = count {
+                item.insert("count".to_string(), Tag::Int(count));
+            }
+            Tag::Compound(HashMap::from([
+                ("slot".to_string(), Tag::Int(slot)),
+                ("item".to_string(), Tag::Compound(item)),
+            ]))
+        };
+        let shulker_box = Item {
+            id: "minecraft:shulker_box".to_string(),
+            tag: None,
+            components: Some(HashMap::from([(
+                "minecraft:container".to_string(),
+                Tag::List(crate::nbt::List::from(vec![
+                    entry(0, "minecraft:diamond", Some(64)),
+                    entry(26, "minecraft:elytra", None),
+                ])),
+            )])),
+            count: 1,
+        };
+        assert_eq!(
+            shulker_box
+                .contained_items()
+                .into_iter()
+                .map(|item| (item.slot, item.item.id, item.item.count))
+                .collect::<Vec<_>>(),
+            vec![
+                (0, "minecraft:diamond".to_string(), 64),
+                (26, "minecraft:elytra".to_string(), 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_bundle_contents() {
+        let stack = |id: &str, count: i32| {
+            Tag::Compound(HashMap::from([
+                ("id".to_string(), Tag::String(id.to_string())),
+                ("count".to_string(), Tag::Int(count)),
+            ]))
+        };
+        let legacy_stack = |id: &str, count: i8| {
+            Tag::Compound(HashMap::from([
+                ("id".to_string(), Tag::String(id.to_string())),
+                ("Count".to_string(), Tag::Byte(count)),
+            ]))
+        };
+        let bundle = Item {
+            id: "minecraft:bundle".to_string(),
+            tag: None,
+            components: Some(HashMap::from([(
+                "minecraft:bundle_contents".to_string(),
+                Tag::List(crate::nbt::List::from(vec![
+                    stack("minecraft:diamond", 16),
+                    stack("minecraft:emerald", 32),
+                ])),
+            )])),
+            count: 1,
+        };
+        let legacy_bundle = Item {
+            id: "minecraft:bundle".to_string(),
+            tag: Some(HashMap::from([(
+                "Items".to_string(),
+                Tag::List(crate::nbt::List::from(vec![
+                    legacy_stack("minecraft:diamond", 16),
+                    legacy_stack("minecraft:emerald", 32),
+                ])),
+            )])),
+            components: None,
+            count: 1,
+        };
+        let expected = vec![
+            ("minecraft:diamond".to_string(), 16),
+            ("minecraft:emerald".to_string(), 32),
+        ];
+        for bundle in [bundle, legacy_bundle] {
+            assert_eq!(
+                bundle
+                    .bundle_contents()
+                    .into_iter()
+                    .map(|item| (item.id, item.count))
+                    .collect::<Vec<_>>(),
+                expected
+            );
+        }
+    }
+
+    fn edited_sword(id: &str, name: &str, count: i8) -> Item {
+        Item {
+            id: id.to_string(),
+            tag: None,
+            components: Some(HashMap::from([
+                (
+                    "minecraft:custom_name".to_string(),
+                    Tag::String(format!(r#"{{"text":"{name}"}}"#)),
+                ),
+                (
+                    "minecraft:enchantments".to_string(),
+                    Tag::Compound(HashMap::from([
+                        ("minecraft:sharpness".to_string(), Tag::Int(32767)),
+                        ("minecraft:looting".to_string(), Tag::Int(255)),
+                    ])),
+                ),
+                ("minecraft:damage".to_string(), Tag::Int(0)),
+            ])),
+            count,
+        }
+    }
+
+    #[test_case(edited_sword("minecraft:diamond_sword", "Dupe", 1) => true; "Identical")]
+    #[test_case(edited_sword("diamond_sword", "Dupe", 1) => true; "Without namespace")]
+    #[test_case(edited_sword("minecraft:diamond_sword", "Dupe", 2) => true; "Different count")]
+    #[test_case(edited_sword("minecraft:diamond_sword", "Other", 1) => false; "Different name")]
+    #[test_case(edited_sword("minecraft:netherite_sword", "Dupe", 1) => false; "Different id")]
+    #[test_case(Item { components: None, ..edited_sword("minecraft:diamond_sword", "Dupe", 1) } => false; "Without components")]
+    fn test_fingerprint(other: Item) -> bool {
+        edited_sword("minecraft:diamond_sword", "Dupe", 1).fingerprint() == other.fingerprint()
+    }
+
+    #[test]
+    fn test_fingerprint_is_stable() {
+        let item = Item {
+            id: "minecraft:stone".to_string(),
+            tag: None,
+            components: None,
+            count: 1,
+        };
+        assert_eq!(item.fingerprint(), 0x2610_61d1_4ec0_bc02);
+    }
+
     pub fn item_with_slot_test_data_provider() -> HashMap<String, Tag> {
         let map = [("Slot", Tag::Byte(0))].map(|(k, v)| (k.to_string(), v));
         let mut map = HashMap::from_iter(map);
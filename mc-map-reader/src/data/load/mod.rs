@@ -2,7 +2,7 @@ macro_rules! mod_try_from_tag {
     ({
         $($name: ty:
             $(
-                [$($(if feature = $feature:literal)? $key:literal => $setter:ident test($nbt_input_value:expr => $prop:ident = $test_value:expr),)*]
+                [$($(if feature = $feature:literal)? $key:literal => $setter:ident $(test($nbt_input_value:expr => $prop:ident = $test_value:expr))?,)*]
                 $(? [$($(if feature = $error_feature:literal)? $data_type:ty,)*])?
             )?
             $(
@@ -13,7 +13,7 @@ macro_rules! mod_try_from_tag {
         mod_try_from_tag!($(
             $name:
                 $(
-                    [$($(if feature = $feature)? $key => $setter test($nbt_input_value => $prop = $test_value),)*]
+                    [$($(if feature = $feature)? $key => $setter $(test($nbt_input_value => $prop = $test_value))?,)*]
                     $(? [$($(if feature = $error_feature)? $data_type,)*])?
                 )?
                 $(
@@ -24,7 +24,7 @@ macro_rules! mod_try_from_tag {
     (
         $($(if feature = $type_feature:literal)? $name: ty:
             $(
-                [$($(if feature = $feature:literal)? $key:literal => $setter:ident test($($nbt_input_value:expr)? => $prop:ident = $test_value:expr $(; $missing_err:expr)?),)*]
+                [$($(if feature = $feature:literal)? $key:literal => $setter:ident $(test($($nbt_input_value:expr)? => $prop:ident = $test_value:expr $(; $missing_err:expr)?))?,)*]
                 $(? [$($(if feature = $error_feature:literal)? $data_type:ty,)*])?
             )?
             $(
@@ -62,9 +62,9 @@ macro_rules! mod_try_from_tag {
                 $(#[cfg(feature = $type_feature)])?
                 $(
                 #[test_case([< $name _test_data_provider >]() => Ok([< $name _test_result >]()); "Success")]
-                $($(
+                $($($(
                     #[test_case($crate::test_util::without(&[< $name _test_data_provider >], $key) => Err($missing_err.into()); $key)]
-                )?)*
+                )?)?)*
                 fn [<test_ $name>](tag: HashMap<String, Tag>) -> Result<$name, [< $name Error >]> {
                     use super::*;
                     $name::try_from(tag)
@@ -72,16 +72,16 @@ macro_rules! mod_try_from_tag {
 
                 pub fn [< $name _test_data_provider >]() -> HashMap<String, Tag> {
                     HashMap::from_iter([
-                        $(
+                        $($(
                             $(($key.to_string(), $nbt_input_value.into()),)?
-                        )*
+                        )?)*
                     ])
                 }
 
                 pub fn [< $name _test_result >]() -> $name {
-                    $name {$(
+                    $name {$($(
                         $prop: $test_value,
-                    )*}
+                    )?)*}
                 }
                 )?
             )*
@@ -98,9 +98,10 @@ macro_rules! try_from_tag {
         try_from_tag!(error $name => builder [< $name Builder >] [$($($data_type $(=> feature = $error_feature)?,)*)?]);
         try_from_tag!(other_impls $name);
         }
-        impl TryFrom<std::collections::HashMap<String, $crate::nbt::Tag>> for $name {
-            type Error = paste::paste!([< $name Error >]);
-            fn try_from(mut nbt_data: std::collections::HashMap<String, $crate::nbt::Tag>) -> Result<Self, Self::Error> {
+        impl $name {
+            /// Parse the keys of this type and leave every other key in `nbt_data`.
+            #[allow(dead_code)]
+            pub(crate) fn take_from_nbt(nbt_data: &mut std::collections::HashMap<String, $crate::nbt::Tag>) -> Result<Self, paste::paste!([< $name Error >])> {
                 type Builder = paste::paste!([<$name Builder>]);
                 let mut builder = Builder::default();
                 add_data_to_builder!(builder, nbt_data => [
@@ -113,6 +114,12 @@ macro_rules! try_from_tag {
                 Ok(b)
             }
         }
+        impl TryFrom<std::collections::HashMap<String, $crate::nbt::Tag>> for $name {
+            type Error = paste::paste!([< $name Error >]);
+            fn try_from(mut nbt_data: std::collections::HashMap<String, $crate::nbt::Tag>) -> Result<Self, Self::Error> {
+                Self::take_from_nbt(&mut nbt_data)
+            }
+        }
         impl TryFrom<$crate::nbt::Tag> for $name {
             type Error = paste::paste! { [< $name Error >] };
             fn try_from(nbt_data: $crate::nbt::Tag) -> Result<Self, Self::Error> {
@@ -1,12 +1,21 @@
 //! Data structures for Minecraft NBT data.
 
+pub mod banner;
 #[cfg(feature = "block_entity")]
 pub mod block_entity;
+pub mod book;
 #[cfg(feature = "region_file")]
 pub mod chunk;
+pub mod color;
+pub mod compass;
 pub mod dimension;
 pub mod entity;
 pub mod file_format;
+pub mod firework;
 pub mod item;
 mod load;
+pub mod player_head;
+pub mod potion;
+mod text;
+pub mod trim;
 pub use load::FieldError;
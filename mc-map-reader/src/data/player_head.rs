@@ -0,0 +1,170 @@
+//! Player heads and the profile of their owner.
+
+use std::collections::HashMap;
+
+use super::item::Item;
+use crate::nbt::{Array, Tag};
+
+/// A player head showing the skin of a player.
+/// [Minecraft Wiki](https://minecraft.fandom.com/wiki/Head#Item_data)
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlayerHead {
+    /// The name of the owner
+    pub name: Option<String>,
+    /// The UUID of the owner stored as four integers
+    pub uuid: Option<Array<i32>>,
+    /// The Base64 encoded `textures` property containing the skin URL
+    pub texture: Option<String>,
+}
+
+impl PlayerHead {
+    /// The id of player head items.
+    pub const ID: &'static str = "minecraft:player_head";
+
+    /// Get the owner of a player head.
+    /// The `minecraft:profile` component takes precedence over the legacy `SkullOwner` tag.
+    /// Returns `None` for heads without an owner.
+    pub fn from_item(item: &Item) -> Option<Self> {
+        if item.id != Self::ID {
+            return None;
+        }
+        let component = item
+            .components
+            .as_ref()
+            .and_then(|components| components.get("minecraft:profile"));
+        match component {
+            Some(Tag::String(name)) => return Some(Self::from_name(name)),
+            Some(Tag::Compound(profile)) => return Some(Self::from_profile(profile)),
+            _ => {}
+        }
+        match item.tag.as_ref()?.get("SkullOwner")? {
+            Tag::String(name) => Some(Self::from_name(name)),
+            Tag::Compound(owner) => Some(Self::from_legacy_owner(owner)),
+            _ => None,
+        }
+    }
+
+    fn from_name(name: &str) -> Self {
+        Self {
+            name: Some(name.to_string()),
+            uuid: None,
+            texture: None,
+        }
+    }
+
+    fn from_profile(profile: &HashMap<String, Tag>) -> Self {
+        let texture = match profile.get("properties") {
+            Some(Tag::List(properties)) => properties.iter().find_map(|property| {
+                let Tag::Compound(property) = property else {
+                    return None;
+                };
+                match (property.get("name"), property.get("value")) {
+                    (Some(Tag::String(name)), Some(Tag::String(value))) if name == "textures" => {
+                        Some(value.clone())
+                    }
+                    _ => None,
+                }
+            }),
+            _ => None,
+        };
+        Self {
+            name: get_string(profile, "name"),
+            uuid: get_uuid(profile, "id"),
+            texture,
+        }
+    }
+
+    fn from_legacy_owner(owner: &HashMap<String, Tag>) -> Self {
+        let texture = match owner.get("Properties") {
+            Some(Tag::Compound(properties)) => match properties.get("textures") {
+                Some(Tag::List(textures)) => textures.iter().find_map(|texture| match texture {
+                    Tag::Compound(texture) => get_string(texture, "Value"),
+                    _ => None,
+                }),
+                _ => None,
+            },
+            _ => None,
+        };
+        Self {
+            name: get_string(owner, "Name"),
+            uuid: get_uuid(owner, "Id"),
+            texture,
+        }
+    }
+}
+
+fn get_string(compound: &HashMap<String, Tag>, key: &str) -> Option<String> {
+    match compound.get(key) {
+        Some(Tag::String(value)) => Some(value.clone()),
+        _ => None,
+    }
+}
+
+fn get_uuid(compound: &HashMap<String, Tag>, key: &str) -> Option<Array<i32>> {
+    match compound.get(key) {
+        Some(Tag::IntArray(uuid)) if uuid.len() == 4 => Some(uuid.clone()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use test_case::test_case;
+
+    use super::PlayerHead;
+    use crate::{
+        data::item::Item,
+        nbt::{Array, List, Tag},
+        test_util::compound,
+    };
+
+    fn head(tag: Option<(&str, Tag)>, components: Option<(&str, Tag)>) -> Item {
+        let to_map = |(key, value): (&str, Tag)| HashMap::from([(key.to_string(), value)]);
+        Item {
+            id: PlayerHead::ID.to_string(),
+            tag: tag.map(to_map),
+            components: components.map(to_map),
+            count: 1,
+        }
+    }
+
+    fn notch() -> PlayerHead {
+        PlayerHead {
+            name: Some("Notch".to_string()),
+            uuid: Some(Array::from(vec![1, 2, 3, 4])),
+            texture: Some("ZXhhbXBsZQ==".to_string()),
+        }
+    }
+
+    #[test_case(head(None, Some(("minecraft:profile", compound(vec![
+        ("name", Tag::String("Notch".to_string())),
+        ("id", Tag::IntArray(Array::from(vec![1, 2, 3, 4]))),
+        ("properties", Tag::List(List::from(vec![compound(vec![
+            ("name", Tag::String("textures".to_string())),
+            ("value", Tag::String("ZXhhbXBsZQ==".to_string())),
+        ])]))),
+    ])))) => Some(notch()); "Component")]
+    #[test_case(head(Some(("SkullOwner", compound(vec![
+        ("Name", Tag::String("Notch".to_string())),
+        ("Id", Tag::IntArray(Array::from(vec![1, 2, 3, 4]))),
+        ("Properties", compound(vec![("textures", Tag::List(List::from(vec![compound(vec![
+            ("Value", Tag::String("ZXhhbXBsZQ==".to_string())),
+        ])])))])),
+    ]))), None) => Some(notch()); "Legacy compound")]
+    #[test_case(head(Some(("SkullOwner", Tag::String("Notch".to_string()))), None) => Some(PlayerHead {
+        name: Some("Notch".to_string()),
+        uuid: None,
+        texture: None,
+    }); "Legacy name")]
+    #[test_case(head(None, Some(("minecraft:profile", Tag::String("Notch".to_string())))) => Some(PlayerHead {
+        name: Some("Notch".to_string()),
+        uuid: None,
+        texture: None,
+    }); "Component name")]
+    #[test_case(head(None, None) => None; "Without owner")]
+    fn test_from_item(item: Item) -> Option<PlayerHead> {
+        PlayerHead::from_item(&item)
+    }
+}
@@ -0,0 +1,295 @@
+//! Potions, splash potions, lingering potions and tipped arrows.
+
+use std::collections::HashMap;
+
+use super::{entity::ActiveEffect, item::Item};
+use crate::nbt::Tag;
+
+/// Numeric effect ids ordered by id starting at 1.
+/// Newer versions store the name instead of the id.
+/// [Minecraft Wiki](https://minecraft.fandom.com/wiki/Effect#Effect_list)
+const EFFECT_NAMES: [&str; 33] = [
+    "speed",
+    "slowness",
+    "haste",
+    "mining_fatigue",
+    "strength",
+    "instant_health",
+    "instant_damage",
+    "jump_boost",
+    "nausea",
+    "regeneration",
+    "resistance",
+    "fire_resistance",
+    "water_breathing",
+    "invisibility",
+    "blindness",
+    "night_vision",
+    "hunger",
+    "weakness",
+    "poison",
+    "wither",
+    "health_boost",
+    "absorption",
+    "saturation",
+    "glowing",
+    "levitation",
+    "luck",
+    "unluck",
+    "slow_falling",
+    "conduit_power",
+    "dolphins_grace",
+    "bad_omen",
+    "hero_of_the_village",
+    "darkness",
+];
+
+/// The content of a potion like item.
+/// [Minecraft Wiki](https://minecraft.fandom.com/wiki/Potion#Item_data)
+#[derive(Debug, PartialEq)]
+pub struct Potion {
+    /// The base potion e.g. `minecraft:strong_healing`
+    pub potion: Option<String>,
+    /// Effects applied in addition to the effects of the base potion
+    pub custom_effects: Vec<ActiveEffect>,
+    /// RGB color overriding the color of the effects
+    pub color: Option<i32>,
+}
+
+impl Potion {
+    /// Get the content of a potion, splash potion, lingering potion or tipped arrow.
+    /// The `minecraft:potion_contents` component takes precedence over the legacy item tag.
+    pub fn from_item(item: &Item) -> Option<Self> {
+        if !matches!(
+            item.id.as_str(),
+            "minecraft:potion"
+                | "minecraft:splash_potion"
+                | "minecraft:lingering_potion"
+                | "minecraft:tipped_arrow"
+        ) {
+            return None;
+        }
+        let component = item
+            .components
+            .as_ref()
+            .and_then(|components| components.get("minecraft:potion_contents"));
+        let potion = match component {
+            // The component may only contain the id of the base potion
+            Some(Tag::String(potion)) => Self {
+                potion: Some(potion.clone()),
+                custom_effects: Vec::new(),
+                color: None,
+            },
+            Some(Tag::Compound(contents)) => {
+                Self::from_compound(contents, "potion", "custom_effects", "custom_color")
+            }
+            _ => item
+                .tag
+                .as_ref()
+                .map(|tag| {
+                    let custom_effects_key = if tag.contains_key("custom_potion_effects") {
+                        "custom_potion_effects"
+                    } else {
+                        "CustomPotionEffects"
+                    };
+                    Self::from_compound(tag, "Potion", custom_effects_key, "CustomPotionColor")
+                })
+                .unwrap_or(Self {
+                    potion: None,
+                    custom_effects: Vec::new(),
+                    color: None,
+                }),
+        };
+        Some(potion)
+    }
+
+    fn from_compound(
+        compound: &HashMap<String, Tag>,
+        potion_key: &str,
+        custom_effects_key: &str,
+        color_key: &str,
+    ) -> Self {
+        let potion = match compound.get(potion_key) {
+            Some(Tag::String(potion)) => Some(potion.clone()),
+            _ => None,
+        };
+        let custom_effects = match compound.get(custom_effects_key) {
+            Some(Tag::List(effects)) => effects.iter().filter_map(effect_from_tag).collect(),
+            _ => Vec::new(),
+        };
+        let color = match compound.get(color_key) {
+            Some(Tag::Int(color)) => Some(*color),
+            _ => None,
+        };
+        Self {
+            potion,
+            custom_effects,
+            color,
+        }
+    }
+}
+
+/// Get the numeric id of an effect by its name e.g. `minecraft:speed`.
+/// Effects added after numeric ids were replaced do not have an id.
+pub fn effect_id_from_name(name: &str) -> Option<i32> {
+    let name = name.strip_prefix("minecraft:").unwrap_or(name);
+    let index = EFFECT_NAMES.iter().position(|effect| *effect == name)?;
+    i32::try_from(index + 1).ok()
+}
+
+/// Parse an effect stored with numeric ids and capitalized keys or with names and lowercase keys.
+fn effect_from_tag(tag: &Tag) -> Option<ActiveEffect> {
+    let Tag::Compound(effect) = tag else {
+        return None;
+    };
+    if effect.contains_key("Id") {
+        return ActiveEffect::try_from(tag.clone()).ok();
+    }
+    let id = match effect.get("id")? {
+        Tag::String(name) => effect_id_from_name(name)?,
+        Tag::Int(id) => *id,
+        _ => return None,
+    };
+    let flag = |key: &str, default: bool| match effect.get(key) {
+        Some(Tag::Byte(value)) => *value != 0,
+        _ => default,
+    };
+    let show_particles = flag("show_particles", true);
+    Some(ActiveEffect {
+        ambient: flag("ambient", false),
+        amplifier: match effect.get("amplifier") {
+            Some(Tag::Byte(amplifier)) => *amplifier,
+            _ => 0,
+        },
+        duration: match effect.get("duration") {
+            Some(Tag::Int(duration)) => *duration,
+            _ => 0,
+        },
+        id,
+        show_icon: flag("show_icon", show_particles),
+        show_particles,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use test_case::test_case;
+
+    use super::Potion;
+    use crate::{
+        data::{entity::ActiveEffect, item::Item},
+        nbt::{List, Tag},
+    };
+
+    fn potion_item(
+        tag: Option<HashMap<String, Tag>>,
+        components: Option<HashMap<String, Tag>>,
+    ) -> Item {
+        Item {
+            id: "minecraft:potion".to_string(),
+            tag,
+            components,
+            count: 1,
+        }
+    }
+
+    fn strength() -> ActiveEffect {
+        ActiveEffect {
+            ambient: false,
+            amplifier: 1,
+            duration: 3600,
+            id: 5,
+            show_icon: true,
+            show_particles: true,
+        }
+    }
+
+    #[test]
+    fn test_from_item_legacy() {
+        let item = potion_item(
+            Some(HashMap::from([
+                (
+                    "Potion".to_string(),
+                    Tag::String("minecraft:water".to_string()),
+                ),
+                ("CustomPotionColor".to_string(), Tag::Int(0xff0000)),
+                (
+                    "CustomPotionEffects".to_string(),
+                    Tag::List(List::from(vec![Tag::Compound(HashMap::from([
+                        ("Ambient".to_string(), Tag::Byte(0)),
+                        ("Amplifier".to_string(), Tag::Byte(1)),
+                        ("Duration".to_string(), Tag::Int(3600)),
+                        ("Id".to_string(), Tag::Int(5)),
+                        ("ShowIcon".to_string(), Tag::Byte(1)),
+                        ("ShowParticles".to_string(), Tag::Byte(1)),
+                    ]))])),
+                ),
+            ])),
+            None,
+        );
+        assert_eq!(
+            Potion::from_item(&item),
+            Some(Potion {
+                potion: Some("minecraft:water".to_string()),
+                custom_effects: vec![strength()],
+                color: Some(0xff0000),
+            })
+        );
+    }
+
+    #[test]
+    fn test_from_item_components() {
+        let item = potion_item(
+            None,
+            Some(HashMap::from([(
+                "minecraft:potion_contents".to_string(),
+                Tag::Compound(HashMap::from([
+                    (
+                        "potion".to_string(),
+                        Tag::String("minecraft:water".to_string()),
+                    ),
+                    ("custom_color".to_string(), Tag::Int(0xff0000)),
+                    (
+                        "custom_effects".to_string(),
+                        Tag::List(List::from(vec![Tag::Compound(HashMap::from([
+                            (
+                                "id".to_string(),
+                                Tag::String("minecraft:strength".to_string()),
+                            ),
+                            ("amplifier".to_string(), Tag::Byte(1)),
+                            ("duration".to_string(), Tag::Int(3600)),
+                        ]))])),
+                    ),
+                ])),
+            )])),
+        );
+        assert_eq!(
+            Potion::from_item(&item),
+            Some(Potion {
+                potion: Some("minecraft:water".to_string()),
+                custom_effects: vec![strength()],
+                color: Some(0xff0000),
+            })
+        );
+    }
+
+    #[test]
+    fn test_from_item_not_a_potion() {
+        let item = Item {
+            id: "minecraft:dirt".to_string(),
+            tag: None,
+            components: None,
+            count: 1,
+        };
+        assert_eq!(Potion::from_item(&item), None);
+    }
+
+    #[test_case("minecraft:speed" => Some(1); "Speed")]
+    #[test_case("darkness" => Some(33); "Without namespace")]
+    #[test_case("minecraft:wind_charged" => None; "Newer than numeric ids")]
+    fn test_effect_id_from_name(name: &str) -> Option<i32> {
+        super::effect_id_from_name(name)
+    }
+}
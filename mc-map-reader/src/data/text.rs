@@ -0,0 +1,69 @@
+//! Text components as used by books and custom names.
+
+use crate::nbt::Tag;
+
+/// Get the plain text of a text component. Formatting is removed.
+/// Text components are stored as JSON strings or, since 1.20.5, as NBT.
+pub(crate) fn plain_text(tag: &Tag) -> String {
+    let mut text = String::new();
+    push_tag_text(tag, &mut text);
+    text
+}
+
+/// Get the plain text of a text component stored as JSON.
+/// Strings that are not valid JSON, e.g. names saved before 1.13, are returned as they are.
+pub(crate) fn plain_text_of_json(value: &str) -> String {
+    let mut text = String::new();
+    push_json_or_plain_text(value, &mut text);
+    text
+}
+
+fn push_json_or_plain_text(value: &str, text: &mut String) {
+    match serde_json::from_str(value) {
+        Ok(json) => push_json_text(&json, text),
+        Err(_) => text.push_str(value),
+    }
+}
+
+fn push_tag_text(tag: &Tag, text: &mut String) {
+    match tag {
+        Tag::String(value) => push_json_or_plain_text(value, text),
+        Tag::List(components) => components
+            .iter()
+            .for_each(|component| push_tag_text(component, text)),
+        Tag::Compound(component) => {
+            if let Some(Tag::String(value)) = component.get("text") {
+                text.push_str(value);
+            }
+            if let Some(Tag::List(extra)) = component.get("extra") {
+                extra
+                    .iter()
+                    .for_each(|component| push_tag_text(component, text));
+            }
+        }
+        _ => {}
+    }
+}
+
+fn push_json_text(json: &serde_json::Value, text: &mut String) {
+    use serde_json::Value;
+    match json {
+        Value::String(value) => text.push_str(value),
+        Value::Number(value) => text.push_str(&value.to_string()),
+        Value::Bool(value) => text.push_str(&value.to_string()),
+        Value::Array(components) => components
+            .iter()
+            .for_each(|component| push_json_text(component, text)),
+        Value::Object(component) => {
+            if let Some(Value::String(value)) = component.get("text") {
+                text.push_str(value);
+            }
+            if let Some(Value::Array(extra)) = component.get("extra") {
+                extra
+                    .iter()
+                    .for_each(|component| push_json_text(component, text));
+            }
+        }
+        Value::Null => {}
+    }
+}
@@ -0,0 +1,100 @@
+//! Armor trims.
+
+use std::collections::HashMap;
+
+use super::item::Item;
+use crate::nbt::Tag;
+
+/// A trim applied to a piece of armor in a smithing table.
+/// [Minecraft Wiki](https://minecraft.fandom.com/wiki/Smithing_Template#Item_data)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArmorTrim {
+    /// The trim pattern e.g. `minecraft:silence`
+    pub pattern: String,
+    /// The trim material e.g. `minecraft:netherite`
+    pub material: String,
+}
+
+impl ArmorTrim {
+    /// Get the trim of a piece of armor.
+    /// The `minecraft:trim` component takes precedence over the legacy `Trim` tag.
+    /// Returns `None` for items without a trim.
+    pub fn from_item(item: &Item) -> Option<Self> {
+        let component = item
+            .components
+            .as_ref()
+            .and_then(|components| components.get("minecraft:trim"));
+        if let Some(Tag::Compound(trim)) = component {
+            return Self::from_compound(trim);
+        }
+        match item.tag.as_ref().and_then(|tag| tag.get("Trim")) {
+            Some(Tag::Compound(trim)) => Self::from_compound(trim),
+            _ => None,
+        }
+    }
+
+    fn from_compound(trim: &HashMap<String, Tag>) -> Option<Self> {
+        let (Some(Tag::String(pattern)), Some(Tag::String(material))) =
+            (trim.get("pattern"), trim.get("material"))
+        else {
+            return None;
+        };
+        Some(Self {
+            pattern: pattern.clone(),
+            material: material.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use test_case::test_case;
+
+    use super::ArmorTrim;
+    use crate::{data::item::Item, nbt::Tag};
+
+    fn trim(pattern: &str, material: &str) -> Tag {
+        Tag::Compound(HashMap::from([
+            ("pattern".to_string(), Tag::String(pattern.to_string())),
+            ("material".to_string(), Tag::String(material.to_string())),
+        ]))
+    }
+
+    fn chestplate(
+        tag: Option<HashMap<String, Tag>>,
+        components: Option<HashMap<String, Tag>>,
+    ) -> Item {
+        Item {
+            id: "minecraft:netherite_chestplate".to_string(),
+            tag,
+            components,
+            count: 1,
+        }
+    }
+
+    fn silence_netherite() -> Option<ArmorTrim> {
+        Some(ArmorTrim {
+            pattern: "minecraft:silence".to_string(),
+            material: "minecraft:netherite".to_string(),
+        })
+    }
+
+    #[test_case(chestplate(
+        Some(HashMap::from([("Trim".to_string(), trim("minecraft:silence", "minecraft:netherite"))])),
+        None
+    ) => silence_netherite(); "Legacy tag")]
+    #[test_case(chestplate(
+        None,
+        Some(HashMap::from([("minecraft:trim".to_string(), trim("minecraft:silence", "minecraft:netherite"))]))
+    ) => silence_netherite(); "Component")]
+    #[test_case(chestplate(
+        Some(HashMap::from([("Trim".to_string(), trim("minecraft:coast", "minecraft:iron"))])),
+        Some(HashMap::from([("minecraft:trim".to_string(), trim("minecraft:silence", "minecraft:netherite"))]))
+    ) => silence_netherite(); "Component takes precedence")]
+    #[test_case(chestplate(Some(HashMap::new()), None) => None; "Without trim")]
+    fn test_from_item(item: Item) -> Option<ArmorTrim> {
+        item.armor_trim()
+    }
+}
@@ -0,0 +1,171 @@
+//! Mapping of data versions to Minecraft release versions.
+//! Chunks, player files and the level.dat store the data version of the game that last saved them.
+
+/// Known release versions sorted by data version.
+const RELEASES: &[(i32, &str)] = &[
+    (169, "1.9"),
+    (175, "1.9.1"),
+    (176, "1.9.2"),
+    (183, "1.9.3"),
+    (184, "1.9.4"),
+    (510, "1.10"),
+    (511, "1.10.1"),
+    (512, "1.10.2"),
+    (819, "1.11"),
+    (921, "1.11.1"),
+    (922, "1.11.2"),
+    (1139, "1.12"),
+    (1241, "1.12.1"),
+    (1343, "1.12.2"),
+    (1519, "1.13"),
+    (1628, "1.13.1"),
+    (1631, "1.13.2"),
+    (1952, "1.14"),
+    (1957, "1.14.1"),
+    (1963, "1.14.2"),
+    (1968, "1.14.3"),
+    (1976, "1.14.4"),
+    (2225, "1.15"),
+    (2227, "1.15.1"),
+    (2230, "1.15.2"),
+    (2566, "1.16"),
+    (2567, "1.16.1"),
+    (2578, "1.16.2"),
+    (2580, "1.16.3"),
+    (2584, "1.16.4"),
+    (2586, "1.16.5"),
+    (2724, "1.17"),
+    (2730, "1.17.1"),
+    (2860, "1.18"),
+    (2865, "1.18.1"),
+    (2975, "1.18.2"),
+    (3105, "1.19"),
+    (3117, "1.19.1"),
+    (3120, "1.19.2"),
+    (3218, "1.19.3"),
+    (3337, "1.19.4"),
+    (3463, "1.20"),
+    (3465, "1.20.1"),
+    (3578, "1.20.2"),
+    (3698, "1.20.3"),
+    (3700, "1.20.4"),
+    (3837, "1.20.5"),
+    (3839, "1.20.6"),
+    (3953, "1.21"),
+    (3955, "1.21.1"),
+    (4080, "1.21.2"),
+    (4082, "1.21.3"),
+    (4189, "1.21.4"),
+];
+
+/// Item ids and the data version of the release that added them.
+/// The table only covers a selection of items of each release.
+const ITEMS: &[(&str, i32)] = &[
+    ("minecraft:trident", 1519),
+    ("minecraft:turtle_helmet", 1519),
+    ("minecraft:heart_of_the_sea", 1519),
+    ("minecraft:nautilus_shell", 1519),
+    ("minecraft:phantom_membrane", 1519),
+    ("minecraft:crossbow", 1952),
+    ("minecraft:bell", 1952),
+    ("minecraft:campfire", 1952),
+    ("minecraft:lantern", 1952),
+    ("minecraft:sweet_berries", 1952),
+    ("minecraft:honey_bottle", 2225),
+    ("minecraft:honeycomb", 2225),
+    ("minecraft:beehive", 2225),
+    ("minecraft:bee_nest", 2225),
+    ("minecraft:netherite_ingot", 2566),
+    ("minecraft:netherite_scrap", 2566),
+    ("minecraft:ancient_debris", 2566),
+    ("minecraft:lodestone", 2566),
+    ("minecraft:netherite_block", 2566),
+    ("minecraft:amethyst_shard", 2724),
+    ("minecraft:spyglass", 2724),
+    ("minecraft:copper_ingot", 2724),
+    ("minecraft:raw_iron", 2724),
+    ("minecraft:glow_ink_sac", 2724),
+    ("minecraft:axolotl_bucket", 2724),
+    ("minecraft:recovery_compass", 3105),
+    ("minecraft:echo_shard", 3105),
+    ("minecraft:disc_fragment_5", 3105),
+    ("minecraft:music_disc_5", 3105),
+    ("minecraft:goat_horn", 3105),
+    ("minecraft:sculk_catalyst", 3105),
+    ("minecraft:mangrove_propagule", 3105),
+    ("minecraft:sniffer_egg", 3463),
+    ("minecraft:brush", 3463),
+    ("minecraft:cherry_sapling", 3463),
+    ("minecraft:torchflower_seeds", 3463),
+    ("minecraft:pitcher_pod", 3463),
+    ("minecraft:calibrated_sculk_sensor", 3463),
+    ("minecraft:netherite_upgrade_smithing_template", 3463),
+    ("minecraft:decorated_pot", 3463),
+    ("minecraft:armadillo_scute", 3837),
+    ("minecraft:wolf_armor", 3837),
+    ("minecraft:mace", 3953),
+    ("minecraft:heavy_core", 3953),
+    ("minecraft:wind_charge", 3953),
+    ("minecraft:breeze_rod", 3953),
+    ("minecraft:trial_key", 3953),
+    ("minecraft:ominous_trial_key", 3953),
+    ("minecraft:ominous_bottle", 3953),
+    ("minecraft:crafter", 3953),
+    ("minecraft:vault", 3953),
+    ("minecraft:trial_spawner", 3953),
+    ("minecraft:flow_armor_trim_smithing_template", 3953),
+    ("minecraft:bolt_armor_trim_smithing_template", 3953),
+    ("minecraft:music_disc_creator", 3953),
+    ("minecraft:music_disc_precipice", 3953),
+    ("minecraft:pale_oak_sapling", 4189),
+    ("minecraft:resin_clump", 4189),
+    ("minecraft:creaking_heart", 4189),
+];
+
+/// Get the name of the release a data version belongs to.
+/// Data versions between two releases resolve to the older release.
+/// Returns `None` for data versions older than 1.9 or newer than the latest known release.
+pub fn version_name(data_version: i32) -> Option<&'static str> {
+    let (latest, _) = RELEASES.last()?;
+    if data_version > *latest {
+        return None;
+    }
+    let index = RELEASES.partition_point(|(version, _)| *version <= data_version);
+    let (_, name) = RELEASES.get(index.checked_sub(1)?)?;
+    Some(name)
+}
+
+/// Get the data version of the release that added an item.
+/// Returns `None` for items that existed before 1.13 and items that are not part of the table.
+pub fn item_introduced(id: &str) -> Option<i32> {
+    ITEMS
+        .iter()
+        .find(|(item, _)| *item == id)
+        .map(|(_, data_version)| *data_version)
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    #[test_case(169 => Some("1.9"); "First known release")]
+    #[test_case(1343 => Some("1.12.2"); "1.12.2")]
+    #[test_case(3955 => Some("1.21.1"); "1.21.1")]
+    #[test_case(4189 => Some("1.21.4"); "Latest known release")]
+    #[test_case(3956 => Some("1.21.1"); "Between two releases")]
+    #[test_case(1000 => Some("1.11.2"); "Far from next release")]
+    #[test_case(168 => None; "Older than first known release")]
+    #[test_case(-1 => None; "Negative")]
+    #[test_case(4190 => None; "Newer than latest known release")]
+    fn test_version_name(data_version: i32) -> Option<&'static str> {
+        super::version_name(data_version)
+    }
+
+    #[test_case("minecraft:mace" => Some(3953); "1.21 item")]
+    #[test_case("minecraft:netherite_ingot" => Some(2566); "1.16 item")]
+    #[test_case("minecraft:diamond" => None; "Old item")]
+    #[test_case("mace" => None; "Missing namespace")]
+    fn test_item_introduced(id: &str) -> Option<i32> {
+        super::item_introduced(id)
+    }
+}
@@ -62,6 +62,7 @@ pub fn get_region_files_in_area(
         .collect()
 }
 
+/// Return a list of all regions in the given area.
 pub fn get_regions_in_area(
     world_directory: &Path,
     dimension_directory: Option<&Path>,
@@ -69,6 +70,23 @@ pub fn get_regions_in_area(
     chunk1_z: i32,
     chunk2_x: i32,
     chunk2_z: i32,
+) -> Vec<RegionFile> {
+    get_regions_in_area_of_region_dir(
+        &region_directory(world_directory, dimension_directory),
+        chunk1_x,
+        chunk1_z,
+        chunk2_x,
+        chunk2_z,
+    )
+}
+
+/// Return a list of all regions in the given area of a region directory.
+pub fn get_regions_in_area_of_region_dir(
+    region_directory: &Path,
+    chunk1_x: i32,
+    chunk1_z: i32,
+    chunk2_x: i32,
+    chunk2_z: i32,
 ) -> Vec<RegionFile> {
     let chunk1_x = chunk1_x >> 5;
     let chunk1_z = chunk1_z >> 5;
@@ -92,17 +110,10 @@ pub fn get_regions_in_area(
             vec
         })
         .into_iter()
-        .map(|(x, z)| {
-            let mut region_file = PathBuf::from(world_directory);
-            if let Some(dimension) = dimension_directory {
-                region_file.push(dimension)
-            }
-            region_file.push(format!("region/r.{x}.{z}.mca"));
-            RegionFile {
-                z,
-                x,
-                path: region_file,
-            }
+        .map(|(x, z)| RegionFile {
+            z,
+            x,
+            path: region_directory.join(format!("r.{x}.{z}.mca")),
         })
         .filter(|region_file| region_file.path.exists())
         .collect()
@@ -123,21 +134,25 @@ pub fn get_region_files(
         .collect::<Result<_, _>>()
 }
 
+/// Return a list of all regions.
 pub fn get_regions(
     world_dir: &Path,
     dimension_directory: Option<&Path>,
 ) -> std::io::Result<Vec<RegionFile>> {
-    let mut region_dir = PathBuf::from(world_dir);
-    if let Some(dimension) = dimension_directory {
-        region_dir.push(dimension)
-    }
-    region_dir.push("region");
-    std::fs::read_dir(region_dir)?
+    get_regions_of_region_dir(&region_directory(world_dir, dimension_directory))
+}
+
+/// Return a list of all regions inside of a region directory.
+pub fn get_regions_of_region_dir(region_directory: &Path) -> std::io::Result<Vec<RegionFile>> {
+    std::fs::read_dir(region_directory)?
         .map(|entry| entry.map(|e| e.path()))
         .filter_map(|entry| {
             let res = entry.map(|path| {
-                let path_cow = path.to_string_lossy();
-                let mut split = path_cow.split('.').skip(1);
+                let file_name = path
+                    .file_name()
+                    .map(|file_name| file_name.to_string_lossy())
+                    .unwrap_or_default();
+                let mut split = file_name.split('.').skip(1);
                 if let Some((x, z)) = split
                     .next()
                     .zip(split.next())
@@ -158,6 +173,26 @@ pub fn get_regions(
         .collect::<Result<_, _>>()
 }
 
+pub(crate) fn region_directory(world_dir: &Path, dimension_directory: Option<&Path>) -> PathBuf {
+    let mut region_dir = PathBuf::from(world_dir);
+    if let Some(dimension) = dimension_directory {
+        region_dir.push(dimension)
+    }
+    region_dir.push("region");
+    region_dir
+}
+
+/// The directory containing the entity region files of a dimension. Used since 1.17.
+#[cfg(feature = "region_file")]
+pub(crate) fn entities_directory(world_dir: &Path, dimension_directory: Option<&Path>) -> PathBuf {
+    let mut entities_dir = PathBuf::from(world_dir);
+    if let Some(dimension) = dimension_directory {
+        entities_dir.push(dimension)
+    }
+    entities_dir.push("entities");
+    entities_dir
+}
+
 #[cfg(test)]
 mod tests {
     use std::path::PathBuf;
@@ -238,4 +273,36 @@ mod tests {
         assert!(expected.iter().all(|file_name| actual.contains(file_name)));
         assert!(actual.iter().all(|file_name| expected.contains(file_name)));
     }
+
+    #[test]
+    fn get_regions_of_region_dir() {
+        let mut region_dir = get_test_world_dir();
+        region_dir.push("region");
+        let mut actual = super::get_regions_of_region_dir(&region_dir)
+            .expect("Error reading region directory")
+            .into_iter()
+            .map(|region| (region.x(), region.z()))
+            .collect::<Vec<_>>();
+        actual.sort();
+        let expected = (-2..=2)
+            .flat_map(|x| (-2..=2).map(move |z| (x, z)))
+            .collect::<Vec<_>>();
+        assert_eq!(actual, expected);
+    }
+
+    #[test_case(-10, -10, 10, 10 => vec![(-1, -1), (-1, 0), (0, -1), (0, 0)]; "Around origin")]
+    #[test_case(-33, 40, -33, 40 => vec![(-2, 1)]; "Single chunk with negative coordinates")]
+    #[test_case(-1, 5, 0, 6 => vec![(-1, 0), (0, 0)]; "Narrow area crossing region border")]
+    #[test_case(31, 31, 32, 31 => vec![(0, 0), (1, 0)]; "Narrow area at positive region border")]
+    #[test_case(200, 200, 300, 300 => Vec::<(i32, i32)>::new(); "Outside of existing regions")]
+    fn get_regions_in_area_of_region_dir(x1: i32, z1: i32, x2: i32, z2: i32) -> Vec<(i32, i32)> {
+        let mut region_dir = get_test_world_dir();
+        region_dir.push("region");
+        let mut actual = super::get_regions_in_area_of_region_dir(&region_dir, x1, z1, x2, z2)
+            .into_iter()
+            .map(|region| (region.x(), region.z()))
+            .collect::<Vec<_>>();
+        actual.sort();
+        actual
+    }
 }
@@ -4,7 +4,9 @@
 
 //! This crate provides a way to read Minecraft saves.
 
+pub mod coords;
 pub mod data;
+pub mod data_version;
 mod load;
 pub use load::*;
 mod compression;
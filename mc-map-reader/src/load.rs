@@ -11,7 +11,9 @@ use crate::{
 #[cfg(feature = "region_file")]
 use {
     crate::data::file_format::anvil::{self, AnvilSave},
-    std::io::Read,
+    std::io::{Read, Seek, SeekFrom},
+    std::path::Path,
+    std::time::{Duration, Instant},
 };
 
 #[cfg(feature = "region_file")]
@@ -32,6 +34,38 @@ pub enum RegionLoadError {
     LoadChunkData(#[from] data::chunk::LoadChunkDataError),
 }
 
+#[cfg(feature = "region_file")]
+/// Problems found while checking a single chunk of a region file.
+#[derive(Error, Debug, PartialEq)]
+pub enum ChunkCheckError {
+    /// The offset of the chunk points into the region header.
+    #[error("Chunk starts inside of the region header")]
+    InsideHeader,
+    /// The chunk has a length of zero sectors.
+    #[error("Chunk has no sectors")]
+    NoSectors,
+    /// The chunk ends after the end of the region file.
+    #[error("Chunk ends after the end of the region file")]
+    OutOfBounds,
+    /// The chunk shares sectors with the chunk at the given position.
+    #[error("Chunk shares sectors with chunk {0},{1}")]
+    Overlapping(i32, i32),
+    /// The chunk could not be decompressed or parsed.
+    #[error(transparent)]
+    Load(#[from] data::chunk::LoadChunkDataError),
+}
+
+#[cfg(feature = "region_file")]
+/// The result of checking a single chunk of a region file.
+#[derive(Debug, PartialEq)]
+pub struct ChunkCheck {
+    /// X position of the chunk inside of the region
+    pub x: i32,
+    /// Z position of the chunk inside of the region
+    pub z: i32,
+    pub result: Result<(), ChunkCheckError>,
+}
+
 /// Errors that can occur when loading a level.dat file.
 #[derive(Error, Debug)]
 pub enum LevelDatLoadError {
@@ -41,41 +75,101 @@ pub enum LevelDatLoadError {
     /// Some data in the level.dat file could not be decompressed.
     #[error(transparent)]
     Compression(crate::compression::Error),
+    /// The level.dat file belongs to a Bedrock Edition world.
+    #[error("The level.dat file belongs to a Bedrock Edition world. Only Java Edition worlds are supported.")]
+    Bedrock,
     #[cfg(feature = "level_dat")]
     /// Some data in the level.dat file is not valid.
     #[error(transparent)]
     LevelDat(#[from] data::file_format::level_dat::LevelDatError),
 }
 
+/// Errors that can occur when loading a player statistics file.
+#[derive(Error, Debug)]
+pub enum PlayerStatsLoadError {
+    /// Error while reading the statistics file.
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    /// The statistics file is not valid JSON.
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    /// Some data in the statistics file is not valid.
+    #[error(transparent)]
+    PlayerStats(#[from] data::file_format::player_stats::PlayerStatsError),
+}
+
+#[cfg(not(tarpaulin_include))]
+/// Parse the statistics file of a player, `stats/<uuid>.json` in the save directory.
+pub fn parse_player_stats(
+    path: impl AsRef<std::path::Path>,
+) -> std::result::Result<data::file_format::player_stats::PlayerStats, PlayerStatsLoadError> {
+    let data = std::fs::read(path)?;
+    let value = serde_json::from_slice::<serde_json::Value>(&data)?;
+    Ok(value.try_into()?)
+}
+
 #[cfg(feature = "level_dat")]
 #[cfg(not(tarpaulin_include))]
 /// Parse a level.dat file.
 pub fn parse_level_dat(data: &[u8]) -> std::result::Result<level_dat::LevelDat, LevelDatLoadError> {
-    let data = compression::decompress(data, &compression::Compression::GZip)
-        .map_err(LevelDatLoadError::Compression)?;
-    let data = crate::nbt::parse(data.as_slice())?
+    let data = parse_level_dat_nbt(data)?
         .get_as_map()?
         .remove("Data")
         .ok_or(crate::nbt::Error::InvalidValue)?;
     LevelDat::try_from(data).map_err(LevelDatLoadError::LevelDat)
 }
 
+#[cfg(feature = "level_dat")]
+#[cfg(not(tarpaulin_include))]
+/// Parse a level.dat file into its raw NBT tags without interpreting them.
+pub fn parse_level_dat_nbt(data: &[u8]) -> std::result::Result<crate::nbt::Tag, LevelDatLoadError> {
+    if is_bedrock_level_dat(data) {
+        return Err(LevelDatLoadError::Bedrock);
+    }
+    let data = compression::decompress(data, &compression::Compression::GZip)
+        .map_err(LevelDatLoadError::Compression)?;
+    Ok(crate::nbt::parse(data.as_slice())?)
+}
+
+#[cfg(feature = "level_dat")]
+/// Bedrock Edition level.dat files are not compressed. They start with an 8 byte header
+/// containing the storage version and the length of the following little-endian NBT data.
+fn is_bedrock_level_dat(data: &[u8]) -> bool {
+    let Some((header, nbt)) = data.split_first_chunk::<8>() else {
+        return false;
+    };
+    let length = u32::from_le_bytes([header[4], header[5], header[6], header[7]]);
+    usize::try_from(length).is_ok_and(|length| length == nbt.len()) && nbt.first() == Some(&10)
+}
+
 #[cfg(feature = "region_file")]
 #[cfg(not(tarpaulin_include))]
 /// Load a region file.
 pub fn load_region(
-    mut read: impl Read,
+    read: impl Read,
     ignore_saved_before: Option<i32>,
 ) -> Result<AnvilSave, RegionLoadError> {
-    let mut raw_header = [0; anvil::MC_REGION_HEADER_SIZE];
-    if read.read(&mut raw_header)? != anvil::MC_REGION_HEADER_SIZE {
-        return Err(std::io::Error::new(
-            std::io::ErrorKind::InvalidData,
-            anvil::INVALID_HEADER_MESSAGE,
-        )
-        .into());
-    }
-    let header = anvil::McRegionHeader::from(raw_header);
+    load_region_timed(read, ignore_saved_before).map(|(region, _)| region)
+}
+
+#[cfg(feature = "region_file")]
+/// Time spent loading the chunks of a region file.
+/// The durations are summed over all chunks, with the `parallel` feature they can exceed the elapsed time.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct RegionLoadTiming {
+    /// Time spent decompressing chunks
+    pub decompress: Duration,
+    /// Time spent parsing the NBT data of chunks
+    pub parse: Duration,
+}
+
+#[cfg(feature = "region_file")]
+/// Load a region file and measure the time spent decompressing and parsing its chunks.
+pub fn load_region_timed(
+    mut read: impl Read,
+    ignore_saved_before: Option<i32>,
+) -> Result<(AnvilSave, RegionLoadTiming), RegionLoadError> {
+    let header = load_region_header(&mut read)?;
     let mut raw_chunk_data = Vec::default();
     read.read_to_end(&mut raw_chunk_data)?;
 
@@ -90,14 +184,653 @@ pub fn load_region(
                 chunk_info.timestamp as i32 >= ignore_saved_before
             })
         })
-        .map(|chunk| data::chunk::load_chunk(&raw_chunk_data, chunk))
-        .collect::<std::result::Result<_, _>>()?;
+        .map(|chunk_info| {
+            let start = Instant::now();
+            let bytes = data::chunk::decompress_chunk(&raw_chunk_data, chunk_info)?;
+            let decompressed = Instant::now();
+            let tag =
+                crate::nbt::parse(bytes.as_slice()).map_err(data::chunk::ChunkDataError::Nbt)?;
+            let chunk = data::chunk::ChunkData::try_from(tag)?;
+            Ok::<_, data::chunk::LoadChunkDataError>((
+                chunk,
+                decompressed - start,
+                decompressed.elapsed(),
+            ))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut timing = RegionLoadTiming::default();
+    let chunks = chunks
+        .into_iter()
+        .map(|(chunk, decompress, parse)| {
+            timing.decompress += decompress;
+            timing.parse += parse;
+            chunk
+        })
+        .collect();
+    Ok((AnvilSave::new(header, chunks), timing))
+}
+
+#[cfg(feature = "region_file")]
+/// Read a single chunk by its chunk coordinates without loading the entire region.
+/// `dimension_directory` is the directory of the dimension inside of the save e.g. `DIM-1` for the nether.
+/// Returns `Ok(None)` if the chunk or its region file has not been generated yet.
+pub fn read_chunk(
+    save_directory: &Path,
+    dimension_directory: Option<&Path>,
+    chunk_x: i32,
+    chunk_z: i32,
+) -> Result<Option<data::chunk::ChunkData>, RegionLoadError> {
+    let region_directory = crate::files::region_directory(save_directory, dimension_directory);
+    let Some(file) = open_region_of_chunk(&region_directory, chunk_x, chunk_z)? else {
+        return Ok(None);
+    };
+    read_chunk_from_region(file, chunk_x, chunk_z)
+}
+
+#[cfg(feature = "region_file")]
+/// Read the uncompressed NBT data of a single chunk exactly as it is stored in the region file.
+/// `dimension_directory` is the directory of the dimension inside of the save e.g. `DIM-1` for the nether.
+/// Returns `Ok(None)` if the chunk or its region file has not been generated yet.
+pub fn read_chunk_bytes(
+    save_directory: &Path,
+    dimension_directory: Option<&Path>,
+    chunk_x: i32,
+    chunk_z: i32,
+) -> Result<Option<Vec<u8>>, RegionLoadError> {
+    let region_directory = crate::files::region_directory(save_directory, dimension_directory);
+    let Some(file) = open_region_of_chunk(&region_directory, chunk_x, chunk_z)? else {
+        return Ok(None);
+    };
+    read_chunk_bytes_from_region(file, chunk_x, chunk_z)
+}
+
+#[cfg(all(feature = "region_file", feature = "block_entity"))]
+/// A container together with the position of its block.
+pub type PositionedContainer = (
+    crate::coords::BlockPos,
+    Box<dyn data::block_entity::Container>,
+);
+
+#[cfg(all(feature = "region_file", feature = "block_entity"))]
+/// Iterate over all containers of a dimension together with their position.
+/// `dimension_directory` is the directory of the dimension inside of the save e.g. `DIM-1` for the nether.
+/// Region files are loaded one at a time, so only the containers of a single region are kept in memory.
+/// Region files that can not be loaded are yielded as an error and skipped.
+pub fn iter_containers(
+    save_directory: &Path,
+    dimension_directory: Option<&Path>,
+) -> std::io::Result<impl Iterator<Item = Result<PositionedContainer, RegionLoadError>>> {
+    let regions = crate::files::get_regions(save_directory, dimension_directory)?;
+    let containers = regions.into_iter().flat_map(|region| {
+        let region = std::fs::File::open(region.as_path())
+            .map(std::io::BufReader::new)
+            .map_err(RegionLoadError::from)
+            .and_then(|file| load_region(file, None));
+        match region {
+            Ok(region) => region
+                .chunks
+                .into_iter()
+                .filter_map(|chunk| chunk.block_entities)
+                .flat_map(crate::nbt::List::take)
+                .filter_map(|block_entity| {
+                    let position = block_entity.position();
+                    block_entity
+                        .into_container()
+                        .map(|container| Ok((position, container)))
+                })
+                .collect::<Vec<_>>(),
+            Err(err) => vec![Err(err)],
+        }
+    });
+    Ok(containers)
+}
+
+#[cfg(feature = "region_file")]
+/// Read the entities of a single chunk.
+/// Since 1.17 entities are stored in separate region files inside of the `entities` directory.
+/// Chunks that have not been converted yet still store their entities inside of the chunk.
+/// The entity region files are checked first, the chunk itself is only used if they do not contain the chunk.
+pub fn entities_for_chunk(
+    save_directory: &Path,
+    dimension_directory: Option<&Path>,
+    chunk_x: i32,
+    chunk_z: i32,
+) -> Result<Vec<data::entity::Entity>, RegionLoadError> {
+    let entities_directory = crate::files::entities_directory(save_directory, dimension_directory);
+    let entities_chunk = match open_region_of_chunk(&entities_directory, chunk_x, chunk_z)? {
+        Some(file) => read_chunk_nbt_from_region(file, chunk_x, chunk_z)?,
+        None => None,
+    };
+    if let Some(entities_chunk) = entities_chunk {
+        let entities = match entities_chunk.get_as_map()?.remove("Entities") {
+            Some(entities) => crate::nbt::List::<data::entity::Entity>::try_from(entities)
+                .map_err(|err| {
+                    data::chunk::LoadChunkDataError::from(data::chunk::ChunkDataError::from(
+                        data::FieldError::new("Entities", err),
+                    ))
+                })?
+                .take(),
+            None => Vec::new(),
+        };
+        return Ok(entities);
+    }
+    let entities = read_chunk(save_directory, dimension_directory, chunk_x, chunk_z)?
+        .and_then(|chunk| chunk.entities)
+        .map(crate::nbt::List::take)
+        .unwrap_or_default();
+    Ok(entities)
+}
+
+#[cfg(feature = "region_file")]
+/// Open the region file containing the chunk. Returns `Ok(None)` if the region file does not exist.
+fn open_region_of_chunk(
+    region_directory: &Path,
+    chunk_x: i32,
+    chunk_z: i32,
+) -> std::io::Result<Option<std::fs::File>> {
+    let region_file = region_directory.join(format!(
+        "r.{}.{}.mca",
+        crate::coords::chunk_to_region(chunk_x),
+        crate::coords::chunk_to_region(chunk_z)
+    ));
+    match std::fs::File::open(region_file) {
+        Ok(file) => Ok(Some(file)),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+#[cfg(feature = "region_file")]
+/// Read a single chunk from a region file. Only the header and the sectors of the chunk are read.
+/// The chunk coordinates may be given as world coordinates or relative to the region.
+/// Returns `Ok(None)` if the chunk has not been generated yet.
+pub fn read_chunk_from_region(
+    read: impl Read + Seek,
+    chunk_x: i32,
+    chunk_z: i32,
+) -> Result<Option<data::chunk::ChunkData>, RegionLoadError> {
+    let Some(tag) = read_chunk_nbt_from_region(read, chunk_x, chunk_z)? else {
+        return Ok(None);
+    };
+    let chunk =
+        data::chunk::ChunkData::try_from(tag).map_err(data::chunk::LoadChunkDataError::from)?;
+    Ok(Some(chunk))
+}
+
+#[cfg(feature = "region_file")]
+/// Read the NBT data of a single chunk from a region file without interpreting it.
+fn read_chunk_nbt_from_region(
+    read: impl Read + Seek,
+    chunk_x: i32,
+    chunk_z: i32,
+) -> Result<Option<crate::nbt::Tag>, RegionLoadError> {
+    let Some(bytes) = read_chunk_bytes_from_region(read, chunk_x, chunk_z)? else {
+        return Ok(None);
+    };
+    let tag = crate::nbt::parse(bytes.as_slice()).map_err(|err| {
+        data::chunk::LoadChunkDataError::from(data::chunk::ChunkDataError::Nbt(err))
+    })?;
+    Ok(Some(tag))
+}
+
+#[cfg(feature = "region_file")]
+/// Read the uncompressed NBT data of a single chunk from a region file.
+/// Only the header and the sectors of the chunk are read.
+/// The chunk coordinates may be given as world coordinates or relative to the region.
+/// Returns `Ok(None)` if the chunk has not been generated yet.
+pub fn read_chunk_bytes_from_region(
+    mut read: impl Read + Seek,
+    chunk_x: i32,
+    chunk_z: i32,
+) -> Result<Option<Vec<u8>>, RegionLoadError> {
+    let header = load_region_header(&mut read)?;
+    let index = (chunk_x.rem_euclid(32) + chunk_z.rem_euclid(32) * 32) as usize;
+    let Some(chunk_info) = &header.get_chunk_info()[index] else {
+        return Ok(None);
+    };
+    read.seek(SeekFrom::Start(
+        u64::from(chunk_info.offset) * anvil::SECTOR_SIZE as u64,
+    ))?;
+    let mut raw_chunk_data = Vec::default();
+    read.take(u64::from(chunk_info.sector_count) * anvil::SECTOR_SIZE as u64)
+        .read_to_end(&mut raw_chunk_data)?;
+    // The offset of the chunk info is relative to the start of the file, load_chunk expects it
+    // relative to the end of the header.
+    let chunk_info = anvil::ChunkInfo {
+        offset: 2,
+        ..chunk_info.clone()
+    };
+    let bytes = data::chunk::decompress_chunk(&raw_chunk_data, &chunk_info)?;
+    Ok(Some(bytes))
+}
+
+#[cfg(feature = "region_file")]
+#[cfg(not(tarpaulin_include))]
+/// Load only the header of a region file. No chunk data is read.
+pub fn load_region_header(mut read: impl Read) -> Result<anvil::McRegionHeader, RegionLoadError> {
+    let mut raw_header = [0; anvil::MC_REGION_HEADER_SIZE];
+    if read.read(&mut raw_header)? != anvil::MC_REGION_HEADER_SIZE {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            anvil::INVALID_HEADER_MESSAGE,
+        )
+        .into());
+    }
+    Ok(anvil::McRegionHeader::from(raw_header))
+}
+
+#[cfg(feature = "region_file")]
+/// Check the header and every chunk of a region file.
+/// Unlike [`load_region`] this does not stop at the first damaged chunk. Only the header itself must be readable.
+pub fn check_region(mut read: impl Read) -> Result<Vec<ChunkCheck>, RegionLoadError> {
+    /// The header takes up the first two sectors of the file.
+    const HEADER_SECTORS: u64 = 2;
+    let header = load_region_header(&mut read)?;
+    let mut raw_chunk_data = Vec::default();
+    read.read_to_end(&mut raw_chunk_data)?;
+    let file_sectors =
+        (anvil::MC_REGION_HEADER_SIZE + raw_chunk_data.len()).div_ceil(anvil::SECTOR_SIZE) as u64;
+
+    let chunks = header
+        .get_chunk_info()
+        .iter()
+        .enumerate()
+        .filter_map(|(index, chunk_info)| chunk_info.as_ref().map(|info| (index, info)))
+        .collect::<Vec<_>>();
+    let sectors = |chunk_info: &anvil::ChunkInfo| {
+        let start = u64::from(chunk_info.offset);
+        start..start + u64::from(chunk_info.sector_count)
+    };
+
+    let mut by_offset = chunks
+        .iter()
+        .filter(|(_, chunk_info)| {
+            let sectors = sectors(chunk_info);
+            sectors.start >= HEADER_SECTORS && !sectors.is_empty()
+        })
+        .collect::<Vec<_>>();
+    by_offset.sort_by_key(|(_, chunk_info)| chunk_info.offset);
+    let mut overlapping = std::collections::HashMap::new();
+    let mut furthest: Option<(usize, u64)> = None;
+    for (index, chunk_info) in by_offset {
+        let sectors = sectors(chunk_info);
+        match furthest {
+            Some((other, end)) if sectors.start < end => {
+                overlapping.insert(*index, other);
+            }
+            _ => {}
+        }
+        if furthest.map_or(true, |(_, end)| sectors.end > end) {
+            furthest = Some((*index, sectors.end));
+        }
+    }
 
-    Ok(AnvilSave::new(header, chunks))
+    let position = |index: usize| ((index % 32) as i32, (index / 32) as i32);
+    let checks = chunks
+        .iter()
+        .map(|(index, chunk_info)| {
+            let sectors = sectors(chunk_info);
+            let result = if sectors.start < HEADER_SECTORS {
+                Err(ChunkCheckError::InsideHeader)
+            } else if sectors.is_empty() {
+                Err(ChunkCheckError::NoSectors)
+            } else if sectors.end > file_sectors {
+                Err(ChunkCheckError::OutOfBounds)
+            } else if let Some(other) = overlapping.get(index) {
+                let (x, z) = position(*other);
+                Err(ChunkCheckError::Overlapping(x, z))
+            } else {
+                data::chunk::load_chunk(&raw_chunk_data, chunk_info)
+                    .map(|_| ())
+                    .map_err(ChunkCheckError::from)
+            };
+            let (x, z) = position(*index);
+            ChunkCheck { x, z, result }
+        })
+        .collect();
+    Ok(checks)
 }
 
 #[cfg(test)]
 mod tests {
+    #[cfg(feature = "level_dat")]
+    use test_case::test_case;
+
+    #[cfg(feature = "region_file")]
+    #[test]
+    fn test_check_region() {
+        use super::{ChunkCheck, ChunkCheckError};
+        use crate::data::{
+            chunk::{tests::valid_chunk_data, LoadChunkDataError},
+            file_format::anvil,
+        };
+        // (index, offset, sector count)
+        let chunks = [
+            (0, 2, 1),
+            (1, 3, 1),
+            (2, 1, 1),
+            (3, 10, 1),
+            (5, 3, 0),
+            (32, 2, 1),
+        ];
+        let mut data = vec![0; anvil::MC_REGION_HEADER_SIZE + 2 * anvil::SECTOR_SIZE];
+        for (index, offset, sector_count) in chunks {
+            let offset: u32 = offset;
+            data[index * 4..index * 4 + 3].copy_from_slice(&offset.to_be_bytes()[1..]);
+            data[index * 4 + 3] = sector_count;
+        }
+        let chunk = valid_chunk_data();
+        data[anvil::MC_REGION_HEADER_SIZE..anvil::MC_REGION_HEADER_SIZE + chunk.len()]
+            .copy_from_slice(&chunk);
+
+        let checks = super::check_region(data.as_slice()).expect("Header is valid");
+        assert_eq!(
+            checks,
+            vec![
+                ChunkCheck {
+                    x: 0,
+                    z: 0,
+                    result: Ok(())
+                },
+                ChunkCheck {
+                    x: 1,
+                    z: 0,
+                    result: Err(ChunkCheckError::Load(
+                        LoadChunkDataError::ChunkDataLengthError
+                    ))
+                },
+                ChunkCheck {
+                    x: 2,
+                    z: 0,
+                    result: Err(ChunkCheckError::InsideHeader)
+                },
+                ChunkCheck {
+                    x: 3,
+                    z: 0,
+                    result: Err(ChunkCheckError::OutOfBounds)
+                },
+                ChunkCheck {
+                    x: 5,
+                    z: 0,
+                    result: Err(ChunkCheckError::NoSectors)
+                },
+                ChunkCheck {
+                    x: 0,
+                    z: 1,
+                    result: Err(ChunkCheckError::Overlapping(0, 0))
+                },
+            ]
+        );
+    }
+
+    #[cfg(feature = "region_file")]
+    #[test]
+    fn test_load_region_timed() {
+        use crate::data::{chunk::tests::valid_chunk_data, file_format::anvil};
+        let mut data = vec![0; anvil::MC_REGION_HEADER_SIZE + anvil::SECTOR_SIZE];
+        data[0..4].copy_from_slice(&[0, 0, 2, 1]);
+        let chunk = valid_chunk_data();
+        data[anvil::MC_REGION_HEADER_SIZE..anvil::MC_REGION_HEADER_SIZE + chunk.len()]
+            .copy_from_slice(&chunk);
+
+        let (region, timing) =
+            super::load_region_timed(data.as_slice(), None).expect("Region is valid");
+        assert_eq!(region.chunks.len(), 1);
+        assert_eq!(
+            Some(&region.chunks[0]),
+            super::load_region(data.as_slice(), None)
+                .expect("Region is valid")
+                .chunks
+                .first()
+        );
+        assert!(timing.decompress + timing.parse > std::time::Duration::ZERO);
+    }
+
+    #[cfg(feature = "region_file")]
+    #[test]
+    fn test_read_chunk_from_region() {
+        use crate::data::{chunk::tests::valid_chunk_data, file_format::anvil};
+        // Chunk 1,2 of the region is stored in the third sector
+        let index = 1 + 2 * 32;
+        let mut data = vec![0; anvil::MC_REGION_HEADER_SIZE + anvil::SECTOR_SIZE];
+        data[index * 4..index * 4 + 4].copy_from_slice(&[0, 0, 2, 1]);
+        let chunk = valid_chunk_data();
+        data[anvil::MC_REGION_HEADER_SIZE..anvil::MC_REGION_HEADER_SIZE + chunk.len()]
+            .copy_from_slice(&chunk);
+
+        let read = |chunk_x, chunk_z| {
+            super::read_chunk_from_region(std::io::Cursor::new(&data), chunk_x, chunk_z)
+                .expect("Region is valid")
+        };
+        let chunk = read(1, 2).expect("Chunk is present");
+        assert_eq!(chunk.x_pos, 1234);
+        // World coordinates of the same chunk in region -1,-1
+        assert_eq!(read(-31, -30), Some(chunk));
+        assert_eq!(read(2, 1), None);
+    }
+
+    #[cfg(feature = "region_file")]
+    #[test]
+    fn test_read_chunk_bytes_from_region() {
+        use crate::data::{
+            chunk::{tests::valid_chunk_data, ChunkData},
+            file_format::anvil,
+        };
+        let index = 1 + 2 * 32;
+        let mut data = vec![0; anvil::MC_REGION_HEADER_SIZE + anvil::SECTOR_SIZE];
+        data[index * 4..index * 4 + 4].copy_from_slice(&[0, 0, 2, 1]);
+        let chunk = valid_chunk_data();
+        data[anvil::MC_REGION_HEADER_SIZE..anvil::MC_REGION_HEADER_SIZE + chunk.len()]
+            .copy_from_slice(&chunk);
+
+        let bytes = super::read_chunk_bytes_from_region(std::io::Cursor::new(&data), 1, 2)
+            .expect("Region is valid")
+            .expect("Chunk is present");
+        let tag = crate::nbt::parse(bytes.as_slice()).expect("Chunk is valid NBT");
+        let chunk = super::read_chunk_from_region(std::io::Cursor::new(&data), 1, 2)
+            .expect("Region is valid");
+        assert_eq!(ChunkData::try_from(tag).ok(), chunk);
+        assert_eq!(
+            super::read_chunk_bytes_from_region(std::io::Cursor::new(&data), 2, 1)
+                .expect("Region is valid"),
+            None
+        );
+    }
+
+    #[cfg(feature = "region_file")]
+    #[test]
+    fn test_read_chunk_missing_region() {
+        let save_directory =
+            std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("resources/tests");
+        let chunk = super::read_chunk(&save_directory, None, 32 * 10, 0)
+            .expect("Missing region is not an error");
+        assert_eq!(chunk, None);
+    }
+
+    #[cfg(all(feature = "region_file", feature = "block_entity"))]
+    const BYTE_ID: u8 = 1;
+    #[cfg(all(feature = "region_file", feature = "block_entity"))]
+    const INT_ID: u8 = 3;
+    #[cfg(feature = "region_file")]
+    const STRING_ID: u8 = 8;
+    #[cfg(feature = "region_file")]
+    const LIST_ID: u8 = 9;
+    #[cfg(feature = "region_file")]
+    const COMPOUND_ID: u8 = 10;
+
+    #[cfg(feature = "region_file")]
+    fn push_str(data: &mut Vec<u8>, string: &str) {
+        data.extend((string.len() as i16).to_be_bytes());
+        data.extend(string.as_bytes());
+    }
+
+    #[cfg(all(feature = "region_file", feature = "block_entity"))]
+    fn push_named(data: &mut Vec<u8>, tag_id: u8, name: &str) {
+        data.push(tag_id);
+        push_str(data, name);
+    }
+
+    /// Write a region file containing a single uncompressed chunk at `index`.
+    #[cfg(feature = "region_file")]
+    fn write_region(path: &std::path::Path, index: usize, mut chunk: Vec<u8>) {
+        use crate::data::file_format::anvil;
+        // Close the root compound and update the length
+        chunk.push(0);
+        let length = (chunk.len() as u32 - 4).to_be_bytes();
+        chunk[0..4].copy_from_slice(&length);
+        let mut region = vec![0; anvil::MC_REGION_HEADER_SIZE + anvil::SECTOR_SIZE];
+        region[index * 4..index * 4 + 4].copy_from_slice(&[0, 0, 2, 1]);
+        region[anvil::MC_REGION_HEADER_SIZE..anvil::MC_REGION_HEADER_SIZE + chunk.len()]
+            .copy_from_slice(&chunk);
+        std::fs::create_dir_all(path.parent().expect("Region file has a parent"))
+            .expect("Error creating region directory");
+        std::fs::write(path, region).expect("Error writing region file");
+    }
+
+    #[cfg(feature = "region_file")]
+    #[test]
+    fn test_entities_for_chunk() {
+        use crate::data::chunk::tests::valid_chunk_data;
+        fn push_entities(data: &mut Vec<u8>, key: &str, ids: &[&str]) {
+            data.push(LIST_ID);
+            push_str(data, key);
+            data.push(COMPOUND_ID);
+            data.extend((ids.len() as i32).to_be_bytes());
+            for id in ids {
+                data.push(STRING_ID);
+                push_str(data, "id");
+                push_str(data, id);
+                data.push(0);
+            }
+        }
+
+        let save_directory =
+            std::env::temp_dir().join(format!("mc-map-reader-entities-{}", std::process::id()));
+        // Chunk 1,0 has not been converted and still stores its entities
+        let mut chunk = valid_chunk_data();
+        push_entities(&mut chunk, "entities", &["minecraft:item"]);
+        write_region(&save_directory.join("region/r.0.0.mca"), 1, chunk);
+        // Chunk 0,0 stores its entities in the entity region
+        let mut entities_chunk = vec![0, 0, 0, 0, 3, COMPOUND_ID, 0, 0];
+        push_entities(&mut entities_chunk, "Entities", &["minecraft:zombie"]);
+        write_region(
+            &save_directory.join("entities/r.0.0.mca"),
+            0,
+            entities_chunk,
+        );
+
+        let entity_ids = |chunk_x| {
+            super::entities_for_chunk(&save_directory, None, chunk_x, 0)
+                .expect("Entities are valid")
+                .into_iter()
+                .map(|entity| entity.id.unwrap_or_default())
+                .collect::<Vec<_>>()
+        };
+        let from_entity_region = entity_ids(0);
+        let from_chunk = entity_ids(1);
+        let missing = entity_ids(2);
+        std::fs::remove_dir_all(&save_directory).expect("Error removing save directory");
+
+        assert_eq!(from_entity_region, vec!["minecraft:zombie"]);
+        assert_eq!(from_chunk, vec!["minecraft:item"]);
+        assert_eq!(missing, Vec::<String>::new());
+    }
+
+    #[cfg(all(feature = "region_file", feature = "block_entity"))]
+    #[test]
+    fn test_iter_containers() {
+        use crate::{coords::BlockPos, data::chunk::tests::valid_chunk_data};
+        fn push_block_entities(data: &mut Vec<u8>, block_entities: &[(&str, i32, i8)]) {
+            push_named(data, LIST_ID, "block_entities");
+            data.push(COMPOUND_ID);
+            data.extend((block_entities.len() as i32).to_be_bytes());
+            for (id, x, item_count) in block_entities {
+                push_named(data, STRING_ID, "id");
+                push_str(data, id);
+                for (key, value) in [("x", *x), ("y", 64), ("z", -3)] {
+                    push_named(data, INT_ID, key);
+                    data.extend(value.to_be_bytes());
+                }
+                push_named(data, LIST_ID, "Items");
+                data.push(COMPOUND_ID);
+                data.extend(1i32.to_be_bytes());
+                push_named(data, BYTE_ID, "Slot");
+                data.push(0);
+                push_named(data, STRING_ID, "id");
+                push_str(data, "minecraft:diamond");
+                push_named(data, BYTE_ID, "Count");
+                data.push(*item_count as u8);
+                data.push(0);
+                data.push(0);
+            }
+        }
+
+        let save_directory =
+            std::env::temp_dir().join(format!("mc-map-reader-containers-{}", std::process::id()));
+        let mut chunk = valid_chunk_data();
+        push_block_entities(
+            &mut chunk,
+            &[
+                ("minecraft:chest", 1, 5),
+                ("minecraft:bell", 2, 0),
+                ("minecraft:hopper", 3, 7),
+            ],
+        );
+        write_region(&save_directory.join("region/r.0.0.mca"), 0, chunk);
+        let mut chunk = valid_chunk_data();
+        push_block_entities(&mut chunk, &[("minecraft:barrel", -20, 9)]);
+        write_region(&save_directory.join("region/r.-1.0.mca"), 0, chunk);
+
+        let mut containers = super::iter_containers(&save_directory, None)
+            .expect("Region directory exists")
+            .map(|container| {
+                let (position, container) = container.expect("Region is valid");
+                (
+                    position,
+                    container.slot_count(),
+                    container.items()[0].item.count,
+                )
+            })
+            .collect::<Vec<_>>();
+        std::fs::remove_dir_all(&save_directory).expect("Error removing save directory");
+        containers.sort_by_key(|(position, _, _)| position.x);
+
+        let position = |x| BlockPos { x, y: 64, z: -3 };
+        assert_eq!(
+            containers,
+            vec![
+                (position(-20), 27, 9),
+                (position(1), 27, 5),
+                (position(3), 5, 7)
+            ]
+        );
+    }
+
+    #[cfg(feature = "region_file")]
+    #[test]
+    fn test_check_region_invalid_header() {
+        assert!(super::check_region([0u8; 16].as_slice()).is_err());
+    }
+
+    #[cfg(feature = "level_dat")]
+    #[test_case(&[0x1f, 0x8b, 8, 0, 0, 0, 0, 0, 0, 0] => false; "Java Edition gzip")]
+    #[test_case(&[10, 0, 0, 0, 3, 0, 0, 0, 10, 0, 0] => true; "Bedrock Edition")]
+    #[test_case(&[10, 0, 0, 0, 4, 0, 0, 0, 10, 0, 0] => false; "Wrong length")]
+    #[test_case(&[10, 0, 0] => false; "Too short")]
+    fn test_is_bedrock_level_dat(data: &[u8]) -> bool {
+        super::is_bedrock_level_dat(data)
+    }
+
+    #[cfg(feature = "level_dat")]
+    #[test]
+    fn test_parse_bedrock_level_dat() {
+        let data = [10, 0, 0, 0, 3, 0, 0, 0, 10, 0, 0];
+        assert!(matches!(
+            super::parse_level_dat(&data),
+            Err(super::LevelDatLoadError::Bedrock)
+        ));
+    }
 
     #[test]
     fn test_level_dat_file_success() {
@@ -315,6 +315,103 @@ impl<T> Deref for List<T> {
     }
 }
 
+impl Tag {
+    /// Serialize the tag to SNBT, the text format used by Minecraft commands.
+    /// Keys of compound tags are sorted to get a stable output.
+    pub fn to_snbt(&self) -> String {
+        let mut snbt = String::new();
+        self.write_snbt(&mut snbt);
+        snbt
+    }
+
+    fn write_snbt(&self, snbt: &mut String) {
+        match self {
+            Self::End => {}
+            Self::Byte(v) => snbt.push_str(&format!("{v}b")),
+            Self::Short(v) => snbt.push_str(&format!("{v}s")),
+            Self::Int(v) => snbt.push_str(&v.to_string()),
+            Self::Long(v) => snbt.push_str(&format!("{v}L")),
+            Self::Float(v) => snbt.push_str(&format!("{v}f")),
+            Self::Double(v) => snbt.push_str(&format!("{v}d")),
+            Self::ByteArray(values) => {
+                write_snbt_array(snbt, "B", values.iter(), |v| format!("{v}b"))
+            }
+            Self::String(v) => write_snbt_string(snbt, v),
+            Self::List(values) => {
+                snbt.push('[');
+                values.iter().enumerate().for_each(|(i, value)| {
+                    if i > 0 {
+                        snbt.push(',');
+                    }
+                    value.write_snbt(snbt);
+                });
+                snbt.push(']');
+            }
+            Self::Compound(map) => {
+                let mut entries = map.iter().collect::<Vec<_>>();
+                entries.sort_by(|a, b| a.0.cmp(b.0));
+                snbt.push('{');
+                entries
+                    .into_iter()
+                    .enumerate()
+                    .for_each(|(i, (key, value))| {
+                        if i > 0 {
+                            snbt.push(',');
+                        }
+                        if is_unquoted_snbt_key(key) {
+                            snbt.push_str(key);
+                        } else {
+                            write_snbt_string(snbt, key);
+                        }
+                        snbt.push(':');
+                        value.write_snbt(snbt);
+                    });
+                snbt.push('}');
+            }
+            Self::IntArray(values) => write_snbt_array(snbt, "I", values.iter(), i32::to_string),
+            Self::LongArray(values) => {
+                write_snbt_array(snbt, "L", values.iter(), |v| format!("{v}L"))
+            }
+        }
+    }
+}
+
+fn write_snbt_array<'a, T: 'a>(
+    snbt: &mut String,
+    prefix: &str,
+    values: impl Iterator<Item = &'a T>,
+    format: impl Fn(&T) -> String,
+) {
+    snbt.push('[');
+    snbt.push_str(prefix);
+    snbt.push(';');
+    values.enumerate().for_each(|(i, value)| {
+        if i > 0 {
+            snbt.push(',');
+        }
+        snbt.push_str(&format(value));
+    });
+    snbt.push(']');
+}
+
+fn is_unquoted_snbt_key(key: &str) -> bool {
+    !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | '+'))
+}
+
+fn write_snbt_string(snbt: &mut String, value: &str) {
+    snbt.push('"');
+    value.chars().for_each(|c| {
+        if matches!(c, '"' | '\\') {
+            snbt.push('\\');
+        }
+        snbt.push(c);
+    });
+    snbt.push('"');
+}
+
 /// Parse a NBT tag from a byte slice.
 pub fn parse(data: &[u8]) -> Result<Tag, Error> {
     match data[0] {
@@ -688,4 +785,27 @@ mod tests {
         result.sort_by(|a, b| a.0.cmp(&b.0));
         result
     }
+
+    #[test_case(Tag::Byte(1) => "1b"; "Byte tag")]
+    #[test_case(Tag::Short(-2) => "-2s"; "Short tag")]
+    #[test_case(Tag::Int(3) => "3"; "Int tag")]
+    #[test_case(Tag::Long(4) => "4L"; "Long tag")]
+    #[test_case(Tag::Float(1.5) => "1.5f"; "Float tag")]
+    #[test_case(Tag::Double(2.5) => "2.5d"; "Double tag")]
+    #[test_case(Tag::ByteArray(Array(vec![1, 2])) => "[B;1b,2b]"; "Byte array tag")]
+    #[test_case(Tag::String("Say \"Hi\"".to_owned()) => "\"Say \\\"Hi\\\"\""; "String tag")]
+    #[test_case(Tag::List(List(vec![Tag::Int(1), Tag::Int(2)])) => "[1,2]"; "List tag")]
+    #[test_case(
+        Tag::Compound(HashMap::from_iter([
+            ("b".to_owned(), Tag::Byte(1)),
+            ("a key".to_owned(), Tag::String("A".to_owned())),
+            ("A".to_owned(), Tag::Compound(HashMap::new())),
+        ])) => "{A:{},\"a key\":\"A\",b:1b}";
+        "Compound tag"
+    )]
+    #[test_case(Tag::IntArray(Array(vec![1, 2])) => "[I;1,2]"; "Int array tag")]
+    #[test_case(Tag::LongArray(Array(vec![1, 2])) => "[L;1L,2L]"; "Long array tag")]
+    fn test_to_snbt(tag: Tag) -> String {
+        tag.to_snbt()
+    }
 }
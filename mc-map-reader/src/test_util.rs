@@ -19,3 +19,12 @@ pub fn merge(mut map: HashMap<String, Tag>, other: HashMap<String, Tag>) -> Hash
     map.extend(other);
     map
 }
+
+pub fn compound(entries: Vec<(&str, Tag)>) -> Tag {
+    Tag::Compound(
+        entries
+            .into_iter()
+            .map(|(key, value)| (key.to_string(), value))
+            .collect(),
+    )
+}
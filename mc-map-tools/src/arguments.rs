@@ -11,9 +11,15 @@ pub struct Args {
     pub action: Action,
     /// Path to the Minecraft Save
     pub save_directory: PathBuf,
-    /// Override the default config file
-    #[arg(short, long)]
-    pub config_file: Option<PathBuf>,
+    /// Override the default config file. Can be given multiple times, later files take precedence
+    #[arg(short, long = "config-file", visible_alias = "config")]
+    pub config_files: Vec<PathBuf>,
+    /// Override a single config value e.g. search_dupe_stashes.groups.diamond.threshold=64. Can be given multiple times
+    #[arg(long = "set", value_name = "KEY=VALUE", value_parser = parse_config_override)]
+    pub config_overrides: Vec<(String, String)>,
+    /// Start from a built-in config instead of the default config. Config files are merged on top of the preset
+    #[arg(long)]
+    pub preset: Option<crate::config::Preset>,
     #[arg(short, long, default_value = "off")]
     pub log_level: LogLevel,
 }
@@ -25,7 +31,22 @@ pub enum Action {
     /// Find inventories of a specific type
     FindInventories(crate::find_inventories::config::SearchEntity),
     #[cfg(feature = "experimental")]
-    ReadLevelDat,
+    ReadLevelDat(crate::read_level_dat::args::ReadLevelDat),
+    /// Check region files for damaged chunks
+    CheckRegion(crate::check_region::args::CheckRegion),
+    /// Write the NBT data of a single chunk to a standalone file
+    ExtractChunk(crate::extract_chunk::args::ExtractChunk),
+    /// Work with the config file
+    #[cfg(feature = "schema")]
+    #[command(subcommand)]
+    Config(ConfigAction),
+}
+
+#[cfg(feature = "schema")]
+#[derive(Debug, Subcommand)]
+pub enum ConfigAction {
+    /// Print a JSON Schema of the config file
+    Schema,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
@@ -58,6 +79,13 @@ impl Display for LogLevel {
     }
 }
 
+fn parse_config_override(value: &str) -> Result<(String, String), String> {
+    value
+        .split_once('=')
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .ok_or_else(|| format!("Can not parse config override \"{value}\". Overrides must be given as followed: \"<key>=<value>\"."))
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -84,4 +112,12 @@ mod tests {
     fn test_level_filer_from_log_level_display(level: LogLevel) -> String {
         level.to_string()
     }
+
+    #[test_case("a.b=64" => Ok(("a.b".to_string(), "64".to_string())); "Success")]
+    #[test_case("a.b={\"c\":1}" => Ok(("a.b".to_string(), "{\"c\":1}".to_string())); "JSON value")]
+    #[test_case("a.b=1=2" => Ok(("a.b".to_string(), "1=2".to_string())); "Value with equal sign")]
+    #[test_case("a.b" => Err("Can not parse config override \"a.b\". Overrides must be given as followed: \"<key>=<value>\".".to_string()); "Missing value")]
+    fn test_parse_config_override(value: &str) -> Result<(String, String), String> {
+        parse_config_override(value)
+    }
 }
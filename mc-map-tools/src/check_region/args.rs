@@ -0,0 +1,7 @@
+use std::path::PathBuf;
+
+#[derive(Debug, clap::Parser)]
+pub struct CheckRegion {
+    /// A region file or a directory containing region files. Defaults to the region directory of the save
+    pub path: Option<PathBuf>,
+}
@@ -0,0 +1,102 @@
+pub mod args;
+
+use std::{fs::File, io::Write, path::Path};
+
+use mc_map_reader::ChunkCheck;
+
+use self::args::CheckRegion;
+
+pub fn main(save_directory: &Path, args: &CheckRegion, writer: &mut dyn Write) {
+    let region_files = match &args.path {
+        Some(path) if path.is_file() => vec![path.clone()],
+        Some(path) => mc_map_reader::files::get_regions_of_region_dir(path)
+            .expect("Could not read region directory")
+            .iter()
+            .map(|region| region.as_path().to_path_buf())
+            .collect(),
+        None => mc_map_reader::files::get_regions(save_directory, None)
+            .expect("Could not read region directory")
+            .iter()
+            .map(|region| region.as_path().to_path_buf())
+            .collect(),
+    };
+
+    region_files.iter().for_each(|path| {
+        let name = region_name(path);
+        let checks = File::open(path)
+            .map_err(mc_map_reader::RegionLoadError::from)
+            .and_then(mc_map_reader::check_region);
+        match checks {
+            Ok(checks) => write_report(&name, &checks, writer),
+            Err(err) => {
+                log::error!("Could not check region file {name}: {err}");
+                writeln!(writer, "{name}: could not be read: {err}")
+            }
+        }
+        .expect("Error writing message");
+    });
+}
+
+fn region_name(path: &Path) -> String {
+    path.file_name()
+        .map(|file_name| file_name.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.display().to_string())
+}
+
+fn write_report(name: &str, checks: &[ChunkCheck], writer: &mut dyn Write) -> std::io::Result<()> {
+    let mut damaged = 0;
+    for check in checks {
+        match &check.result {
+            Ok(()) => writeln!(writer, "{name}: chunk {},{} OK", check.x, check.z)?,
+            Err(err) => {
+                damaged += 1;
+                writeln!(
+                    writer,
+                    "{name}: chunk {},{} damaged: {err}",
+                    check.x, check.z
+                )?
+            }
+        }
+    }
+    writeln!(
+        writer,
+        "{name}: {} healthy, {damaged} damaged",
+        checks.len() - damaged
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use mc_map_reader::{ChunkCheck, ChunkCheckError};
+
+    #[test]
+    fn test_write_report() {
+        let checks = [
+            ChunkCheck {
+                x: 0,
+                z: 0,
+                result: Ok(()),
+            },
+            ChunkCheck {
+                x: 1,
+                z: 0,
+                result: Err(ChunkCheckError::OutOfBounds),
+            },
+            ChunkCheck {
+                x: 0,
+                z: 1,
+                result: Ok(()),
+            },
+        ];
+        let mut output = Vec::new();
+        super::write_report("r.0.0.mca", &checks, &mut output).expect("Error writing report");
+        assert_eq!(
+            String::from_utf8(output).expect("Output is valid UTF-8"),
+            "r.0.0.mca: chunk 0,0 OK
+r.0.0.mca: chunk 1,0 damaged: Chunk ends after the end of the region file
+r.0.0.mca: chunk 0,1 OK
+r.0.0.mca: 2 healthy, 1 damaged
+"
+        );
+    }
+}
@@ -1,16 +1,36 @@
 use std::io::Read;
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use thiserror::Error;
 
 use crate::search_dupe_stashes::config::SearchDupeStashesConfig;
 
-#[derive(Debug, PartialEq, Deserialize, Default)]
+#[derive(Debug, PartialEq, Deserialize, Serialize, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Config {
     pub search_dupe_stashes: SearchDupeStashesConfig,
 }
 
+/// Built-in configs that can be used instead of writing a config file.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, clap::ValueEnum)]
+pub enum Preset {
+    /// Groups for diamonds, netherite, elytras, totems, enchanted golden apples, nether stars and dragon eggs
+    Valuables,
+}
+
 impl Config {
+    /// The config of a built-in preset.
+    pub fn preset(preset: Preset) -> Self {
+        let search_dupe_stashes = match preset {
+            Preset::Valuables => include_str!("../preset-valuables.json"),
+        };
+        Self {
+            search_dupe_stashes: serde_json::from_str(search_dupe_stashes)
+                .expect("Invalid preset"),
+        }
+    }
+
     pub fn new<R>(reader: R) -> Result<Self, ConfigLoadError>
     where
         R: Read,
@@ -18,6 +38,40 @@ impl Config {
         let config = serde_json::from_reader(reader)?;
         Ok(config)
     }
+
+    /// Merge another config into this one. Values of `other` take precedence.
+    pub fn merge(&mut self, other: Self) {
+        self.search_dupe_stashes.merge(other.search_dupe_stashes);
+    }
+
+    /// Set a single value of the config. `key` is a dotted path e.g. `search_dupe_stashes.groups.diamond.threshold`.
+    /// The key must already exist in the config. List entries are selected by their index.
+    /// The value is parsed as JSON. Values that are not valid JSON are used as strings.
+    pub fn set(self, key: &str, value: &str) -> Result<Self, ConfigLoadError> {
+        let mut config = serde_json::to_value(self)?;
+        let target = key
+            .split('.')
+            .try_fold(&mut config, |target, segment| match target {
+                Value::Object(map) => map.get_mut(segment),
+                Value::Array(list) => segment
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|index| list.get_mut(index)),
+                _ => None,
+            })
+            .ok_or_else(|| ConfigLoadError::UnknownKey(key.to_string()))?;
+        *target = serde_json::from_str(value).unwrap_or_else(|_| Value::String(value.to_string()));
+        serde_json::from_value(config).map_err(|source| ConfigLoadError::InvalidValue {
+            key: key.to_string(),
+            source,
+        })
+    }
+}
+
+/// JSON Schema of the config file. Editors can use it to validate and autocomplete config files.
+#[cfg(feature = "schema")]
+pub fn schema() -> schemars::schema::RootSchema {
+    schemars::schema_for!(Config)
 }
 
 #[derive(Debug, Error)]
@@ -26,6 +80,13 @@ pub enum ConfigLoadError {
     Io(#[from] std::io::Error),
     #[error(transparent)]
     Json(#[from] serde_json::Error),
+    #[error("Unknown config key \"{0}\"")]
+    UnknownKey(String),
+    #[error("Invalid value for config key \"{key}\": {source}")]
+    InvalidValue {
+        key: String,
+        source: serde_json::Error,
+    },
 }
 
 #[cfg(test)]
@@ -33,6 +94,7 @@ mod tests {
     use std::collections::HashMap;
 
     use super::*;
+    use test_case::test_case;
 
     #[test]
     fn test_config() {
@@ -43,8 +105,116 @@ mod tests {
             Config {
                 search_dupe_stashes: SearchDupeStashesConfig {
                     groups: HashMap::new(),
+                    exclude: Vec::new(),
+                    max_stack_sizes: HashMap::new(),
+                    exclude_items: Vec::new(),
                 }
             }
         );
     }
+
+    #[test]
+    fn test_set_keeps_single_chunk_exclude() {
+        let config = Config::new(
+            r#"{"search_dupe_stashes": {
+                "groups": {"diamond": {"items": [{"id": "minecraft:diamond"}], "threshold": 100}},
+                "exclude": ["3,-4"]
+            }}"#
+            .as_bytes(),
+        )
+        .expect("Invalid config")
+        .set("search_dupe_stashes.groups.diamond.threshold", "10")
+        .expect("Valid override");
+        let config = config.search_dupe_stashes;
+        assert_eq!(config.groups["diamond"].threshold, 10);
+        assert!(config.is_chunk_excluded(3, -4));
+        assert!(!config.is_chunk_excluded(4, -4));
+        assert!(!config.is_chunk_excluded(3, -3));
+    }
+
+    #[test]
+    fn test_set_list_entry() {
+        let config = Config::default()
+            .set(
+                "search_dupe_stashes.groups.diamond.items.1.id",
+                "minecraft:diamond_ore",
+            )
+            .expect("Valid override");
+        let item = mc_map_reader::data::item::Item {
+            id: "minecraft:diamond_ore".to_string(),
+            tag: None,
+            components: None,
+            count: 1,
+        };
+        assert!(config.search_dupe_stashes.groups["diamond"].matches(&item));
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut config = Config::new(
+            r#"{"search_dupe_stashes": {
+                "groups": {
+                    "diamond": {"items": [{"id": "minecraft:diamond"}], "threshold": 100},
+                    "gold": {"items": [{"id": "minecraft:gold_ingot"}], "threshold": 200}
+                },
+                "max_stack_sizes": {"minecraft:ender_pearl": 64},
+                "exclude_items": ["minecraft:cobblestone"]
+            }}"#
+            .as_bytes(),
+        )
+        .expect("Invalid config");
+        let overrides = Config::new(
+            r#"{"search_dupe_stashes": {
+                "groups": {"diamond": {"items": [{"id": "minecraft:diamond"}], "threshold": 10}},
+                "exclude": ["0,0"],
+                "exclude_items": ["minecraft:dirt"]
+            }}"#
+            .as_bytes(),
+        )
+        .expect("Invalid config");
+        config.merge(overrides);
+        let config = config.search_dupe_stashes;
+        assert_eq!(config.groups["diamond"].threshold, 10);
+        assert_eq!(config.groups["gold"].threshold, 200);
+        assert_eq!(
+            config.max_stack_sizes,
+            HashMap::from([("minecraft:ender_pearl".to_string(), 64)])
+        );
+        assert!(config.is_chunk_excluded(0, 0));
+        assert_eq!(
+            config.exclude_items,
+            vec!["minecraft:cobblestone", "minecraft:dirt"]
+        );
+    }
+
+    #[test_case("search_dupe_stashes.groups.emerald.threshold", "64" => "Unknown config key \"search_dupe_stashes.groups.emerald.threshold\""; "Unknown group")]
+    #[test_case("search_dupe_stashes.groups.diamond.threshold.value", "64" => "Unknown config key \"search_dupe_stashes.groups.diamond.threshold.value\""; "Path into number")]
+    #[test_case("search_dupe_stashes.groups.diamond.items.5", "{}" => "Unknown config key \"search_dupe_stashes.groups.diamond.items.5\""; "Index out of range")]
+    #[test_case("search_dupe_stashes.groups.diamond.threshold", "many" => "Invalid value for config key \"search_dupe_stashes.groups.diamond.threshold\": invalid type: string \"many\", expected usize"; "Wrong type")]
+    fn test_set_invalid(key: &str, value: &str) -> String {
+        Config::default()
+            .set(key, value)
+            .expect_err("Invalid override")
+            .to_string()
+    }
+
+    #[test]
+    fn test_preset_valuables() {
+        let config = Config::preset(Preset::Valuables).search_dupe_stashes;
+        assert_eq!(config.groups["netherite"].threshold, 256);
+        assert!(config.groups.contains_key("elytra"));
+        assert!(config.exclude.is_empty());
+    }
+
+    #[cfg(feature = "schema")]
+    #[test]
+    fn test_schema_validates_default_config() {
+        let schema = serde_json::to_string(&schema()).expect("Error serializing schema");
+        let schema: Value = serde_json::from_str(&schema).expect("Schema is not valid JSON");
+        let schema = jsonschema::JSONSchema::compile(&schema).expect("Invalid JSON Schema");
+        let config = serde_json::to_value(Config::default()).expect("Error serializing config");
+        assert!(schema.is_valid(&config));
+        let invalid_config = serde_json::json!({"search_dupe_stashes": {"groups": {"diamond": {"items": [], "threshold": "many"}}}});
+        assert!(!schema.is_valid(&invalid_config));
+    }
 }
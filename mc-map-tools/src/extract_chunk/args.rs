@@ -0,0 +1,17 @@
+use std::path::PathBuf;
+
+use crate::find_inventories::config::Dimension;
+
+#[derive(Debug, clap::Parser)]
+#[command(allow_negative_numbers = true)]
+pub struct ExtractChunk {
+    /// The dimension containing the chunk
+    #[arg(value_enum)]
+    pub dimension: Dimension,
+    /// The x coordinate of the chunk
+    pub chunk_x: i32,
+    /// The z coordinate of the chunk
+    pub chunk_z: i32,
+    /// The file the gzip compressed NBT data of the chunk is written to
+    pub output: PathBuf,
+}
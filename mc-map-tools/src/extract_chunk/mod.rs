@@ -0,0 +1,95 @@
+pub mod args;
+
+use std::{
+    fs::File,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use mc_map_reader::RegionLoadError;
+use thiserror::Error;
+
+use self::args::ExtractChunk;
+
+#[derive(Debug, Error)]
+pub enum ExtractChunkError {
+    #[error("Chunk {0},{1} has not been generated")]
+    NotGenerated(i32, i32),
+    #[error(transparent)]
+    Load(#[from] RegionLoadError),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+pub fn main(save_directory: &Path, args: &ExtractChunk, writer: &mut dyn Write) {
+    match extract_chunk(save_directory, args) {
+        Ok(()) => writeln!(
+            writer,
+            "Extracted chunk {},{} to {}",
+            args.chunk_x,
+            args.chunk_z,
+            args.output.display()
+        ),
+        Err(err) => {
+            log::error!("Could not extract chunk: {err}");
+            writeln!(writer, "Could not extract chunk: {err}")
+        }
+    }
+    .expect("Error writing message");
+}
+
+fn extract_chunk(save_directory: &Path, args: &ExtractChunk) -> Result<(), ExtractChunkError> {
+    let dimension: Option<PathBuf> = args.dimension.into();
+    let chunk = mc_map_reader::read_chunk_bytes(
+        save_directory,
+        dimension.as_deref(),
+        args.chunk_x,
+        args.chunk_z,
+    )?
+    .ok_or(ExtractChunkError::NotGenerated(args.chunk_x, args.chunk_z))?;
+    write_chunk(&chunk, File::create(&args.output)?)
+}
+
+/// Write the NBT data of a chunk gzip compressed, the same way Minecraft stores standalone NBT files.
+fn write_chunk(chunk: &[u8], writer: impl Write) -> Result<(), ExtractChunkError> {
+    let mut encoder = libflate::gzip::Encoder::new(writer)?;
+    encoder.write_all(chunk)?;
+    encoder.finish().into_result()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use mc_map_reader::nbt::{self, Tag};
+
+    #[test]
+    fn test_write_chunk_reparses() {
+        const INT_ID: u8 = 3;
+        const COMPOUND_ID: u8 = 10;
+        let mut chunk = vec![COMPOUND_ID, 0, 0];
+        for (key, value) in [("DataVersion", 3700_i32), ("xPos", -3), ("zPos", 7)] {
+            chunk.push(INT_ID);
+            chunk.extend((key.len() as u16).to_be_bytes());
+            chunk.extend(key.as_bytes());
+            chunk.extend(value.to_be_bytes());
+        }
+        chunk.push(0);
+
+        let mut file = Vec::new();
+        super::write_chunk(&chunk, &mut file).expect("Writing to a Vec does not fail");
+        let mut decompressed = Vec::new();
+        libflate::gzip::Decoder::new(file.as_slice())
+            .expect("File is gzip compressed")
+            .read_to_end(&mut decompressed)
+            .expect("File is gzip compressed");
+
+        assert_eq!(decompressed, chunk);
+        let tag = nbt::parse(&decompressed).expect("Chunk is valid NBT");
+        let Tag::Compound(tag) = tag else {
+            panic!("Chunk is not a compound");
+        };
+        assert_eq!(tag.get("xPos"), Some(&Tag::Int(-3)));
+    }
+}
@@ -70,6 +70,31 @@ where
     }
 }
 
+#[async_trait]
+impl<R> FileItemRead<R> for String
+where
+    R: Read + Unpin + Send + Sync,
+{
+    async fn read(data: &mut R) -> Result<Self> {
+        let len = u32::read(data).await?;
+        let mut bytes = vec![0; len as usize];
+        data.read_exact(&mut bytes).await?;
+        String::from_utf8(bytes)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+    }
+}
+
+#[async_trait]
+impl<W> FileItemWrite<W> for String
+where
+    W: Write + Unpin + Send + Sync,
+{
+    async fn write(&self, data: &mut W) -> Result<()> {
+        (self.len() as u32).write(data).await?;
+        data.write_all(self.as_bytes()).await
+    }
+}
+
 #[async_trait]
 impl<R, T> FileItemRead<R> for Vec<T>
 where
@@ -202,6 +227,31 @@ mod tests {
             assert!(u32::read(&mut slice).await.is_err());
         }
     }
+    mod string {
+        use crate::file::{FileItemRead, FileItemWrite};
+
+        #[async_std::test]
+        async fn string_serialize() {
+            let mut vec = Vec::new();
+            String::from("abc")
+                .write(&mut vec)
+                .await
+                .expect("Unexpected Error");
+            assert_eq!(&[0, 0, 0, 3, b'a', b'b', b'c'], vec.as_slice());
+            assert_eq!(
+                "abc",
+                String::read(&mut vec.as_slice())
+                    .await
+                    .expect("Unexpected Error")
+            );
+        }
+
+        #[async_std::test]
+        async fn string_from_invalid_utf8() {
+            let mut slice = [0u8, 0, 0, 1, 0xFF].as_slice();
+            assert!(String::read(&mut slice).await.is_err());
+        }
+    }
     mod vec {
         use crate::file::{FileItemRead, FileItemWrite};
 
@@ -10,6 +10,8 @@ pub struct Inventory {
     pub x: i32,
     pub y: i32,
     pub z: i32,
+    /// Block entity id of the inventory e.g. `minecraft:trapped_chest`
+    pub id: String,
     pub items: Vec<Item>,
 }
 
@@ -50,6 +52,7 @@ where
             x: FileItemRead::read(data).await?,
             y: FileItemRead::read(data).await?,
             z: FileItemRead::read(data).await?,
+            id: FileItemRead::read(data).await?,
             items: FileItemRead::read(data).await?,
         })
     }
@@ -64,6 +67,7 @@ where
         self.x.write(data).await?;
         self.y.write(data).await?;
         self.z.write(data).await?;
+        self.id.write(data).await?;
         self.items.write(data).await?;
         Ok(())
     }
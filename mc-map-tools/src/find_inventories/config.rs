@@ -16,7 +16,7 @@ impl From<Dimension> for Option<PathBuf> {
     fn from(value: Dimension) -> Self {
         match value {
             Dimension::End => Some(PathBuf::from("DIM1")),
-            Dimension::Nether => Some(PathBuf::from("DIM")),
+            Dimension::Nether => Some(PathBuf::from("DIM-1")),
             Dimension::Overworld => None,
         }
     }
@@ -4,7 +4,7 @@ use std::{
     path::{Path, PathBuf},
 };
 
-use mc_map_reader::data::chunk::ChunkData;
+use mc_map_reader::{coords::BlockPos, data::chunk::ChunkData};
 use wildmatch::WildMatch;
 
 use self::config::SearchEntity;
@@ -43,7 +43,10 @@ fn search_block_entity(chunk_data: &ChunkData, wildcards: &[WildMatch]) {
     block_entities
         .iter()
         .filter(|be| wildcards.iter().any(|w| w.matches(&be.id)))
-        .for_each(|be| println!("Found {} at x:{} y:{} z:{}", be.id, be.x, be.y, be.z))
+        .for_each(|be| {
+            let BlockPos { x, y, z } = be.position();
+            println!("Found {} at x:{x} y:{y} z:{z}", be.id)
+        })
 }
 
 fn compile_wildcards(wildcards: &[String]) -> Vec<WildMatch> {
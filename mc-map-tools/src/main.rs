@@ -10,10 +10,16 @@
 //! ### FindInventories (experimental)
 //! Find inventories of a specific type.
 //! ### ReadLevelDat (experimental)
-//! Read the level.dat file. This feature is currently pretty useless.
+//! Read the level.dat file and print it as debug output, JSON or SNBT.
+//! ### CheckRegion
+//! Check region files for damaged chunks.
+//! ### ExtractChunk
+//! Write the NBT data of a single chunk to a standalone file.
 
 mod arguments;
+mod check_region;
 mod config;
+mod extract_chunk;
 mod file;
 mod find_inventories;
 mod paths;
@@ -35,10 +41,30 @@ use crate::arguments::Args;
 async fn main() {
     let args = Args::parse();
     setup_logger(args.log_level.into());
-    let config = if let Some(config_file) = args.config_file.map(File::open) {
-        log::info!("Reading config file :\"{config_file:#?}\"");
-        let config_file = config_file.expect("Failed to open config file");
-        Config::new(config_file).expect("Failed to load config")
+    let read_config = |path: &PathBuf| {
+        log::info!("Reading config file :\"{path:#?}\"");
+        Config::new(File::open(path).expect("Failed to open config file"))
+            .expect("Failed to load config")
+    };
+    let config = if let Some(preset) = args.preset {
+        log::info!("Using preset {preset:?}");
+        let path: PathBuf = paths::Files::ConfigFile.into();
+        let config_files = if args.config_files.is_empty() && path.exists() {
+            vec![path]
+        } else {
+            args.config_files.clone()
+        };
+        config_files
+            .iter()
+            .fold(Config::preset(preset), |mut config, path| {
+                config.merge(read_config(path));
+                config
+            })
+    } else if let Some((first, rest)) = args.config_files.split_first() {
+        rest.iter().fold(read_config(first), |mut config, path| {
+            config.merge(read_config(path));
+            config
+        })
     } else {
         let path: PathBuf = paths::Files::ConfigFile.into();
         if path.exists() {
@@ -50,6 +76,11 @@ async fn main() {
             Config::default()
         }
     };
+    let config = args
+        .config_overrides
+        .iter()
+        .try_fold(config, |config, (key, value)| config.set(key, value))
+        .expect("Invalid config override");
     log::debug!("Config: {config:?}");
 
     match args.action {
@@ -67,7 +98,24 @@ async fn main() {
             find_inventories::main(args.save_directory.as_path(), &sub_args)
         }
         #[cfg(feature = "experimental")]
-        Action::ReadLevelDat => read_level_dat::main(args.save_directory.as_path()),
+        Action::ReadLevelDat(sub_args) => {
+            read_level_dat::main(args.save_directory.as_path(), &sub_args)
+        }
+        Action::CheckRegion(sub_args) => check_region::main(
+            args.save_directory.as_path(),
+            &sub_args,
+            &mut std::io::stdout().lock(),
+        ),
+        Action::ExtractChunk(sub_args) => extract_chunk::main(
+            args.save_directory.as_path(),
+            &sub_args,
+            &mut std::io::stdout().lock(),
+        ),
+        #[cfg(feature = "schema")]
+        Action::Config(arguments::ConfigAction::Schema) => {
+            serde_json::to_writer_pretty(std::io::stdout().lock(), &config::schema())
+                .expect("Error writing schema")
+        }
     }
 }
 
@@ -0,0 +1,19 @@
+#[derive(Debug, clap::Parser)]
+pub struct ReadLevelDat {
+    /// The output format
+    #[arg(short, long, default_value = "debug")]
+    pub format: Format,
+    /// Do not fall back to the backup level.dat_old if level.dat can not be read
+    #[arg(long)]
+    pub no_backup: bool,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, clap::ValueEnum)]
+pub enum Format {
+    /// Rust debug output of the parsed level.dat
+    Debug,
+    /// JSON representation of the raw NBT data
+    Json,
+    /// SNBT representation of the raw NBT data
+    Snbt,
+}
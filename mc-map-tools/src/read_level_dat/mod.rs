@@ -1,8 +1,153 @@
-use std::path::Path;
+pub mod args;
 
-pub fn main(save_directory: &Path) {
-    let level = save_directory.join("level.dat");
-    let level_dat = std::fs::read(level).expect("Failed to read level.dat");
-    let a = mc_map_reader::parse_level_dat(&level_dat).expect("Failed to parse level.dat");
-    println!("{:#?}", a);
+use std::{fmt::Display, path::Path};
+
+use mc_map_reader::nbt::Tag;
+use serde_json::Value;
+
+use self::args::{Format, ReadLevelDat};
+
+pub fn main(save_directory: &Path, args: &ReadLevelDat) {
+    let use_backup = !args.no_backup;
+    match args.format {
+        Format::Debug => {
+            let a = load_level_dat(save_directory, use_backup, mc_map_reader::parse_level_dat)
+                .expect("Failed to parse level.dat");
+            println!("{:#?}", a);
+        }
+        Format::Json => {
+            let nbt = load_level_dat(
+                save_directory,
+                use_backup,
+                mc_map_reader::parse_level_dat_nbt,
+            )
+            .expect("Failed to parse level.dat");
+            let json =
+                serde_json::to_string_pretty(&tag_to_json(nbt)).expect("Failed to serialize JSON");
+            println!("{json}");
+        }
+        Format::Snbt => {
+            let nbt = load_level_dat(
+                save_directory,
+                use_backup,
+                mc_map_reader::parse_level_dat_nbt,
+            )
+            .expect("Failed to parse level.dat");
+            println!("{}", nbt.to_snbt());
+        }
+    }
+}
+
+/// Read and parse the level.dat file of a save.
+/// If this fails and `use_backup` is set, the backup `level.dat_old` written by Minecraft is used instead.
+fn load_level_dat<T, E>(
+    save_directory: &Path,
+    use_backup: bool,
+    parse: impl Fn(&[u8]) -> Result<T, E>,
+) -> Result<T, String>
+where
+    E: Display,
+{
+    let read = |file_name: &str| -> Result<T, String> {
+        let data = std::fs::read(save_directory.join(file_name))
+            .map_err(|err| format!("Failed to read {file_name}: {err}"))?;
+        parse(&data).map_err(|err| format!("Failed to parse {file_name}: {err}"))
+    };
+    match read("level.dat") {
+        Ok(level_dat) => Ok(level_dat),
+        Err(err) if use_backup => match read("level.dat_old") {
+            Ok(level_dat) => {
+                log::warn!("{err}. Using the backup level.dat_old instead.");
+                Ok(level_dat)
+            }
+            Err(backup_err) => Err(format!("{err}. {backup_err}")),
+        },
+        Err(err) => Err(err),
+    }
+}
+
+fn tag_to_json(tag: Tag) -> Value {
+    match tag {
+        Tag::End => Value::Null,
+        Tag::Byte(v) => v.into(),
+        Tag::Short(v) => v.into(),
+        Tag::Int(v) => v.into(),
+        Tag::Long(v) => v.into(),
+        Tag::Float(v) => v.into(),
+        Tag::Double(v) => v.into(),
+        Tag::ByteArray(v) => v.to_vec().into(),
+        Tag::String(v) => v.into(),
+        Tag::List(v) => v.into_iter().map(tag_to_json).collect(),
+        Tag::Compound(v) => v.into_iter().map(|(k, v)| (k, tag_to_json(v))).collect(),
+        Tag::IntArray(v) => v.to_vec().into(),
+        Tag::LongArray(v) => v.to_vec().into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use mc_map_reader::nbt::{List, Tag};
+    use serde_json::{json, Value};
+    use test_case::test_case;
+
+    fn load_level_dat(
+        level_dat: Option<&str>,
+        level_dat_old: Option<&str>,
+        use_backup: bool,
+    ) -> Result<String, String> {
+        static SAVE_COUNTER: AtomicUsize = AtomicUsize::new(0);
+        let save_directory = std::env::temp_dir().join(format!(
+            "mc-map-tools-level-dat-{}-{}",
+            std::process::id(),
+            SAVE_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        std::fs::create_dir_all(&save_directory).expect("Error creating save directory");
+        for (file_name, content) in [("level.dat", level_dat), ("level.dat_old", level_dat_old)] {
+            if let Some(content) = content {
+                std::fs::write(save_directory.join(file_name), content)
+                    .expect("Error writing level.dat");
+            }
+        }
+        let result =
+            super::load_level_dat(
+                &save_directory,
+                use_backup,
+                |data| match std::str::from_utf8(data) {
+                    Ok(data) if data.starts_with("valid") => Ok(data.to_string()),
+                    _ => Err("corrupt"),
+                },
+            );
+        std::fs::remove_dir_all(&save_directory).expect("Error removing save directory");
+        result
+    }
+
+    #[test_case(Some("valid primary"), Some("valid backup"), true => Ok("valid primary".to_string()); "Valid primary")]
+    #[test_case(Some("broken"), Some("valid backup"), true => Ok("valid backup".to_string()); "Corrupt primary")]
+    #[test_case(None, Some("valid backup"), true => Ok("valid backup".to_string()); "Missing primary")]
+    #[test_case(Some("broken"), Some("valid backup"), false => Err("Failed to parse level.dat: corrupt".to_string()); "Backup disabled")]
+    #[test_case(Some("broken"), Some("broken"), true => Err("Failed to parse level.dat: corrupt. Failed to parse level.dat_old: corrupt".to_string()); "Corrupt backup")]
+    fn test_load_level_dat(
+        level_dat: Option<&str>,
+        level_dat_old: Option<&str>,
+        use_backup: bool,
+    ) -> Result<String, String> {
+        load_level_dat(level_dat, level_dat_old, use_backup)
+    }
+
+    #[test_case(Tag::Byte(1) => json!(1); "Byte")]
+    #[test_case(Tag::Long(-4) => json!(-4); "Long")]
+    #[test_case(Tag::Double(1.5) => json!(1.5); "Double")]
+    #[test_case(Tag::String("Hi".to_string()) => json!("Hi"); "String")]
+    #[test_case(Tag::List(List::from(vec![Tag::Int(1), Tag::Int(2)])) => json!([1, 2]); "List")]
+    #[test_case(
+        Tag::Compound(HashMap::from_iter([("Data".to_string(), Tag::Compound(HashMap::from_iter([("Version".to_string(), Tag::Int(19133))])))]))
+        => json!({"Data": {"Version": 19133}});
+        "Compound"
+    )]
+    fn test_tag_to_json(tag: Tag) -> Value {
+        super::tag_to_json(tag)
+    }
 }
@@ -1,17 +1,98 @@
-use std::path::PathBuf;
+use std::{fmt::Display, path::PathBuf, time::Duration};
 
 #[derive(Debug, clap::Parser)]
 pub struct SearchDupeStashes {
     /// An area of chunks
     #[arg(short, long, value_parser=parse_area)]
     pub area: Option<Area>,
-    /// The radius of chunks to be searched
+    /// The radius in blocks around each container in which items are counted
     #[arg(default_value = "1")]
     pub radius: u32,
+    /// The coordinate space used to print locations
+    #[arg(long, default_value = "block")]
+    pub coords: CoordinateSpace,
+    /// The format used to print potential stashes
+    #[arg(long, default_value = "text")]
+    pub output_format: OutputFormat,
+    /// Read region files from this directory instead of the save directory
+    #[arg(long)]
+    pub region_dir: Option<PathBuf>,
+    /// Only search region files modified within this duration e.g. 24h or 7d
+    #[arg(long, value_parser=parse_duration)]
+    pub since: Option<Duration>,
+    /// Only print the region files and the number of chunks that would be searched
+    #[arg(long)]
+    pub dry_run: bool,
+    /// Write a map of all chunks with findings to this SVG file
+    #[arg(long)]
+    pub svg: Option<PathBuf>,
+    /// Print the total count of every item id in the searched area instead of searching for stashes
+    #[arg(long)]
+    pub report: bool,
+    /// Only count containers at or above this y coordinate
+    #[arg(long, allow_hyphen_values = true)]
+    pub min_y: Option<i32>,
+    /// Only count containers at or below this y coordinate
+    #[arg(long, allow_hyphen_values = true)]
+    pub max_y: Option<i32>,
+    /// Only print findings near this location given as "<x>,<z>" in the coordinate space of --coords
+    #[arg(long, value_parser = parse_location)]
+    pub near: Option<(i32, i32)>,
+    /// The distance on the x and z axis a finding may have to the location given by --near
+    #[arg(long, default_value = "64")]
+    pub near_radius: u32,
+    /// List the stacks counted towards every reported group with their count, custom name and enchantments
+    #[arg(long)]
+    pub detail: bool,
+    /// Never count or report items with this id. Can be given multiple times
+    #[arg(long = "exclude-item", value_name = "ID")]
+    pub exclude_items: Vec<String>,
+    /// Search this save directory in addition to the save directory. Can be given multiple times.
+    /// Findings are tagged with the name of the save directory they were found in
+    #[arg(long = "world", value_name = "SAVE_DIRECTORY")]
+    pub worlds: Vec<PathBuf>,
+    /// Only search containers with this block entity id e.g. shulker_box. Can be given multiple times
+    #[arg(long = "container", value_name = "ID", value_parser = parse_container_id)]
+    pub containers: Vec<String>,
+    /// Print the N region files that took the longest to read, decompress and parse
+    #[arg(long, value_name = "N", num_args = 0..=1, default_missing_value = "10")]
+    pub profile: Option<usize>,
+    /// Print a note for every game rule of level.dat that lets players collect more items than in vanilla
+    #[arg(long)]
+    pub check_gamerules: bool,
+    /// Print every item that was added in a newer release than the world was last saved with instead of searching for stashes.
+    /// Only a selection of items added since 1.13 is known
+    #[arg(long)]
+    pub version_check: bool,
+    /// Print the ids, multipliers and threshold of every configured group and exit without scanning
+    #[arg(long)]
+    pub dump_groups: bool,
+    /// Stop at the first region file that can not be read.
+    /// By default such region files are skipped and a summary of the skipped files is printed at the end
+    #[arg(long, action = clap::ArgAction::SetTrue)]
+    pub abort_on_error: bool,
     #[command(subcommand)]
     pub mode: Option<SearchDupeStashesMode>,
 }
 
+#[derive(Debug, Copy, Clone, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// One line per group and container
+    Text,
+    /// A JSON array of all findings
+    Json,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, clap::ValueEnum)]
+pub enum CoordinateSpace {
+    /// Block coordinates as shown in game
+    Block,
+    /// Chunk coordinates
+    Chunk,
+    /// Region coordinates as used in region file names
+    Region,
+}
+
 #[derive(Debug, clap::Subcommand, PartialEq)]
 pub enum SearchDupeStashesMode {
     /// Gives warnings for every group that has more items than the threshold in a area
@@ -33,7 +114,7 @@ pub struct GrothRate {
     file_location: Option<PathBuf>,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
 pub struct Area {
     /// X value of first point
     pub x1: i32,
@@ -45,14 +126,138 @@ pub struct Area {
     pub z2: i32,
 }
 
+impl Area {
+    /// Create an area between two corners given in any order.
+    /// The corners are normalized so that `x1 <= x2` and `z1 <= z2`.
+    /// Both corners are part of the area, so corners with the same x or z value describe a single row of chunks.
+    pub fn new(x1: i32, z1: i32, x2: i32, z2: i32) -> Self {
+        Self {
+            x1: x1.min(x2),
+            z1: z1.min(z2),
+            x2: x1.max(x2),
+            z2: z1.max(z2),
+        }
+    }
+
+    /// Create an area containing a single chunk.
+    pub fn chunk(x: i32, z: i32) -> Self {
+        Self {
+            x1: x,
+            z1: z,
+            x2: x,
+            z2: z,
+        }
+    }
+
+    /// Returns `true` if the chunk is inside of the area. Both corners are part of the area.
+    pub fn contains(&self, chunk_x: i32, chunk_z: i32) -> bool {
+        (self.x1.min(self.x2)..=self.x1.max(self.x2)).contains(&chunk_x)
+            && (self.z1.min(self.z2)..=self.z1.max(self.z2)).contains(&chunk_z)
+    }
+}
+
+impl Display for Area {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{},{} -> {},{}]", self.x1, self.z1, self.x2, self.z2)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for Area {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        match RawArea::deserialize(deserializer)? {
+            RawArea::Text(value) => parse_area(&value).map_err(serde::de::Error::custom),
+            RawArea::Points { x1, z1, x2, z2 } => Ok(Area::new(x1, z1, x2, z2)),
+        }
+    }
+}
+
+#[cfg(feature = "schema")]
+impl schemars::JsonSchema for Area {
+    fn schema_name() -> String {
+        String::from("Area")
+    }
+
+    fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        RawArea::json_schema(gen)
+    }
+}
+
+/// The formats an area can be given in inside of the config file.
+#[derive(serde::Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(untagged)]
+enum RawArea {
+    /// `x1,z1;x2,z2` or `x,z` for a single chunk
+    Text(String),
+    Points {
+        x1: i32,
+        z1: i32,
+        x2: i32,
+        z2: i32,
+    },
+}
+
 fn parse_area(value: &str) -> Result<Area, String> {
+    if !value.contains(';') {
+        if let Some((x, z)) = parse_point(value) {
+            return Ok(Area::chunk(x, z));
+        }
+    }
     let Some(((x1, z1), (x2, z2))) = value
         .split_once(';')
         .and_then(|(pos1, pos2)| parse_point(pos1).zip(parse_point(pos2)))
     else {
-        return Err(String::from("Can not parse provided area. Area must be give as followed: \"<x1>,<z1>;<x2>,<z2>\". Make sure that you have no spaces and all numbers are valid integers."));
+        return Err(String::from("Can not parse provided area. Area must be give as followed: \"<x1>,<z1>;<x2>,<z2>\" or \"<x>,<z>\" for a single chunk. Make sure that you have no spaces and all numbers are valid integers."));
     };
-    Ok(Area { x1, z1, x2, z2 })
+    Ok(Area::new(x1, z1, x2, z2))
+}
+
+/// Parse a block entity id. Ids without a namespace are in the `minecraft` namespace.
+fn parse_container_id(value: &str) -> Result<String, String> {
+    if value.is_empty() {
+        return Err(String::from("Container id must not be empty"));
+    }
+    if value.contains(':') {
+        Ok(value.to_string())
+    } else {
+        Ok(format!("minecraft:{value}"))
+    }
+}
+
+/// Parse a duration given as a number followed by a unit.
+/// Supported units are `s`, `m`, `h`, `d` and `w`.
+fn parse_duration(value: &str) -> Result<Duration, String> {
+    let error = || {
+        format!("Can not parse duration \"{value}\". Duration must be given as a number followed by s, m, h, d or w e.g. \"24h\".")
+    };
+    let unit_start = value
+        .find(|c: char| !c.is_ascii_digit())
+        .ok_or_else(error)?;
+    let (amount, unit) = value.split_at(unit_start);
+    let amount: u64 = amount.parse().map_err(|_| error())?;
+    let seconds = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        "w" => 7 * 24 * 60 * 60,
+        _ => return Err(error()),
+    };
+    amount
+        .checked_mul(seconds)
+        .map(Duration::from_secs)
+        .ok_or_else(error)
+}
+
+fn parse_location(value: &str) -> Result<(i32, i32), String> {
+    parse_point(value).ok_or_else(|| {
+        format!(
+            "Can not parse location \"{value}\". Location must be given as followed: \"<x>,<z>\"."
+        )
+    })
 }
 
 fn parse_point(value: &str) -> Option<(i32, i32)> {
@@ -88,10 +293,77 @@ mod tests {
     }
 
     #[test_case("1,2;3,4" => Ok(Area { x1: 1, z1: 2, x2: 3, z2: 4 }); "Success")]
-    #[test_case("1,2;3,4,5" => Err(String::from("Can not parse provided area. Area must be give as followed: \"<x1>,<z1>;<x2>,<z2>\". Make sure that you have no spaces and all numbers are valid integers.")); "Too many values")]
-    #[test_case("1,2" => Err(String::from("Can not parse provided area. Area must be give as followed: \"<x1>,<z1>;<x2>,<z2>\". Make sure that you have no spaces and all numbers are valid integers.")); "Too few values")]
-    #[test_case("a,2;3,4" => Err(String::from("Can not parse provided area. Area must be give as followed: \"<x1>,<z1>;<x2>,<z2>\". Make sure that you have no spaces and all numbers are valid integers.")); "First value of first point is not a number")]
+    #[test_case("1,2;3,4,5" => Err(String::from("Can not parse provided area. Area must be give as followed: \"<x1>,<z1>;<x2>,<z2>\" or \"<x>,<z>\" for a single chunk. Make sure that you have no spaces and all numbers are valid integers.")); "Too many values")]
+    #[test_case("1,2" => Ok(Area { x1: 1, z1: 2, x2: 1, z2: 2 }); "Single chunk")]
+    #[test_case("1" => Err(String::from("Can not parse provided area. Area must be give as followed: \"<x1>,<z1>;<x2>,<z2>\" or \"<x>,<z>\" for a single chunk. Make sure that you have no spaces and all numbers are valid integers.")); "Too few values")]
+    #[test_case("a,2;3,4" => Err(String::from("Can not parse provided area. Area must be give as followed: \"<x1>,<z1>;<x2>,<z2>\" or \"<x>,<z>\" for a single chunk. Make sure that you have no spaces and all numbers are valid integers.")); "First value of first point is not a number")]
+    #[test_case("3,4;1,2" => Ok(Area { x1: 1, z1: 2, x2: 3, z2: 4 }); "Reversed corners")]
+    #[test_case("1,4;3,2" => Ok(Area { x1: 1, z1: 2, x2: 3, z2: 4 }); "Mixed corners")]
+    #[test_case("1,2;1,4" => Ok(Area { x1: 1, z1: 2, x2: 1, z2: 4 }); "Zero width")]
+    #[test_case("1,2;3,2" => Ok(Area { x1: 1, z1: 2, x2: 3, z2: 2 }); "Zero height")]
     fn test_parse_area(v: &str) -> Result<Area, String> {
         parse_area(v)
     }
+
+    #[test_case("30s" => Ok(Duration::from_secs(30)); "Seconds")]
+    #[test_case("15m" => Ok(Duration::from_secs(15 * 60)); "Minutes")]
+    #[test_case("24h" => Ok(Duration::from_secs(24 * 60 * 60)); "Hours")]
+    #[test_case("7d" => Ok(Duration::from_secs(7 * 24 * 60 * 60)); "Days")]
+    #[test_case("2w" => Ok(Duration::from_secs(14 * 24 * 60 * 60)); "Weeks")]
+    #[test_case("24" => Err(String::from("Can not parse duration \"24\". Duration must be given as a number followed by s, m, h, d or w e.g. \"24h\".")); "Missing unit")]
+    #[test_case("h" => Err(String::from("Can not parse duration \"h\". Duration must be given as a number followed by s, m, h, d or w e.g. \"24h\".")); "Missing number")]
+    #[test_case("3y" => Err(String::from("Can not parse duration \"3y\". Duration must be given as a number followed by s, m, h, d or w e.g. \"24h\".")); "Unknown unit")]
+    #[test_case("-1d" => Err(String::from("Can not parse duration \"-1d\". Duration must be given as a number followed by s, m, h, d or w e.g. \"24h\".")); "Negative")]
+    fn test_parse_duration(v: &str) -> Result<Duration, String> {
+        parse_duration(v)
+    }
+
+    #[test_case("-12,400" => Ok((-12, 400)); "Success")]
+    #[test_case("12" => Err(String::from("Can not parse location \"12\". Location must be given as followed: \"<x>,<z>\".")); "Missing z")]
+    fn test_parse_location(v: &str) -> Result<(i32, i32), String> {
+        parse_location(v)
+    }
+
+    #[test_case("shulker_box" => Ok(String::from("minecraft:shulker_box")); "Without namespace")]
+    #[test_case("minecraft:barrel" => Ok(String::from("minecraft:barrel")); "With namespace")]
+    #[test_case("" => Err(String::from("Container id must not be empty")); "Empty")]
+    fn test_parse_container_id(v: &str) -> Result<String, String> {
+        parse_container_id(v)
+    }
+
+    #[test_case(r#""1,2;3,4""# => Area { x1: 1, z1: 2, x2: 3, z2: 4 }; "Text")]
+    #[test_case(r#"{"x1": 1, "z1": 2, "x2": 3, "z2": 4}"# => Area { x1: 1, z1: 2, x2: 3, z2: 4 }; "Points")]
+    #[test_case(r#"{"x1": 3, "z1": 4, "x2": 1, "z2": 2}"# => Area { x1: 1, z1: 2, x2: 3, z2: 4 }; "Reversed points")]
+    fn test_deserialize_area(v: &str) -> Area {
+        serde_json::from_str(v).expect("Invalid area")
+    }
+
+    #[test_case(Area::chunk(0, 0); "Single chunk")]
+    #[test_case(Area::chunk(-3, 7); "Negative single chunk")]
+    #[test_case(Area::new(1, 2, 1, 4); "Single row")]
+    fn test_area_round_trip(area: Area) {
+        let value = serde_json::to_value(&area).expect("Error serializing area");
+        let deserialized: Area = serde_json::from_value(value).expect("Invalid area");
+        assert_eq!(deserialized, area);
+    }
+
+    #[test_case(Area { x1: 1, z1: 2, x2: 3, z2: 4 } => "[1,2 -> 3,4]"; "Positive")]
+    #[test_case(Area { x1: -10, z1: -20, x2: 5, z2: -1 } => "[-10,-20 -> 5,-1]"; "Negative")]
+    fn test_display_area(area: Area) -> String {
+        area.to_string()
+    }
+
+    #[test_case(0, 0 => true; "Inside")]
+    #[test_case(-2, 3 => true; "Corner")]
+    #[test_case(-3, 0 => false; "Outside x")]
+    #[test_case(0, 4 => false; "Outside z")]
+    fn test_area_contains(x: i32, z: i32) -> bool {
+        Area {
+            x1: 2,
+            z1: 3,
+            x2: -2,
+            z2: -3,
+        }
+        .contains(x, z)
+    }
 }
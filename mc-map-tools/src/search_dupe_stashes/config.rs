@@ -1,21 +1,36 @@
 use std::collections::HashMap;
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+
+use super::args::Area;
 
 type Nbt = serde_json::value::Map<String, serde_json::Value>;
 
-#[derive(Debug, PartialEq, Deserialize)]
+#[derive(Debug, PartialEq, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct SearchDupeStashesConfig {
+    #[serde(default)]
     pub groups: HashMap<String, Group>,
+    /// Areas of chunks that are not scanned
+    #[serde(default)]
+    pub exclude: Vec<Area>,
+    /// Maximum stack sizes by item id. Overrides the vanilla stack sizes.
+    #[serde(default)]
+    pub max_stack_sizes: HashMap<String, i8>,
+    /// Item ids that are never counted and never reported
+    #[serde(default)]
+    pub exclude_items: Vec<String>,
 }
 
-#[derive(Debug, PartialEq, Deserialize)]
+#[derive(Debug, PartialEq, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Group {
     pub items: Vec<GroupEntry>,
     pub threshold: usize,
 }
 
-#[derive(Debug, PartialEq, Deserialize)]
+#[derive(Debug, PartialEq, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct GroupEntry {
     pub id: Option<Wildcard>,
     pub nbt: Option<Nbt>,
@@ -40,12 +55,77 @@ impl Default for SearchDupeStashesConfig {
     }
 }
 
+impl SearchDupeStashesConfig {
+    /// Merge another config into this one. Groups and stack sizes of `other` replace entries with the same name,
+    /// excluded areas and items are added.
+    pub fn merge(&mut self, other: Self) {
+        self.groups.extend(other.groups);
+        self.exclude.extend(other.exclude);
+        self.max_stack_sizes.extend(other.max_stack_sizes);
+        self.exclude_items.extend(other.exclude_items);
+    }
+
+    pub fn is_chunk_excluded(&self, chunk_x: i32, chunk_z: i32) -> bool {
+        self.exclude
+            .iter()
+            .any(|area| area.contains(chunk_x, chunk_z))
+    }
+
+    /// Returns `true` if items with this id are ignored.
+    pub fn is_item_excluded(&self, id: &str) -> bool {
+        self.exclude_items.iter().any(|excluded| excluded == id)
+    }
+
+    /// The maximum stack size of an item.
+    /// The `minecraft:max_stack_size` component of the item takes precedence over the configured
+    /// stack sizes, which take precedence over the vanilla stack sizes.
+    pub fn max_stack_size(&self, item: &mc_map_reader::data::item::Item) -> i8 {
+        let component = item
+            .components
+            .as_ref()
+            .and_then(|components| components.get("minecraft:max_stack_size"));
+        if let Some(mc_map_reader::nbt::Tag::Int(max_stack_size)) = component {
+            return i8::try_from(*max_stack_size).unwrap_or(i8::MAX);
+        }
+        self.max_stack_sizes
+            .get(&item.id)
+            .copied()
+            .unwrap_or_else(|| super::stack_size::vanilla_max_stack_size(&item.id))
+    }
+}
+
+#[cfg(feature = "schema")]
+impl schemars::JsonSchema for Wildcard {
+    fn schema_name() -> String {
+        String::from("Wildcard")
+    }
+
+    fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        String::json_schema(gen)
+    }
+}
+
 impl From<&str> for Wildcard {
     fn from(value: &str) -> Self {
         Self(wildmatch::WildMatch::new(value))
     }
 }
 
+impl std::fmt::Display for Wildcard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Serialize for Wildcard {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.0.to_string())
+    }
+}
+
 impl<'de> Deserialize<'de> for Wildcard {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -181,6 +261,7 @@ mod tests {
     fn test_wildcard() {
         let wildcard = Wildcard::from("fo*ar");
         assert_eq!(wildcard.0, wildmatch::WildMatch::new("fo*ar"));
+        assert_eq!(wildcard.to_string(), "fo*ar");
     }
 
     #[test_case(Some("foo*") => true; "Does match")]
@@ -196,6 +277,7 @@ mod tests {
             id: "foobar".to_string(),
             count: 1,
             tag: None,
+            components: None,
         };
         entry.matches_id(&item)
     }
@@ -205,27 +287,27 @@ mod tests {
             GroupEntry { id: Some(Wildcard::from("item")), nbt: None, multiplier: 1 }
         ],
         threshold: 1
-    }, McItem { id: String::from("item"), tag: None, count: 1 } => true; "Is Equals single")]
+    }, McItem { id: String::from("item"), tag: None, components: None, count: 1 } => true; "Is Equals single")]
     #[test_case(Group {
         items: vec![
             GroupEntry { id: Some(Wildcard::from("test")), nbt: None, multiplier: 1 },
             GroupEntry { id: Some(Wildcard::from("item")), nbt: None, multiplier: 1 }
         ],
         threshold: 1
-    }, McItem { id: String::from("item"), tag: None, count: 1 } => true; "Is Equals multiple")]
+    }, McItem { id: String::from("item"), tag: None, components: None, count: 1 } => true; "Is Equals multiple")]
     #[test_case(Group {
         items: vec![
             GroupEntry { id: Some(Wildcard::from("item2")), nbt: None, multiplier: 1 }
         ],
         threshold: 1
-    }, McItem { id: String::from("item"), tag: None, count: 1 } => false; "Is Not Equals single")]
+    }, McItem { id: String::from("item"), tag: None, components: None, count: 1 } => false; "Is Not Equals single")]
     #[test_case(Group {
         items: vec![
             GroupEntry { id: Some(Wildcard::from("test")), nbt: None, multiplier: 1 },
             GroupEntry { id: Some(Wildcard::from("item2")), nbt: None, multiplier: 1 }
         ],
         threshold: 1
-    }, McItem { id: String::from("item"), tag: None, count: 1 } => false; "Is not equals multiple")]
+    }, McItem { id: String::from("item"), tag: None, components: None, count: 1 } => false; "Is not equals multiple")]
     fn test_group_matches(group: Group, item: McItem) -> bool {
         group.matches(&item)
     }
@@ -253,6 +335,7 @@ mod tests {
             id: "foobar".to_string(),
             count: 1,
             tag: item_nbt,
+            components: None,
         };
         entry.matches_nbt(&item)
     }
@@ -277,6 +360,7 @@ mod tests {
             id: item_id.to_string(),
             count: 1,
             tag: item_nbt,
+            components: None,
         };
         entry.matches(&item)
     }
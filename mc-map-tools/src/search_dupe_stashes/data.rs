@@ -2,11 +2,16 @@ use async_std::sync::RwLock;
 use std::collections::VecDeque;
 use std::path::Path;
 use std::sync::Arc;
-use std::{collections::HashMap, fmt::Display};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Display,
+};
 
+use super::args::CoordinateSpace;
 use crate::file::region_inventories::RegionInventories;
 use crate::file::FileItemRead;
 use qutee::Point;
+use serde::{Deserialize, Serialize};
 
 pub struct RegionInventoryCache<'a> {
     regions: RwLock<VecDeque<RegionInventoryCacheItem>>,
@@ -25,6 +30,69 @@ pub struct FoundInventory<'a> {
     pub inventory_type: String,
     pub position: Position,
     pub items: HashMap<&'a str, FoundItem>,
+    pub anomalies: Vec<Anomaly>,
+    /// Every stack counted towards a group
+    pub stacks: Vec<FoundStack<'a>>,
+}
+
+/// A stack of items inside of a container that is counted towards a group.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FoundStack<'a> {
+    pub group: &'a str,
+    pub position: Position,
+    pub id: String,
+    pub count: i8,
+    pub custom_name: Option<String>,
+    pub enchantments: Vec<(String, i32)>,
+}
+
+/// An item that can not exist in vanilla Minecraft.
+/// Anomalies are reported regardless of the group thresholds.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Anomaly {
+    Slot(SlotAnomaly),
+    StackSize(StackSizeAnomaly),
+    BannerPatterns(BannerPatternsAnomaly),
+    OminousBottle(OminousBottleAnomaly),
+}
+
+/// An item stored in a slot the container does not have in vanilla Minecraft.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SlotAnomaly {
+    pub inventory_type: String,
+    pub position: Position,
+    pub item_id: String,
+    pub slot: i8,
+    pub slot_count: usize,
+}
+
+/// A stack with more items than the maximum stack size of the item.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StackSizeAnomaly {
+    pub inventory_type: String,
+    pub position: Position,
+    pub item_id: String,
+    pub count: i8,
+    pub max_stack_size: i8,
+}
+
+/// A banner or shield with more patterns than a loom can apply.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BannerPatternsAnomaly {
+    /// The container holding the banner. `None` for placed banners.
+    pub inventory_type: Option<String>,
+    pub position: Position,
+    pub item_id: String,
+    pub patterns: usize,
+}
+
+/// An ominous bottle with a higher amplifier than vanilla bottles can have.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OminousBottleAnomaly {
+    pub inventory_type: String,
+    pub position: Position,
+    pub item_id: String,
+    pub amplifier: i32,
 }
 
 #[derive(Debug)]
@@ -51,6 +119,63 @@ pub struct PotentialStashLocationsByGroup<'a> {
 
 pub struct PotentialStashLocations<'a>(pub Vec<PotentialStashLocationsByGroup<'a>>);
 
+/// All containers whose surrounding area exceeds the threshold of at least one group.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ScanResults(pub Vec<Finding>);
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Finding {
+    /// The name of the world the finding is in. Only set if more than one world is searched.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub world: Option<String>,
+    pub location: Location,
+    pub container: String,
+    pub groups: Vec<GroupCount>,
+    /// The stacks counted towards the groups. Only set with `--detail`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stacks: Vec<StackDetail>,
+}
+
+/// A stack of items that contributes to a finding.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct StackDetail {
+    pub location: Location,
+    pub group: String,
+    pub id: String,
+    pub count: i8,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub custom_name: Option<String>,
+    /// Enchantment levels by enchantment id
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub enchantments: BTreeMap<String, i32>,
+}
+
+/// Number of items of a group found around a container.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct GroupCount {
+    pub group: String,
+    pub count: u64,
+    pub threshold: usize,
+}
+
+/// Total number of items with the same id found in all searched containers.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct ItemCount {
+    pub id: String,
+    pub count: u64,
+}
+
+/// A position in the selected coordinate space.
+/// Chunk and region coordinates have no y component.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Location {
+    pub x: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub y: Option<i32>,
+    pub z: i32,
+}
+
 impl<'a> RegionInventoryCache<'a> {
     pub fn new(base_dir: &'a Path, cache_size: usize) -> Self {
         Self {
@@ -90,6 +215,44 @@ impl<'a> RegionInventoryCache<'a> {
     }
 }
 
+impl Position {
+    /// Convert the position into the given coordinate space.
+    pub fn location(&self, coords: CoordinateSpace) -> Location {
+        use mc_map_reader::coords::{block_to_chunk, block_to_region};
+        match coords {
+            CoordinateSpace::Block => Location {
+                x: self.x,
+                y: Some(self.y),
+                z: self.z,
+            },
+            CoordinateSpace::Chunk => Location {
+                x: block_to_chunk(self.x),
+                y: None,
+                z: block_to_chunk(self.z),
+            },
+            CoordinateSpace::Region => Location {
+                x: block_to_region(self.x),
+                y: None,
+                z: block_to_region(self.z),
+            },
+        }
+    }
+}
+
+impl FoundStack<'_> {
+    /// Convert the stack into its printed form using the given coordinate space.
+    pub fn into_detail(self, coords: CoordinateSpace) -> StackDetail {
+        StackDetail {
+            location: self.position.location(coords),
+            group: self.group.to_string(),
+            id: self.id,
+            count: self.count,
+            custom_name: self.custom_name,
+            enchantments: self.enchantments.into_iter().collect(),
+        }
+    }
+}
+
 impl From<Position> for Point<i32> {
     fn from(pos: Position) -> Self {
         (pos.x, pos.z).into()
@@ -107,6 +270,102 @@ impl qutee::AsPoint<i32> for &FoundInventory<'_> {
     }
 }
 
+impl qutee::AsPoint<i32> for &Finding {
+    fn as_point(&self) -> Point<i32> {
+        (self.location.x, self.location.z).into()
+    }
+}
+
+impl Display for SlotAnomaly {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Position { x, y, z } = self.position;
+        write!(
+            f,
+            "{x},{y},{z},{} in slot {} of {} with {} slots",
+            self.item_id, self.slot, self.inventory_type, self.slot_count
+        )
+    }
+}
+
+impl Display for StackSizeAnomaly {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Position { x, y, z } = self.position;
+        write!(
+            f,
+            "{x},{y},{z},{} with {} items in {} exceeds the maximum stack size of {}",
+            self.item_id, self.count, self.inventory_type, self.max_stack_size
+        )
+    }
+}
+
+impl Display for BannerPatternsAnomaly {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Position { x, y, z } = self.position;
+        write!(f, "{x},{y},{z},{}", self.item_id)?;
+        if let Some(inventory_type) = &self.inventory_type {
+            write!(f, " in {inventory_type}")?;
+        }
+        write!(
+            f,
+            " has {} patterns but a loom can only apply {}",
+            self.patterns,
+            mc_map_reader::data::banner::MAX_LOOM_PATTERNS
+        )
+    }
+}
+
+impl Display for OminousBottleAnomaly {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Position { x, y, z } = self.position;
+        write!(
+            f,
+            "{x},{y},{z},{} in {} has amplifier {} but vanilla ominous bottles have at most {}",
+            self.item_id,
+            self.inventory_type,
+            self.amplifier,
+            mc_map_reader::data::item::MAX_OMINOUS_BOTTLE_AMPLIFIER
+        )
+    }
+}
+
+impl Display for Anomaly {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Slot(anomaly) => anomaly.fmt(f),
+            Self::StackSize(anomaly) => anomaly.fmt(f),
+            Self::BannerPatterns(anomaly) => anomaly.fmt(f),
+            Self::OminousBottle(anomaly) => anomaly.fmt(f),
+        }
+    }
+}
+
+impl Display for StackDetail {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{},{},{}", self.location, self.id, self.count)?;
+        if let Some(custom_name) = &self.custom_name {
+            write!(f, " named \"{custom_name}\"")?;
+        }
+        if !self.enchantments.is_empty() {
+            let enchantments = self
+                .enchantments
+                .iter()
+                .map(|(id, level)| format!("{id} {level}"))
+                .collect::<Vec<_>>();
+            write!(f, " enchanted with {}", enchantments.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for Location {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.y {
+            Some(y) => write!(f, "{},{y},{}", self.x, self.z),
+            None => write!(f, "{},{}", self.x, self.z),
+        }
+    }
+}
+
 impl Display for PotentialStashLocations<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         for group in &self.0 {
@@ -126,9 +385,13 @@ impl Display for PotentialStashLocations<'_> {
 #[cfg(test)]
 mod tests {
     use super::{
-        Position, PotentialStashLocation, PotentialStashLocations, PotentialStashLocationsByGroup,
+        BannerPatternsAnomaly, Finding, GroupCount, Location, Position, PotentialStashLocation,
+        PotentialStashLocations, PotentialStashLocationsByGroup, ScanResults, SlotAnomaly,
+        StackDetail,
     };
+    use crate::search_dupe_stashes::args::CoordinateSpace;
     use qutee::Point;
+    use std::collections::BTreeMap;
     use test_case::test_case;
 
     #[test_case(Position { x: 0, y: 0, z: 0 } => Point::from((0, 0)) )]
@@ -180,6 +443,110 @@ Group: key2
         )
     }
 
+    #[test_case(CoordinateSpace::Block => "600,64,-20"; "Block")]
+    #[test_case(CoordinateSpace::Chunk => "37,-2"; "Chunk")]
+    #[test_case(CoordinateSpace::Region => "1,-1"; "Region")]
+    fn test_format_position(coords: CoordinateSpace) -> String {
+        Position {
+            x: 600,
+            y: 64,
+            z: -20,
+        }
+        .location(coords)
+        .to_string()
+    }
+
+    #[test]
+    fn test_scan_results_json_round_trip() {
+        let results = ScanResults(vec![
+            Finding {
+                world: None,
+                location: Position { x: 1, y: 2, z: 3 }.location(CoordinateSpace::Block),
+                container: "minecraft:chest".to_string(),
+                groups: vec![GroupCount {
+                    group: "diamonds".to_string(),
+                    count: 1000,
+                    threshold: 500,
+                }],
+                stacks: vec![StackDetail {
+                    location: Position { x: 1, y: 2, z: 3 }.location(CoordinateSpace::Block),
+                    group: "diamonds".to_string(),
+                    id: "minecraft:diamond_block".to_string(),
+                    count: 64,
+                    custom_name: None,
+                    enchantments: BTreeMap::new(),
+                }],
+            },
+            Finding {
+                world: None,
+                location: Position {
+                    x: 600,
+                    y: 64,
+                    z: -20,
+                }
+                .location(CoordinateSpace::Chunk),
+                container: "minecraft:barrel".to_string(),
+                groups: vec![
+                    GroupCount {
+                        group: "diamonds".to_string(),
+                        count: 600,
+                        threshold: 500,
+                    },
+                    GroupCount {
+                        group: "shulker boxes".to_string(),
+                        count: 40,
+                        threshold: 27,
+                    },
+                ],
+                stacks: Vec::new(),
+            },
+        ]);
+        let json = serde_json::to_string(&results).expect("Error serializing scan results");
+        assert_eq!(
+            serde_json::from_str::<ScanResults>(&json).expect("Error deserializing scan results"),
+            results
+        );
+    }
+
+    #[test_case(CoordinateSpace::Block => r#"{"x":600,"y":64,"z":-20}"#; "Block")]
+    #[test_case(CoordinateSpace::Chunk => r#"{"x":37,"z":-2}"#; "Chunk")]
+    fn test_serialize_location(coords: CoordinateSpace) -> String {
+        let location: Location = Position {
+            x: 600,
+            y: 64,
+            z: -20,
+        }
+        .location(coords);
+        serde_json::to_string(&location).expect("Error serializing location")
+    }
+
+    #[test]
+    fn test_display_slot_anomaly() {
+        let anomaly = SlotAnomaly {
+            inventory_type: "minecraft:chest".to_string(),
+            position: Position { x: 1, y: 2, z: 3 },
+            item_id: "minecraft:diamond".to_string(),
+            slot: 40,
+            slot_count: 27,
+        };
+        assert_eq!(
+            anomaly.to_string(),
+            "1,2,3,minecraft:diamond in slot 40 of minecraft:chest with 27 slots"
+        )
+    }
+
+    #[test_case(Some("minecraft:chest") => "1,2,3,minecraft:white_banner in minecraft:chest has 7 patterns but a loom can only apply 6"; "In container")]
+    #[test_case(None => "1,2,3,minecraft:white_banner has 7 patterns but a loom can only apply 6"; "Placed")]
+    fn test_display_banner_patterns_anomaly(inventory_type: Option<&str>) -> String {
+        BannerPatternsAnomaly {
+            inventory_type: inventory_type.map(str::to_string),
+            position: Position { x: 1, y: 2, z: 3 },
+            item_id: "minecraft:white_banner".to_string(),
+            patterns: 7,
+        }
+        .to_string()
+    }
+
     mod cache {
         use super::super::RegionInventoryCache;
         use std::path::PathBuf;
@@ -0,0 +1,169 @@
+use std::{
+    collections::{BTreeMap, HashSet},
+    io::Write,
+};
+
+use super::config::{Group, GroupEntry, SearchDupeStashesConfig};
+
+/// Print every configured group with its threshold and entries, sorted by name.
+/// Mistakes in the config are printed below the group they belong to.
+pub fn write_groups_text(
+    config: &SearchDupeStashesConfig,
+    writer: &mut dyn Write,
+) -> std::io::Result<()> {
+    let groups = config.groups.iter().collect::<BTreeMap<_, _>>();
+    groups.iter().try_for_each(|(name, group)| {
+        writeln!(writer, "{name}: threshold {}", group.threshold)?;
+        group
+            .items
+            .iter()
+            .try_for_each(|entry| writeln!(writer, "  {}", entry_text(entry)))?;
+        group_problems(group)
+            .iter()
+            .try_for_each(|problem| writeln!(writer, "  warning: {problem}"))
+    })
+}
+
+/// Print every configured group as a JSON object keyed by the group name.
+pub fn write_groups_json(
+    config: &SearchDupeStashesConfig,
+    writer: &mut dyn Write,
+) -> serde_json::Result<()> {
+    let groups = config.groups.iter().collect::<BTreeMap<_, _>>();
+    serde_json::to_writer_pretty(writer, &groups)
+}
+
+/// Log every mistake found in the configured groups.
+pub fn log_group_problems(config: &SearchDupeStashesConfig) {
+    config.groups.iter().for_each(|(name, group)| {
+        group_problems(group)
+            .iter()
+            .for_each(|problem| log::warn!("Group {name}: {problem}"))
+    });
+}
+
+/// `<id> x<multiplier>` followed by the required NBT data if there is any.
+/// Entries without an id match every item and are printed as `*`.
+fn entry_text(entry: &GroupEntry) -> String {
+    let id = entry
+        .id
+        .as_ref()
+        .map(|id| id.to_string())
+        .unwrap_or_else(|| "*".to_string());
+    match &entry.nbt {
+        Some(nbt) => format!(
+            "{id} x{} with nbt {}",
+            entry.multiplier,
+            serde_json::Value::Object(nbt.clone())
+        ),
+        None => format!("{id} x{}", entry.multiplier),
+    }
+}
+
+/// Mistakes that make a group count more or less than intended.
+fn group_problems(group: &Group) -> Vec<String> {
+    let mut problems = Vec::new();
+    if group.items.is_empty() {
+        problems.push("the group has no items and is never reported".to_string());
+    }
+    if group.threshold == 0 {
+        problems.push("a threshold of 0 reports every container".to_string());
+    }
+    let mut seen = HashSet::new();
+    group.items.iter().for_each(|entry| {
+        let text = entry_text(entry);
+        let matches_everything =
+            entry.id.is_none() && entry.nbt.as_ref().map_or(true, |nbt| nbt.is_empty());
+        if matches_everything {
+            problems.push(format!("{text} matches every item"));
+        }
+        if entry.multiplier == 0 {
+            problems.push(format!("{text} is never counted"));
+        }
+        if !seen.insert(text.clone()) {
+            problems.push(format!("{text} is listed more than once"));
+        }
+    });
+    problems
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::super::config::SearchDupeStashesConfig;
+
+    fn config() -> SearchDupeStashesConfig {
+        serde_json::from_value(json!({
+            "groups": {
+                "netherite": {
+                    "items": [
+                        { "id": "minecraft:netherite_ingot" },
+                        { "id": "minecraft:netherite_block", "multiplier": 9 }
+                    ],
+                    "threshold": 32
+                },
+                "diamonds": {
+                    "items": [
+                        { "id": "minecraft:diamond*", "multiplier": 0 },
+                        { "nbt": {} },
+                        { "id": "minecraft:diamond*", "multiplier": 0 }
+                    ],
+                    "threshold": 0
+                },
+                "named": {
+                    "items": [{ "id": "minecraft:paper", "nbt": { "display": "x" } }],
+                    "threshold": 1
+                }
+            }
+        }))
+        .expect("Invalid config")
+    }
+
+    #[test]
+    fn test_write_groups_text() {
+        let mut output = Vec::new();
+        super::write_groups_text(&config(), &mut output).expect("Error writing groups");
+        assert_eq!(
+            String::from_utf8(output).expect("Output is valid UTF-8"),
+            "diamonds: threshold 0
+  minecraft:diamond* x0
+  * x1 with nbt {}
+  minecraft:diamond* x0
+  warning: a threshold of 0 reports every container
+  warning: minecraft:diamond* x0 is never counted
+  warning: * x1 with nbt {} matches every item
+  warning: minecraft:diamond* x0 is never counted
+  warning: minecraft:diamond* x0 is listed more than once
+named: threshold 1
+  minecraft:paper x1 with nbt {\"display\":\"x\"}
+netherite: threshold 32
+  minecraft:netherite_ingot x1
+  minecraft:netherite_block x9
+"
+        );
+    }
+
+    #[test]
+    fn test_write_groups_json() {
+        let mut output = Vec::new();
+        super::write_groups_json(&config(), &mut output).expect("Error writing groups");
+        let groups: serde_json::Value =
+            serde_json::from_slice(&output).expect("Output is valid JSON");
+        assert_eq!(
+            groups["netherite"],
+            json!({
+                "items": [
+                    { "id": "minecraft:netherite_ingot", "nbt": null, "multiplier": 1 },
+                    { "id": "minecraft:netherite_block", "nbt": null, "multiplier": 9 }
+                ],
+                "threshold": 32
+            })
+        );
+        assert_eq!(
+            groups.as_object().map(|groups| groups.len()),
+            Some(3),
+            "Every group is written"
+        );
+    }
+}
@@ -0,0 +1,195 @@
+use std::{collections::BTreeMap, io::Write, path::Path, sync::Mutex};
+
+use mc_map_reader::RegionLoadError;
+
+use super::Error;
+
+/// Number of paths printed for every kind of error.
+const SAMPLE_COUNT: usize = 3;
+
+/// A region file that could not be searched.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RegionError {
+    pub region: String,
+    pub kind: &'static str,
+    pub message: String,
+}
+
+/// Collects the region files skipped because of errors during a scan.
+#[derive(Debug, Default)]
+pub struct ErrorReport {
+    errors: Mutex<Vec<RegionError>>,
+    /// Stop the scan at the first error instead of skipping the region file
+    abort_on_error: bool,
+}
+
+impl ErrorReport {
+    pub fn new(abort_on_error: bool) -> Self {
+        Self {
+            errors: Mutex::default(),
+            abort_on_error,
+        }
+    }
+
+    pub fn record(&self, region: &Path, error: &Error) {
+        log::error!(
+            "Error while reading region file {}: {error}",
+            region.display()
+        );
+        self.errors
+            .lock()
+            .expect("Error report lock poisoned")
+            .push(RegionError {
+                region: region.display().to_string(),
+                kind: error_kind(error),
+                message: error.to_string(),
+            });
+    }
+
+    /// The first error recorded during the scan.
+    pub fn first(&self) -> Option<RegionError> {
+        self.errors
+            .lock()
+            .expect("Error report lock poisoned")
+            .first()
+            .cloned()
+    }
+
+    /// The number of region files skipped because of errors.
+    pub fn len(&self) -> usize {
+        self.errors
+            .lock()
+            .expect("Error report lock poisoned")
+            .len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if an error was recorded and region files may not be skipped.
+    /// Region files that have not been read yet are not read anymore.
+    pub fn is_aborted(&self) -> bool {
+        self.abort_on_error && !self.is_empty()
+    }
+
+    /// The skipped region files grouped by the kind of error, sorted by kind.
+    pub fn by_kind(&self) -> BTreeMap<&'static str, Vec<String>> {
+        self.errors
+            .lock()
+            .expect("Error report lock poisoned")
+            .iter()
+            .fold(BTreeMap::new(), |mut kinds, error| {
+                kinds
+                    .entry(error.kind)
+                    .or_insert_with(Vec::new)
+                    .push(error.region.clone());
+                kinds
+            })
+    }
+}
+
+/// Print the number of skipped region files of every kind of error with a few of their paths.
+pub fn write_error_report(errors: &ErrorReport, writer: &mut dyn Write) -> std::io::Result<()> {
+    if errors.is_empty() {
+        return Ok(());
+    }
+    writeln!(
+        writer,
+        "Skipped {} region files because of errors:",
+        errors.len()
+    )?;
+    errors.by_kind().iter().try_for_each(|(kind, regions)| {
+        let samples = regions
+            .iter()
+            .take(SAMPLE_COUNT)
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join(", ");
+        let more = if regions.len() > SAMPLE_COUNT {
+            ", ..."
+        } else {
+            ""
+        };
+        writeln!(writer, "  {kind}: {} ({samples}{more})", regions.len())
+    })
+}
+
+/// Returns `true` if the scan was aborted because of an error. The first error is printed.
+pub fn abort_on_error(errors: &ErrorReport, writer: &mut dyn Write) -> bool {
+    if !errors.is_aborted() {
+        return false;
+    }
+    let Some(error) = errors.first() else {
+        return false;
+    };
+    writeln!(
+        writer,
+        "Aborted because of an error in region file {}: {}",
+        error.region, error.message
+    )
+    .expect("Error writing message");
+    true
+}
+
+fn error_kind(error: &Error) -> &'static str {
+    match error {
+        Error::Io(_) | Error::RegionLoadError(RegionLoadError::Io(_)) => "io",
+        Error::RegionLoadError(RegionLoadError::Decode(_)) => "decompression",
+        Error::RegionLoadError(RegionLoadError::NBT(_)) => "nbt",
+        Error::RegionLoadError(RegionLoadError::LoadChunkData(_)) => "chunk data",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use test_case::test_case;
+
+    use super::{super::Error, ErrorReport};
+
+    fn io_error() -> Error {
+        std::io::Error::new(std::io::ErrorKind::InvalidData, "broken").into()
+    }
+
+    #[test]
+    fn test_write_error_report() {
+        let errors = ErrorReport::default();
+        for region in ["r.0.0.mca", "r.1.0.mca", "r.2.0.mca", "r.3.0.mca"] {
+            errors.record(Path::new(region), &io_error());
+        }
+        errors.record(
+            Path::new("r.0.1.mca"),
+            &Error::RegionLoadError(mc_map_reader::nbt::Error::InvalidValue.into()),
+        );
+        let mut output = Vec::new();
+        super::write_error_report(&errors, &mut output).expect("Error writing error report");
+        assert_eq!(
+            String::from_utf8(output).expect("Output is valid UTF-8"),
+            "Skipped 5 region files because of errors:\n  io: 4 (r.0.0.mca, r.1.0.mca, r.2.0.mca, ...)\n  nbt: 1 (r.0.1.mca)\n"
+        );
+    }
+
+    #[test_case(false => false; "Skip region files")]
+    #[test_case(true => true; "Abort on error")]
+    fn test_is_aborted(abort_on_error: bool) -> bool {
+        let errors = ErrorReport::new(abort_on_error);
+        assert!(!errors.is_aborted());
+        errors.record(Path::new("r.0.0.mca"), &io_error());
+        let mut output = Vec::new();
+        assert_eq!(
+            super::abort_on_error(&errors, &mut output),
+            errors.is_aborted()
+        );
+        errors.is_aborted()
+    }
+
+    #[test]
+    fn test_write_empty_error_report() {
+        let mut output = Vec::new();
+        super::write_error_report(&ErrorReport::default(), &mut output)
+            .expect("Error writing error report");
+        assert!(output.is_empty());
+    }
+}
@@ -0,0 +1,125 @@
+use std::{fmt::Display, path::Path};
+
+use mc_map_reader::data::file_format::level_dat::GameRules;
+
+/// The vanilla default of `maxEntityCramming`
+const DEFAULT_MAX_ENTITY_CRAMMING: i32 = 24;
+/// Values of `maxEntityCramming` above this allow mob farms far larger than in vanilla
+const HIGH_MAX_ENTITY_CRAMMING: i32 = DEFAULT_MAX_ENTITY_CRAMMING * 4;
+
+/// A game rule set to a value that changes how many items exist in a world.
+#[derive(Debug, PartialEq)]
+pub struct GameRuleNote {
+    pub rule: &'static str,
+    pub value: String,
+    pub reason: &'static str,
+}
+
+impl Display for GameRuleNote {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Note: game rule {} is {}: {}",
+            self.rule, self.value, self.reason
+        )
+    }
+}
+
+/// Check the game rules that allow players to collect more items than in vanilla.
+pub fn check_game_rules(game_rules: GameRules) -> Vec<GameRuleNote> {
+    let mut notes = Vec::new();
+    if game_rules.get_bool("keepInventory") == Some(true) {
+        notes.push(GameRuleNote {
+            rule: "keepInventory",
+            value: "true".to_string(),
+            reason: "players keep their items when they die",
+        });
+    }
+    if game_rules.get_bool("doEntityDrops") == Some(false) {
+        notes.push(GameRuleNote {
+            rule: "doEntityDrops",
+            value: "false".to_string(),
+            reason: "minecarts, boats and item frames do not drop their items when destroyed",
+        });
+    }
+    if let Some(max_entity_cramming) = game_rules.get_int("maxEntityCramming") {
+        if max_entity_cramming > HIGH_MAX_ENTITY_CRAMMING || max_entity_cramming <= 0 {
+            notes.push(GameRuleNote {
+                rule: "maxEntityCramming",
+                value: max_entity_cramming.to_string(),
+                reason: "mob farms can hold far more mobs than in vanilla",
+            });
+        }
+    }
+    notes
+}
+
+/// Read the level.dat of a save and check its game rules.
+/// Saves without a readable level.dat are skipped.
+pub fn check_save(save_directory: &Path) -> Vec<GameRuleNote> {
+    let level_dat = std::fs::read(save_directory.join("level.dat"))
+        .map_err(|err| err.to_string())
+        .and_then(|data| mc_map_reader::parse_level_dat(&data).map_err(|err| err.to_string()));
+    match level_dat {
+        Ok(level_dat) => check_game_rules(level_dat.game_rules()),
+        Err(err) => {
+            log::warn!(
+                "Could not check game rules of {}: {err}",
+                save_directory.display()
+            );
+            Vec::new()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use test_case::test_case;
+
+    use super::GameRuleNote;
+
+    #[test_case(&[("keepInventory", "true")] => vec![GameRuleNote {
+        rule: "keepInventory",
+        value: "true".to_string(),
+        reason: "players keep their items when they die",
+    }]; "Keep inventory")]
+    #[test_case(&[("keepInventory", "false"), ("doEntityDrops", "true"), ("maxEntityCramming", "24")] => Vec::new(); "Vanilla")]
+    #[test_case(&[] => Vec::new(); "Not set")]
+    #[test_case(&[("doEntityDrops", "false")] => vec![GameRuleNote {
+        rule: "doEntityDrops",
+        value: "false".to_string(),
+        reason: "minecarts, boats and item frames do not drop their items when destroyed",
+    }]; "No entity drops")]
+    #[test_case(&[("maxEntityCramming", "1000")] => vec![GameRuleNote {
+        rule: "maxEntityCramming",
+        value: "1000".to_string(),
+        reason: "mob farms can hold far more mobs than in vanilla",
+    }]; "High entity cramming")]
+    #[test_case(&[("maxEntityCramming", "0")] => vec![GameRuleNote {
+        rule: "maxEntityCramming",
+        value: "0".to_string(),
+        reason: "mob farms can hold far more mobs than in vanilla",
+    }]; "Entity cramming disabled")]
+    fn test_check_game_rules(game_rules: &[(&str, &str)]) -> Vec<GameRuleNote> {
+        let game_rules = game_rules
+            .iter()
+            .map(|(rule, value)| (rule.to_string(), value.to_string()))
+            .collect::<HashMap<_, _>>();
+        super::check_game_rules((&game_rules).into())
+    }
+
+    #[test]
+    fn test_note_display() {
+        let note = GameRuleNote {
+            rule: "keepInventory",
+            value: "true".to_string(),
+            reason: "players keep their items when they die",
+        };
+        assert_eq!(
+            note.to_string(),
+            "Note: game rule keepInventory is true: players keep their items when they die"
+        );
+    }
+}
@@ -2,6 +2,13 @@ pub mod args;
 pub mod config;
 mod data;
 mod detection_method;
+mod dump_groups;
+mod error_report;
+mod game_rules;
+mod profile;
+mod stack_size;
+mod svg;
+mod version_check;
 
 use async_std::fs::OpenOptions;
 use data::*;
@@ -9,15 +16,23 @@ use futures::AsyncWriteExt;
 use qutee::{Boundary, ConstCap};
 use std::hash::{Hash, Hasher};
 use std::io::Write;
+use std::ops::RangeInclusive;
 use std::sync::Arc;
-use std::{collections::HashMap, path::Path};
+use std::time::SystemTime;
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::{Path, PathBuf},
+};
 
 use mc_map_reader::{
+    coords::{self, BlockPos},
     data::{
-        block_entity::{BlockEntity, BlockEntityType, InventoryBlock, ShulkerBox},
+        banner::BannerDesign,
+        block_entity::{BlockEntity, Container, ShulkerBox},
         chunk::ChunkData,
-        item::Item,
+        item::{Item, MAX_OMINOUS_BOTTLE_AMPLIFIER},
     },
+    files::RegionFile,
     RegionLoadError,
 };
 
@@ -29,8 +44,6 @@ use crate::{config::Config, read_file};
 
 use self::config::SearchDupeStashesConfig;
 
-const BLOCKS_IN_CHUNK: i32 = 16;
-const CHUNKS_IN_REGION_FILE: i32 = 32;
 type QuadTree<'a> = qutee::QuadTree<i32, &'a Inventory, ConstCap<32>>;
 
 #[derive(Debug, thiserror::Error)]
@@ -44,52 +57,343 @@ enum Error {
 pub async fn main(
     world_dir: &Path,
     data: args::SearchDupeStashes,
-    config: Config,
+    mut config: Config,
     writer: &mut dyn Write,
 ) {
-    let detection_method = Box::new(detection_method::Absolute::new(
-        &config.search_dupe_stashes.groups,
-    ));
-    let region_files = if let Some(area) = data.area {
-        mc_map_reader::files::get_regions_in_area(
-            world_dir, None, area.x1, area.z1, area.x2, area.z2,
+    config
+        .search_dupe_stashes
+        .exclude_items
+        .extend(data.exclude_items.iter().cloned());
+    if data.dump_groups {
+        let config = &config.search_dupe_stashes;
+        match data.output_format {
+            args::OutputFormat::Text => {
+                dump_groups::write_groups_text(config, writer).expect("Error writing message")
+            }
+            args::OutputFormat::Json => {
+                dump_groups::log_group_problems(config);
+                dump_groups::write_groups_json(config, writer).expect("Error writing message");
+                writeln!(writer).expect("Error writing message");
+            }
+        }
+        return;
+    }
+    let worlds = std::iter::once((world_dir, data.region_dir.as_deref()))
+        .chain(
+            data.worlds
+                .iter()
+                .map(|world_dir| (world_dir.as_path(), None)),
         )
-    } else {
-        mc_map_reader::files::get_regions(world_dir, None).expect("Could not read region directory")
-    };
-    log::debug!(
-        "Found {} region files {region_files:#?}",
-        region_files.len()
-    );
+        .map(|(world_dir, region_dir)| {
+            let region_files = find_region_files(world_dir, region_dir, data.area.as_ref());
+            let region_files = match data.since {
+                Some(since) => modified_since(
+                    region_files,
+                    SystemTime::now()
+                        .checked_sub(since)
+                        .unwrap_or(SystemTime::UNIX_EPOCH),
+                ),
+                None => region_files,
+            };
+            log::debug!(
+                "Found {} region files in {} {region_files:#?}",
+                region_files.len(),
+                world_dir.display()
+            );
+            (world_name(world_dir), region_files)
+        })
+        .collect::<Vec<_>>();
     let config = &config.search_dupe_stashes;
 
+    if data.check_gamerules {
+        let tag_worlds = !data.worlds.is_empty();
+        std::iter::once(world_dir)
+            .chain(data.worlds.iter().map(PathBuf::as_path))
+            .for_each(|world_dir| {
+                let world = tag_worlds
+                    .then(|| format!("{},", world_name(world_dir)))
+                    .unwrap_or_default();
+                game_rules::check_save(world_dir).iter().for_each(|note| {
+                    log::warn!("{world}{note}");
+                    if data.output_format == args::OutputFormat::Text {
+                        writeln!(writer, "{world}{note}").expect("Error writing message");
+                    }
+                })
+            });
+    }
+
+    let area = data.area.as_ref();
+    let y_range = data.min_y.unwrap_or(i32::MIN)..=data.max_y.unwrap_or(i32::MAX);
+    let y_range = &y_range;
+
+    let errors = error_report::ErrorReport::new(data.abort_on_error);
+
+    if data.version_check {
+        let tag_worlds = !data.worlds.is_empty();
+        let world_dirs = std::iter::once(world_dir).chain(data.worlds.iter().map(PathBuf::as_path));
+        let mut too_new_items = Vec::new();
+        for (world_dir, (world, region_files)) in world_dirs.zip(worlds) {
+            let Some(world_data_version) = version_check::world_data_version(world_dir) else {
+                continue;
+            };
+            let items = find_too_new_items(
+                &region_files,
+                world_data_version,
+                config,
+                area,
+                y_range,
+                &data.containers,
+                &errors,
+            )
+            .await;
+            if error_report::abort_on_error(&errors, writer) {
+                return;
+            }
+            too_new_items.extend(items.into_iter().map(|item| version_check::TooNewItem {
+                world: tag_worlds.then(|| world.clone()),
+                ..item
+            }));
+        }
+        match data.output_format {
+            args::OutputFormat::Text => too_new_items.iter().for_each(|item| {
+                writeln!(writer, "{item}").expect("Error writing message");
+            }),
+            args::OutputFormat::Json => {
+                serde_json::to_writer_pretty(&mut *writer, &too_new_items)
+                    .expect("Error writing message");
+                writeln!(writer).expect("Error writing message");
+            }
+        }
+        write_errors(&errors, data.output_format, writer);
+        return;
+    }
+
+    if data.dry_run || data.report {
+        let region_files = worlds
+            .into_iter()
+            .flat_map(|(_, region_files)| region_files)
+            .collect::<Vec<_>>();
+        if data.dry_run {
+            dry_run(&region_files, config, area, &errors, writer);
+            if error_report::abort_on_error(&errors, writer) {
+                return;
+            }
+            write_errors(&errors, data.output_format, writer);
+        } else {
+            let item_counts = count_items(
+                &region_files,
+                config,
+                area,
+                y_range,
+                &data.containers,
+                &errors,
+            )
+            .await;
+            if error_report::abort_on_error(&errors, writer) {
+                return;
+            }
+            write_item_counts(&item_counts, data.output_format, writer);
+            write_errors(&errors, data.output_format, writer);
+        }
+        return;
+    }
+
+    let tag_worlds = !data.worlds.is_empty();
+    let profile = data.profile.map(|_| profile::Profile::default());
+    let mut findings = Vec::new();
+    for (world, region_files) in worlds {
+        let world_findings = scan_world(
+            region_files,
+            &data,
+            config,
+            y_range,
+            profile.as_ref(),
+            &errors,
+            writer,
+        )
+        .await;
+        if error_report::abort_on_error(&errors, writer) {
+            return;
+        }
+        if tag_worlds {
+            findings.extend(tag_findings_with_world(world_findings, &world));
+        } else {
+            findings.extend(world_findings);
+        }
+    }
+
+    if let Some(svg_path) = &data.svg {
+        let chunks = findings
+            .iter()
+            .fold(BTreeMap::new(), |mut chunks, (chunk, finding)| {
+                *chunks.entry(*chunk).or_insert(0) +=
+                    finding.groups.iter().map(|group| group.count).sum::<u64>();
+                chunks
+            });
+        let written = std::fs::File::create(svg_path).and_then(|file| {
+            let mut file = std::io::BufWriter::new(file);
+            svg::write_svg(&chunks, data.area.as_ref(), &mut file)?;
+            file.flush()
+        });
+        if let Err(err) = written {
+            log::error!("Could not write svg file \"{}\": {err}", svg_path.display());
+        }
+    }
+
+    let results = ScanResults(findings.into_iter().map(|(_, finding)| finding).collect());
+    let findings = match data.near {
+        Some(location) => findings_near(&results, location, data.near_radius),
+        None => results.0.iter().collect(),
+    };
+
+    match data.output_format {
+        args::OutputFormat::Text => write_findings_text(&findings, writer),
+        args::OutputFormat::Json => {
+            serde_json::to_writer_pretty(&mut *writer, &findings).expect("Error writing message");
+            writeln!(writer).expect("Error writing message");
+        }
+    }
+
+    if let (Some(profile), Some(count)) = (profile, data.profile) {
+        let slowest = profile.slowest(count);
+        slowest
+            .iter()
+            .for_each(|region| log::info!("Loaded region file {region}"));
+        if data.output_format == args::OutputFormat::Text {
+            profile::write_profile(&slowest, writer).expect("Error writing message");
+        }
+    }
+    write_errors(&errors, data.output_format, writer);
+}
+
+/// Print a summary of the region files skipped because of errors.
+/// With json output the errors are only logged.
+fn write_errors(
+    errors: &error_report::ErrorReport,
+    output_format: args::OutputFormat,
+    writer: &mut dyn Write,
+) {
+    if output_format == args::OutputFormat::Text {
+        error_report::write_error_report(errors, writer).expect("Error writing message");
+    }
+}
+
+/// Print one line per group of every finding.
+/// Stacks collected with `--detail` are printed indented below the group they count towards.
+fn write_findings_text(findings: &[&Finding], writer: &mut dyn Write) {
+    findings.iter().for_each(|finding| {
+        let world = finding
+            .world
+            .as_ref()
+            .map(|world| format!("{world},"))
+            .unwrap_or_default();
+        finding.groups.iter().for_each(|group| {
+            writeln!(
+                writer,
+                "{world}{},{},{},{}",
+                finding.location, finding.container, group.group, group.count
+            )
+            .expect("Error writing message");
+            finding
+                .stacks
+                .iter()
+                .filter(|stack| stack.group == group.group)
+                .for_each(|stack| writeln!(writer, "  {stack}").expect("Error writing message"));
+        })
+    })
+}
+
+/// Search the region files of a single world for potential stashes.
+/// Returns every finding together with the chunk it is in.
+async fn scan_world(
+    region_files: Vec<RegionFile>,
+    data: &args::SearchDupeStashes,
+    config: &SearchDupeStashesConfig,
+    y_range: &RangeInclusive<i32>,
+    profile: Option<&profile::Profile>,
+    errors: &error_report::ErrorReport,
+    writer: &mut dyn Write,
+) -> Vec<((i32, i32), Finding)> {
+    let detection_method = Box::new(detection_method::Absolute::new(&config.groups));
+    let area = data.area.as_ref();
+
     let temp_dir = TmpDir::new().expect("Error creating tmp dir");
     let inventories_dir = temp_dir.as_ref().join("inventories");
 
     if let Err(e) = async_std::fs::create_dir(&inventories_dir).await {
         log::error!("Error creating tmp directory: {e}");
-        return;
+        return Vec::new();
     }
     let inventories_dir = inventories_dir.as_path();
     let regions_future = region_files.into_iter().map(|region| async move {
-        let inventories = search_inventories_in_region(region.as_path(), config).await;
-        let inventories = match inventories {
-            Ok(inventories) => inventories,
+        if errors.is_aborted() {
+            return None;
+        }
+        let inventories = search_inventories_in_region(
+            region.as_path(),
+            config,
+            area,
+            y_range,
+            &data.containers,
+            profile,
+        )
+        .await;
+        let mut inventories = match inventories {
+            Ok(inventories) => inventories.collect::<Vec<_>>(),
             Err(err) => {
-                log::error!("{err}");
-                return Err(err);
+                errors.record(region.as_path(), &err);
+                return None;
             }
         };
-        save_region_inventories(inventories_dir, region.x(), region.z(), inventories).await?;
-        Ok((region.x(), region.z()))
+        let anomalies = inventories
+            .iter()
+            .flat_map(|inventory| inventory.anomalies.iter().cloned())
+            .collect::<Vec<_>>();
+        let stacks = if data.detail {
+            inventories
+                .iter_mut()
+                .flat_map(|inventory| std::mem::take(&mut inventory.stacks))
+                .collect::<Vec<_>>()
+        } else {
+            Vec::new()
+        };
+        let saved = save_region_inventories(
+            inventories_dir,
+            region.x(),
+            region.z(),
+            inventories.into_iter(),
+        )
+        .await;
+        if let Err(err) = saved {
+            errors.record(region.as_path(), &Error::from(err));
+            return None;
+        }
+        Some((region.x(), region.z(), anomalies, stacks))
     });
     let results = futures::future::join_all(regions_future).await;
+    if errors.is_aborted() {
+        return Vec::new();
+    }
 
-    let regions = results.into_iter().filter_map(|e| match e {
-        Ok((x, z)) => Some((x, z)),
-        Err(e) => {
-            log::error!("Error while reading region file {}", e);
-            None
+    let mut anomalies = Vec::new();
+    // Stacks sorted by their x coordinate to find the stacks around a finding
+    let mut stacks_by_x = BTreeMap::<i32, Vec<FoundStack>>::new();
+    let regions = results
+        .into_iter()
+        .flatten()
+        .map(|(x, z, region_anomalies, region_stacks)| {
+            anomalies.extend(region_anomalies);
+            region_stacks
+                .into_iter()
+                .for_each(|stack| stacks_by_x.entry(stack.position.x).or_default().push(stack));
+            (x, z)
+        })
+        .collect::<Vec<_>>();
+
+    anomalies.iter().for_each(|anomaly| {
+        log::warn!("Found illegal item: {anomaly}");
+        if data.output_format == args::OutputFormat::Text {
+            writeln!(writer, "{anomaly}").expect("Error writing message");
         }
     });
 
@@ -102,13 +406,14 @@ pub async fn main(
     let detection_method_ref = detection_method.as_ref();
     let group_hash_lookup_table_ref = &group_hash_lookup_table;
     let region_cache_ref = &region_cache;
-    let potential_stash_locations = regions.map(|(x, z)| async move {
+    let potential_stash_locations = regions.into_iter().map(|(x, z)| async move {
         let top = z - 1;
         let bottom = z + 1;
         let left = x - 1;
         let right = x + 1;
+        // The 3x3 neighbourhood of the region, index 4 is the region itself
         let regions =
-            (left..right).flat_map(|x| (top..bottom).map(move |z| region_cache_ref.get(x, z)));
+            (left..=right).flat_map(|x| (top..=bottom).map(move |z| region_cache_ref.get(x, z)));
         let regions = futures::future::join_all(regions).await;
 
         let Some(Ok(center_region)) = regions.get(4) else {
@@ -154,17 +459,35 @@ pub async fn main(
 
     let potential_stash_locations = futures::future::join_all(potential_stash_locations).await;
 
-    potential_stash_locations
+    let findings = potential_stash_locations
         .into_iter()
-        .filter(|location| location.is_empty())
         .flatten()
-        .for_each(|(Position { x, y, z }, sl)| {
-            sl.iter().for_each(|(item, count)| {
-                writer
-                    .write_all(format!("{x},{y},{z},{item},{count}").as_bytes())
-                    .expect("Error writing message");
+        .filter_map(|(position, id, groups)| {
+            let chunk = position.location(args::CoordinateSpace::Chunk);
+            let stacks = stacks_near(&stacks_by_x, &position, data.radius);
+            into_finding(
+                position,
+                id,
+                groups,
+                data.coords,
+                &group_hash_lookup_table,
+                config,
+            )
+            .map(|finding| {
+                let stacks = stacks
+                    .into_iter()
+                    .filter(|stack| {
+                        finding
+                            .groups
+                            .iter()
+                            .any(|group| group.group == stack.group)
+                    })
+                    .map(|stack| stack.clone().into_detail(data.coords))
+                    .collect();
+                ((chunk.x, chunk.z), Finding { stacks, ..finding })
             })
-        });
+        })
+        .collect::<Vec<_>>();
 
     if let Err(err) = async_std::fs::remove_dir_all(temp_dir.as_ref()).await {
         log::error!(
@@ -172,21 +495,347 @@ pub async fn main(
             temp_dir.as_ref().display()
         );
     }
+    findings
+}
+
+/// All stacks in containers at most `radius` blocks away from `position` on both the x and the z axis.
+fn stacks_near<'a, 'b>(
+    stacks_by_x: &'a BTreeMap<i32, Vec<FoundStack<'b>>>,
+    position: &Position,
+    radius: u32,
+) -> Vec<&'a FoundStack<'b>> {
+    let radius = i32::try_from(radius).unwrap_or(i32::MAX);
+    stacks_by_x
+        .range(position.x.saturating_sub(radius)..=position.x.saturating_add(radius))
+        .flat_map(|(_, stacks)| stacks)
+        .filter(|stack| {
+            (i64::from(stack.position.z) - i64::from(position.z)).abs() <= i64::from(radius)
+        })
+        .collect()
+}
+
+/// The name of a world used to tag its findings. This is the name of the save directory.
+fn world_name(world_dir: &Path) -> String {
+    world_dir
+        .file_name()
+        .unwrap_or(world_dir.as_os_str())
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// Attribute the findings of a world to that world.
+fn tag_findings_with_world(
+    findings: Vec<((i32, i32), Finding)>,
+    world: &str,
+) -> impl Iterator<Item = ((i32, i32), Finding)> + '_ {
+    findings.into_iter().map(move |(chunk, finding)| {
+        (
+            chunk,
+            Finding {
+                world: Some(world.to_string()),
+                ..finding
+            },
+        )
+    })
+}
+
+/// All findings at most `radius` away from `location` on both the x and the z axis, sorted by location.
+/// `location` and `radius` use the coordinate space of the findings.
+fn findings_near(results: &ScanResults, location: (i32, i32), radius: u32) -> Vec<&Finding> {
+    if results.0.is_empty() {
+        return Vec::new();
+    }
+    let (min_x, min_z, max_x, max_z) = results.0.iter().fold(
+        (i32::MAX, i32::MAX, i32::MIN, i32::MIN),
+        |(min_x, min_z, max_x, max_z), finding| {
+            let Location { x, z, .. } = finding.location;
+            (min_x.min(x), min_z.min(z), max_x.max(x), max_z.max(z))
+        },
+    );
+    let mut tree = qutee::QuadTree::<i32, &Finding, ConstCap<32>>::new_with_const_cap(
+        Boundary::between_points(
+            (min_x, min_z),
+            (max_x.saturating_add(1), max_z.saturating_add(1)),
+        ),
+    );
+    results.0.iter().for_each(|finding| {
+        tree.insert(finding)
+            .expect("Finding is outside of quad tree");
+    });
+    let radius = i32::try_from(radius).unwrap_or(i32::MAX);
+    let (x, z) = location;
+    let boundary = Boundary::between_points(
+        (x.saturating_sub(radius), z.saturating_sub(radius)),
+        (
+            x.saturating_add(radius).saturating_add(1),
+            z.saturating_add(radius).saturating_add(1),
+        ),
+    );
+    let is_near = |finding: &&Finding| {
+        (i64::from(finding.location.x) - i64::from(x)).abs() <= i64::from(radius)
+            && (i64::from(finding.location.z) - i64::from(z)).abs() <= i64::from(radius)
+    };
+    let mut findings = tree
+        .query(boundary)
+        .copied()
+        .filter(is_near)
+        .collect::<Vec<_>>();
+    findings.sort_by_key(|finding| (finding.location.x, finding.location.z));
+    findings
+}
+
+/// Find the region files to search.
+/// Without an area every region file of the world or region directory is searched.
+fn find_region_files(
+    world_dir: &Path,
+    region_dir: Option<&Path>,
+    area: Option<&args::Area>,
+) -> Vec<RegionFile> {
+    match (region_dir, area) {
+        (Some(region_dir), Some(area)) => {
+            log::debug!("Searching chunks in area {area}");
+            mc_map_reader::files::get_regions_in_area_of_region_dir(
+                region_dir, area.x1, area.z1, area.x2, area.z2,
+            )
+        }
+        (Some(region_dir), None) => mc_map_reader::files::get_regions_of_region_dir(region_dir)
+            .expect("Could not read region directory"),
+        (None, Some(area)) => {
+            log::debug!("Searching chunks in area {area}");
+            mc_map_reader::files::get_regions_in_area(
+                world_dir, None, area.x1, area.z1, area.x2, area.z2,
+            )
+        }
+        (None, None) => {
+            log::debug!("Searching all region files");
+            mc_map_reader::files::get_regions(world_dir, None)
+                .expect("Could not read region directory")
+        }
+    }
+}
+
+/// Print the region files and the number of chunks a search would cover.
+/// Only the region headers are read.
+fn dry_run(
+    region_files: &[RegionFile],
+    config: &SearchDupeStashesConfig,
+    area: Option<&args::Area>,
+    errors: &error_report::ErrorReport,
+    writer: &mut dyn Write,
+) {
+    let chunk_count = region_files
+        .iter()
+        .take_while(|_| !errors.is_aborted())
+        .map(|region| {
+            let chunks = std::fs::File::open(region.as_path())
+                .map_err(RegionLoadError::from)
+                .and_then(|file| count_chunks(file, region.x(), region.z(), config, area));
+            match chunks {
+                Ok(chunks) => {
+                    writeln!(writer, "{}: {chunks} chunks", region.as_path().display())
+                        .expect("Error writing message");
+                    chunks
+                }
+                Err(err) => {
+                    errors.record(region.as_path(), &Error::from(err));
+                    0
+                }
+            }
+        })
+        .sum::<usize>();
+    writeln!(
+        writer,
+        "Would search {} region files with {chunk_count} chunks",
+        region_files.len()
+    )
+    .expect("Error writing message");
+}
+
+/// Count every item id found in the searched containers, most common items first.
+/// Group thresholds are ignored. Region files that can not be read are recorded in `errors` and skipped.
+async fn count_items(
+    region_files: &[RegionFile],
+    config: &SearchDupeStashesConfig,
+    area: Option<&args::Area>,
+    y_range: &RangeInclusive<i32>,
+    containers: &[String],
+    errors: &error_report::ErrorReport,
+) -> Vec<ItemCount> {
+    let histograms = region_files.iter().map(|region| async move {
+        let chunks = load_region_chunks_or_record(region, errors).await?;
+        Some(chunks.iter().fold(HashMap::new(), |mut histogram, chunk| {
+            count_items_in_chunk(chunk, config, area, y_range, containers, &mut histogram);
+            histogram
+        }))
+    });
+    let histogram = futures::future::join_all(histograms)
+        .await
+        .into_iter()
+        .flatten()
+        .fold(HashMap::new(), |mut total, histogram| {
+            histogram.into_iter().for_each(|(id, count)| {
+                *total.entry(id).or_insert(0) += count;
+            });
+            total
+        });
+    sorted_item_counts(histogram)
+}
+
+/// Print the total count of every item id.
+fn write_item_counts(
+    item_counts: &[ItemCount],
+    output_format: args::OutputFormat,
+    writer: &mut dyn Write,
+) {
+    match output_format {
+        args::OutputFormat::Text => item_counts.iter().for_each(|item| {
+            writeln!(writer, "{},{}", item.id, item.count).expect("Error writing message");
+        }),
+        args::OutputFormat::Json => {
+            serde_json::to_writer_pretty(&mut *writer, &item_counts)
+                .expect("Error writing message");
+            writeln!(writer).expect("Error writing message");
+        }
+    }
+}
+
+/// Find the items in the searched containers that were added after the world's data version.
+async fn find_too_new_items(
+    region_files: &[RegionFile],
+    world_data_version: i32,
+    config: &SearchDupeStashesConfig,
+    area: Option<&args::Area>,
+    y_range: &RangeInclusive<i32>,
+    containers: &[String],
+    errors: &error_report::ErrorReport,
+) -> Vec<version_check::TooNewItem> {
+    let regions = region_files.iter().map(|region| async move {
+        let chunks = load_region_chunks_or_record(region, errors).await?;
+        Some(
+            chunks
+                .iter()
+                .flat_map(|chunk| {
+                    version_check::too_new_items_in_chunk(
+                        chunk,
+                        world_data_version,
+                        config,
+                        area,
+                        y_range,
+                        containers,
+                    )
+                })
+                .collect::<Vec<_>>(),
+        )
+    });
+    futures::future::join_all(regions)
+        .await
+        .into_iter()
+        .flatten()
+        .flatten()
+        .collect()
+}
+
+/// Add the items of all containers in the chunk to `histogram`.
+/// Items inside of shulker boxes are counted as well.
+/// Containers with a loot table, outside of `y_range` or not selected by `containers` are skipped like in a search.
+fn count_items_in_chunk(
+    chunk: &ChunkData,
+    config: &SearchDupeStashesConfig,
+    area: Option<&args::Area>,
+    y_range: &RangeInclusive<i32>,
+    containers: &[String],
+    histogram: &mut HashMap<String, u64>,
+) {
+    if !is_chunk_searched(chunk.x_pos, chunk.z_pos, config, area) {
+        return;
+    }
+    let Some(block_entities) = &chunk.block_entities else {
+        return;
+    };
+    let mut add_item = |item: &Item| {
+        if config.is_item_excluded(&item.id) {
+            return;
+        }
+        *histogram.entry(item.id.clone()).or_insert(0) += u64::try_from(item.count).unwrap_or(0);
+    };
+    block_entities
+        .iter()
+        .filter(|block_entity| y_range.contains(&block_entity.y))
+        .filter(|block_entity| is_container_searched(&block_entity.id, containers))
+        .filter_map(BlockEntity::as_container)
+        .filter(|container| !container.has_loot_table())
+        .flat_map(|container| container.items())
+        .for_each(|item| {
+            add_item(&item.item);
+            nested_items(&item.item, 0).iter().for_each(&mut add_item);
+        });
+}
+
+/// Sort the item counts by count, most common items first. Items with the same count are sorted by id.
+fn sorted_item_counts(histogram: HashMap<String, u64>) -> Vec<ItemCount> {
+    let mut item_counts = histogram
+        .into_iter()
+        .map(|(id, count)| ItemCount { id, count })
+        .collect::<Vec<_>>();
+    item_counts.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.id.cmp(&b.id)));
+    item_counts
+}
+
+/// Count the generated chunks of a region that are inside of the area and not excluded by the config.
+fn count_chunks(
+    read: impl std::io::Read,
+    region_x: i32,
+    region_z: i32,
+    config: &SearchDupeStashesConfig,
+    area: Option<&args::Area>,
+) -> Result<usize, RegionLoadError> {
+    let header = mc_map_reader::load_region_header(read)?;
+    let chunks = header
+        .get_chunk_info()
+        .iter()
+        .enumerate()
+        .filter(|(_, chunk_info)| chunk_info.is_some())
+        .filter(|(index, _)| {
+            let chunk_x = coords::region_to_chunk(region_x) + (index % 32) as i32;
+            let chunk_z = coords::region_to_chunk(region_z) + (index / 32) as i32;
+            is_chunk_searched(chunk_x, chunk_z, config, area)
+        })
+        .count();
+    Ok(chunks)
+}
+
+/// Remove all region files that were last modified before `cutoff`.
+/// Region files without a readable modification time are kept.
+fn modified_since(region_files: Vec<RegionFile>, cutoff: SystemTime) -> Vec<RegionFile> {
+    region_files
+        .into_iter()
+        .filter(|region| {
+            match std::fs::metadata(region.as_path()).and_then(|metadata| metadata.modified()) {
+                Ok(modified) => modified >= cutoff,
+                Err(err) => {
+                    log::warn!(
+                        "Could not read modification time of {}: {err}",
+                        region.as_path().display()
+                    );
+                    true
+                }
+            }
+        })
+        .collect()
 }
 
 fn min_corner_block_in_chunk(region_x: i32, region_z: i32) -> (i32, i32) {
-    let chunk_x = region_x << 5;
-    let chunk_z = region_z << 5;
-    let block_x = chunk_x * BLOCKS_IN_CHUNK;
-    let block_z = chunk_z * BLOCKS_IN_CHUNK;
-    (block_x, block_z)
+    (
+        coords::region_to_block(region_x),
+        coords::region_to_block(region_z),
+    )
 }
 
 fn max_corner_block_in_chunk(region_x: i32, region_z: i32) -> (i32, i32) {
     let (min_block_x, min_block_z) = min_corner_block_in_chunk(region_x, region_z);
     (
-        min_block_x + CHUNKS_IN_REGION_FILE * BLOCKS_IN_CHUNK,
-        min_block_z + CHUNKS_IN_REGION_FILE * BLOCKS_IN_CHUNK,
+        min_block_x + coords::CHUNKS_PER_REGION * coords::BLOCKS_PER_CHUNK,
+        min_block_z + coords::CHUNKS_PER_REGION * coords::BLOCKS_PER_CHUNK,
     )
 }
 
@@ -196,7 +845,7 @@ fn collect_items_in_area(
     inventory_tree: &QuadTree,
     detection_method: &dyn DetectionMethod,
     group_hash_lookup_table: &HashMap<u64, &str>,
-) -> (Position, HashMap<u64, u64>) {
+) -> (Position, String, HashMap<u64, u64>) {
     let boundary = Boundary::new((inventory.x - radius, inventory.z - radius), radius, radius);
     let mut items_in_area_by_group =
         inventory_tree
@@ -224,21 +873,104 @@ fn collect_items_in_area(
             y: inventory.y,
             z: inventory.z,
         },
+        inventory.id.clone(),
         items_in_area_by_group,
     )
 }
 
+fn into_finding(
+    position: Position,
+    container: String,
+    groups: HashMap<u64, u64>,
+    coords: args::CoordinateSpace,
+    group_hash_lookup_table: &HashMap<u64, &str>,
+    config: &SearchDupeStashesConfig,
+) -> Option<Finding> {
+    if groups.is_empty() {
+        return None;
+    }
+    let mut groups = groups
+        .into_iter()
+        .filter_map(|(group_hash, count)| {
+            let group = *group_hash_lookup_table.get(&group_hash)?;
+            Some(GroupCount {
+                group: group.to_string(),
+                count,
+                threshold: config.groups.get(group)?.threshold,
+            })
+        })
+        .collect::<Vec<_>>();
+    groups.sort_by(|a, b| a.group.cmp(&b.group));
+    Some(Finding {
+        world: None,
+        location: position.location(coords),
+        container,
+        groups,
+        stacks: Vec::new(),
+    })
+}
+
+/// Returns `true` if the chunk is inside of the searched area and not excluded by the config.
+fn is_chunk_searched(
+    chunk_x: i32,
+    chunk_z: i32,
+    config: &SearchDupeStashesConfig,
+    area: Option<&args::Area>,
+) -> bool {
+    area.map_or(true, |area| area.contains(chunk_x, chunk_z))
+        && !config.is_chunk_excluded(chunk_x, chunk_z)
+}
+
+/// Load the chunks of a region file and record the error if it can not be read.
+/// Once the scan is aborted the region file is skipped without reading it.
+async fn load_region_chunks_or_record(
+    region: &RegionFile,
+    errors: &error_report::ErrorReport,
+) -> Option<Vec<ChunkData>> {
+    if errors.is_aborted() {
+        return None;
+    }
+    match load_region_chunks(region.as_path(), None).await {
+        Ok(chunks) => Some(chunks),
+        Err(err) => {
+            errors.record(region.as_path(), &err);
+            None
+        }
+    }
+}
+
+async fn load_region_chunks(
+    region: &Path,
+    profile: Option<&profile::Profile>,
+) -> Result<Vec<ChunkData>, Error> {
+    let start = std::time::Instant::now();
+    let file = OpenOptions::new().read(true).open(region).await?;
+    let file = read_file(file).await?;
+    let read = start.elapsed();
+    let (anvil_save, timing) = mc_map_reader::load_region_timed(file.as_slice(), None)?;
+    if let Some(profile) = profile {
+        profile.record(profile::RegionProfile {
+            region: region.display().to_string(),
+            read,
+            decompress: timing.decompress,
+            parse: timing.parse,
+        });
+    }
+    Ok(anvil_save.chunks)
+}
+
 async fn search_inventories_in_region<'a>(
     region: &Path,
     config: &'a SearchDupeStashesConfig,
+    area: Option<&'a args::Area>,
+    y_range: &'a RangeInclusive<i32>,
+    containers: &'a [String],
+    profile: Option<&profile::Profile>,
 ) -> Result<impl Iterator<Item = FoundInventory<'a>>, Error> {
-    let region = OpenOptions::new().read(true).open(region).await?;
-    let region = read_file(region).await?;
-    let region = mc_map_reader::load_region(region.as_slice(), None)?;
-    let inv = region
-        .chunks
+    let inv = load_region_chunks(region, profile)
+        .await?
         .into_iter()
-        .filter_map(|c| search_inventories_in_chunk(c, config))
+        .filter_map(move |c| search_inventories_in_chunk(c, config, area, y_range, containers))
         .flatten();
     Ok(inv)
 }
@@ -246,55 +978,120 @@ async fn search_inventories_in_region<'a>(
 fn search_inventories_in_chunk<'inventory, 'config, 'chunk>(
     chunk: ChunkData,
     config: &'config SearchDupeStashesConfig,
+    area: Option<&args::Area>,
+    y_range: &RangeInclusive<i32>,
+    containers: &'config [String],
 ) -> Option<impl Iterator<Item = FoundInventory<'inventory>>>
 where
     'config: 'inventory,
     'chunk: 'inventory,
 {
+    if !is_chunk_searched(chunk.x_pos, chunk.z_pos, config, area) {
+        return None;
+    }
     let Some(block_entities) = chunk.block_entities else {
         return None;
     };
-    let inventories = block_entities.into_iter().filter_map(|block_entity| {
-        let inventory: &dyn InventoryBlock = match &block_entity.entity_type {
-            BlockEntityType::Barrel(block) => block,
-            BlockEntityType::Chest(block) => block,
-            BlockEntityType::Dispenser(block) => block,
-            BlockEntityType::Dropper(block) => block,
-            BlockEntityType::Hopper(block) => block,
-            BlockEntityType::ShulkerBox(block) => block,
-            BlockEntityType::TrappedChest(block) => block,
-            _ => return None,
-        };
-        search_inventory_block(inventory, &block_entity, config)
-    });
+    let y_range = y_range.clone();
+    let inventories = block_entities
+        .into_iter()
+        .filter(move |block_entity| y_range.contains(&block_entity.y))
+        .filter(move |block_entity| is_container_searched(&block_entity.id, containers))
+        .filter_map(|block_entity| match block_entity.as_container() {
+            Some(inventory) => search_inventory_block(inventory, &block_entity, config),
+            None => search_banner_block(&block_entity),
+        });
     Some(inventories)
 }
 
+/// Returns `true` if containers with this block entity id are searched.
+/// All containers are searched if no container ids are given.
+fn is_container_searched(id: &str, containers: &[String]) -> bool {
+    containers.is_empty() || containers.iter().any(|container| container == id)
+}
+
+/// Report a placed banner with more patterns than a loom can apply.
+/// Banners hold no items, so the banner is only found if it has an anomaly.
+fn search_banner_block<'a>(block_entity: &BlockEntity) -> Option<FoundInventory<'a>> {
+    let design = BannerDesign::from_block_entity(block_entity)?;
+    if !design.exceeds_loom_limit() {
+        return None;
+    }
+    let BlockPos { x, y, z } = block_entity.position();
+    Some(FoundInventory {
+        inventory_type: block_entity.id.clone(),
+        items: HashMap::new(),
+        position: Position { x, y, z },
+        anomalies: vec![Anomaly::BannerPatterns(BannerPatternsAnomaly {
+            inventory_type: None,
+            position: Position { x, y, z },
+            item_id: block_entity.id.clone(),
+            patterns: design.patterns.len(),
+        })],
+        stacks: Vec::new(),
+    })
+}
+
 fn search_inventory_block<'a, 'b>(
-    inventory: &dyn InventoryBlock,
+    inventory: &dyn Container,
     base_entity: &BlockEntity,
     config: &'b SearchDupeStashesConfig,
 ) -> Option<FoundInventory<'a>>
 where
     'b: 'a,
 {
-    if inventory.loot_table().is_some() || inventory.loot_table_seed().is_some() {
+    if inventory.has_loot_table() {
         return None;
     }
-    let x = base_entity.x;
-    let z = base_entity.z;
-    let y = base_entity.y;
-    let items = if let Some(items) = inventory.items() {
-        items.iter().fold(HashMap::default(), |mut item_map, item| {
-            add_item_to_map(item, &mut item_map, config);
-            if item_is_shulker_box(&item.item.id) {
-                search_subinventory(&item.item, &mut item_map, config)
-            }
-            item_map
-        })
-    } else {
+    let BlockPos { x, y, z } = base_entity.position();
+    let items = inventory.items();
+    if items.is_empty() {
         return None;
-    };
+    }
+    let slot_anomalies = inventory
+        .out_of_range_items()
+        .into_iter()
+        .filter(|item| !config.is_item_excluded(&item.item.id))
+        .map(|item| {
+            Anomaly::Slot(SlotAnomaly {
+                inventory_type: base_entity.id.clone(),
+                position: Position { x, y, z },
+                item_id: item.item.id.clone(),
+                slot: item.slot,
+                slot_count: inventory.slot_count(),
+            })
+        });
+    let stack_size_anomalies = items.iter().filter_map(|item| {
+        if config.is_item_excluded(&item.item.id) {
+            return None;
+        }
+        let max_stack_size = config.max_stack_size(&item.item);
+        (item.item.count > max_stack_size).then(|| {
+            Anomaly::StackSize(StackSizeAnomaly {
+                inventory_type: base_entity.id.clone(),
+                position: Position { x, y, z },
+                item_id: item.item.id.clone(),
+                count: item.item.count,
+                max_stack_size,
+            })
+        })
+    });
+    let edited_item_anomalies = items
+        .iter()
+        .flat_map(|item| std::iter::once(item.item.clone()).chain(nested_items(&item.item, 0)))
+        .filter(|item| !config.is_item_excluded(&item.id))
+        .flat_map(|item| edited_item_anomalies(&item, &base_entity.id, Position { x, y, z }));
+    let anomalies = slot_anomalies
+        .chain(stack_size_anomalies)
+        .chain(edited_item_anomalies)
+        .collect();
+    let position = Position { x, y, z };
+    let mut stacks = Vec::new();
+    let items = items.iter().fold(HashMap::default(), |mut item_map, item| {
+        add_item_to_map(&item.item, &position, &mut item_map, &mut stacks, config);
+        search_subinventory(&item.item, &position, &mut item_map, &mut stacks, config);
+        item_map
+    });
     log::debug!(
         "Found inventory at ({x}, {y}, {z}) with {items_len} items",
         items_len = items.len()
@@ -302,46 +1099,90 @@ where
     Some(FoundInventory {
         inventory_type: base_entity.id.clone(),
         items,
-        position: Position { x, y, z },
+        position,
+        anomalies,
+        stacks,
     })
 }
 
-#[inline]
-fn item_is_shulker_box(id: &str) -> bool {
-    id.starts_with("minecraft:") && id.ends_with("shulker_box")
+/// Anomalies of an item whose NBT data was edited to values vanilla Minecraft does not create.
+fn edited_item_anomalies(item: &Item, inventory_type: &str, position: Position) -> Vec<Anomaly> {
+    let mut anomalies = Vec::new();
+    if let Some(design) = BannerDesign::from_item(item) {
+        if design.exceeds_loom_limit() {
+            anomalies.push(Anomaly::BannerPatterns(BannerPatternsAnomaly {
+                inventory_type: Some(inventory_type.to_string()),
+                position: position.clone(),
+                item_id: item.id.clone(),
+                patterns: design.patterns.len(),
+            }));
+        }
+    }
+    if let Some(amplifier) = item.ominous_bottle_amplifier() {
+        if amplifier > MAX_OMINOUS_BOTTLE_AMPLIFIER {
+            anomalies.push(Anomaly::OminousBottle(OminousBottleAnomaly {
+                inventory_type: inventory_type.to_string(),
+                position,
+                item_id: item.id.clone(),
+                amplifier,
+            }));
+        }
+    }
+    anomalies
 }
 
 fn search_subinventory<'a, 'b>(
     item: &Item,
+    position: &Position,
     item_map: &mut HashMap<&'a str, FoundItem>,
+    stacks: &mut Vec<FoundStack<'a>>,
     config: &'b SearchDupeStashesConfig,
 ) where
     'b: 'a,
 {
-    let Some(tag) = &item.tag else {
-        return;
-    };
-    let Some(block_entity_tag) = tag.get("BlockEntityTag").cloned() else {
-        return;
-    };
-    let Ok(inventory) = ShulkerBox::try_from(block_entity_tag) else {
-        return;
-    };
-    if let Some(items) = inventory.items() {
-        items
-            .iter()
-            .for_each(|item| add_item_to_map(item, item_map, config))
+    nested_items(item, 0)
+        .iter()
+        .for_each(|item| add_item_to_map(item, position, item_map, stacks, config))
+}
+
+/// Maximum depth of shulker boxes and bundles nested in each other that is searched.
+const MAX_NESTING_DEPTH: usize = 8;
+
+/// Items stored inside of a shulker box or bundle including the items nested inside of those.
+fn nested_items(item: &Item, depth: usize) -> Vec<Item> {
+    if depth >= MAX_NESTING_DEPTH {
+        return Vec::new();
     }
+    let mut contained = if ShulkerBox::is_shulker_box_id(&item.id) {
+        item.contained_items()
+            .into_iter()
+            .map(|item| item.item)
+            .collect()
+    } else {
+        Vec::new()
+    };
+    contained.extend(item.bundle_contents());
+    let nested = contained
+        .iter()
+        .flat_map(|item| nested_items(item, depth + 1))
+        .collect::<Vec<_>>();
+    contained.extend(nested);
+    contained
 }
 
+/// Count the item towards every group it matches and remember the stack for `--detail`.
 fn add_item_to_map<'a, 'b>(
-    item: &mc_map_reader::data::item::ItemWithSlot,
+    item: &Item,
+    position: &Position,
     item_map: &mut HashMap<&'a str, FoundItem>,
+    stacks: &mut Vec<FoundStack<'a>>,
     config: &'b SearchDupeStashesConfig,
 ) where
     'b: 'a,
 {
-    let item = &item.item;
+    if config.is_item_excluded(&item.id) {
+        return;
+    }
     config
         .groups
         .iter()
@@ -361,6 +1202,14 @@ fn add_item_to_map<'a, 'b>(
                 .or_insert_with(|| FoundItem {
                     count: item.count as usize * mul,
                 });
+            stacks.push(FoundStack {
+                group: group_name,
+                position: position.clone(),
+                id: item.id.clone(),
+                count: item.count,
+                custom_name: item.custom_name(),
+                enchantments: item.enchantments(),
+            });
         });
 }
 
@@ -394,6 +1243,7 @@ async fn save_region_inventories<'a>(
                 x: inv.position.x,
                 y: inv.position.y,
                 z: inv.position.z,
+                id: inv.inventory_type,
                 items: inv
                     .items
                     .into_iter()
@@ -407,3 +1257,1160 @@ async fn save_region_inventories<'a>(
     file.write_all(&buf).await?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use mc_map_reader::{
+        data::{
+            block_entity::{BlockEntity, BlockEntityType, Chest, ShulkerBox, TrappedChest},
+            chunk::{ChunkData, ChunkStatus},
+            item::{Item, ItemWithSlot},
+        },
+        nbt::{List, Tag},
+    };
+    use std::{
+        collections::HashMap,
+        time::{Duration, SystemTime},
+    };
+    use test_case::test_case;
+
+    use super::{
+        args::{Area, CoordinateSpace},
+        config::{Group, GroupEntry, SearchDupeStashesConfig},
+        Anomaly, Finding, GroupCount, ItemCount, Location, Position, ScanResults, SlotAnomaly,
+        StackSizeAnomaly,
+    };
+
+    fn item(slot: i8) -> ItemWithSlot {
+        ItemWithSlot {
+            slot,
+            item: Item {
+                id: "minecraft:dirt".to_string(),
+                tag: None,
+                components: None,
+                count: 1,
+            },
+        }
+    }
+
+    fn block_entity(id: &str, entity_type: BlockEntityType) -> BlockEntity {
+        BlockEntity {
+            id: id.to_string(),
+            keep_packed: false,
+            x: 1,
+            y: 2,
+            z: 3,
+            entity_type,
+        }
+    }
+
+    fn chest() -> BlockEntityType {
+        chest_with(vec![item(0)])
+    }
+
+    fn chest_with(items: Vec<ItemWithSlot>) -> BlockEntityType {
+        BlockEntityType::Chest(Chest {
+            custom_name: None,
+            items: Some(List::from(items)),
+            lock: None,
+            loot_table: None,
+            loot_table_seed: None,
+        })
+    }
+
+    fn chunk_at(x_pos: i32, z_pos: i32, block_entities: Vec<BlockEntity>) -> ChunkData {
+        ChunkData {
+            data_version: 0,
+            x_pos,
+            y_pos: 0,
+            z_pos,
+            status: ChunkStatus::Full,
+            last_update: 0,
+            sections: List::from(vec![]),
+            entities: None,
+            block_entities: Some(List::from(block_entities)),
+        }
+    }
+
+    #[test]
+    fn test_valuables_preset_warns_for_chest_of_netherite() {
+        use super::detection_method::{Absolute, DetectionMethod};
+
+        let items = (0..27)
+            .map(|slot| ItemWithSlot {
+                slot,
+                item: Item {
+                    id: "minecraft:netherite_ingot".to_string(),
+                    tag: None,
+                    components: None,
+                    count: 64,
+                },
+            })
+            .collect::<Vec<_>>();
+        let block_entity = block_entity("minecraft:chest", chest_with(items));
+        let config =
+            crate::config::Config::preset(crate::config::Preset::Valuables).search_dupe_stashes;
+        let inventory = super::search_inventory_block(
+            block_entity.as_container().expect("Chest is a container"),
+            &block_entity,
+            &config,
+        )
+        .expect("Chest contains items");
+        let detection_method = Absolute::new(&config.groups);
+        let warnings = inventory
+            .items
+            .iter()
+            .filter(|(group, item)| detection_method.exceeds_max(group, item.count))
+            .map(|(group, _)| *group)
+            .collect::<Vec<_>>();
+        assert_eq!(warnings, vec!["netherite"]);
+    }
+
+    #[test_case(6 => Vec::<String>::new(); "Loom limit")]
+    #[test_case(7 => vec!["1,2,3,minecraft:white_banner in minecraft:chest has 7 patterns but a loom can only apply 6".to_string()]; "Above loom limit")]
+    fn test_search_inventory_block_reports_over_patterned_banner(patterns: usize) -> Vec<String> {
+        let pattern = Tag::Compound(HashMap::from_iter([
+            (
+                "pattern".to_string(),
+                Tag::String("minecraft:stripe_bottom".to_string()),
+            ),
+            ("color".to_string(), Tag::String("red".to_string())),
+        ]));
+        let banner = ItemWithSlot {
+            slot: 0,
+            item: Item {
+                id: "minecraft:white_banner".to_string(),
+                tag: None,
+                components: Some(HashMap::from_iter([(
+                    "minecraft:banner_patterns".to_string(),
+                    Tag::List(List::from(vec![pattern; patterns])),
+                )])),
+                count: 1,
+            },
+        };
+        let block_entity = block_entity("minecraft:chest", chest_with(vec![banner]));
+        let config = SearchDupeStashesConfig::default();
+        let inventory = super::search_inventory_block(
+            block_entity.as_container().expect("Chest is a container"),
+            &block_entity,
+            &config,
+        )
+        .expect("Chest contains items");
+        inventory
+            .anomalies
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    #[test_case(4 => Vec::<String>::new(); "Bad Omen V")]
+    #[test_case(5 => vec!["1,2,3,minecraft:ominous_bottle in minecraft:chest has amplifier 5 but vanilla ominous bottles have at most 4".to_string()]; "Over amplified")]
+    fn test_search_inventory_block_reports_over_amplified_ominous_bottle(
+        amplifier: i32,
+    ) -> Vec<String> {
+        let bottle = ItemWithSlot {
+            slot: 0,
+            item: Item {
+                id: "minecraft:ominous_bottle".to_string(),
+                tag: None,
+                components: Some(HashMap::from_iter([(
+                    "minecraft:ominous_bottle_amplifier".to_string(),
+                    Tag::Int(amplifier),
+                )])),
+                count: 1,
+            },
+        };
+        let block_entity = block_entity("minecraft:chest", chest_with(vec![bottle]));
+        let config = SearchDupeStashesConfig::default();
+        let inventory = super::search_inventory_block(
+            block_entity.as_container().expect("Chest is a container"),
+            &block_entity,
+            &config,
+        )
+        .expect("Chest contains items");
+        inventory
+            .anomalies
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn test_search_inventory_block_reports_out_of_range_slot() {
+        let block_entity = block_entity("minecraft:chest", chest_with(vec![item(0), item(40)]));
+        let config = SearchDupeStashesConfig::default();
+        let inventory = super::search_inventory_block(
+            block_entity.as_container().expect("Chest is a container"),
+            &block_entity,
+            &config,
+        )
+        .expect("Chest contains items");
+        assert_eq!(
+            inventory.anomalies,
+            vec![Anomaly::Slot(SlotAnomaly {
+                inventory_type: "minecraft:chest".to_string(),
+                position: Position { x: 1, y: 2, z: 3 },
+                item_id: "minecraft:dirt".to_string(),
+                slot: 40,
+                slot_count: 27,
+            })]
+        );
+    }
+
+    fn stack(id: &str, count: i8) -> ItemWithSlot {
+        ItemWithSlot {
+            slot: 0,
+            item: Item {
+                id: id.to_string(),
+                tag: None,
+                components: None,
+                count,
+            },
+        }
+    }
+
+    #[test]
+    fn test_search_inventory_block_counts_component_shulker_box_content() {
+        let entry = |slot: i32, id: &str, count: i32| {
+            Tag::Compound(HashMap::from([
+                ("slot".to_string(), Tag::Int(slot)),
+                (
+                    "item".to_string(),
+                    Tag::Compound(HashMap::from([
+                        ("id".to_string(), Tag::String(id.to_string())),
+                        ("count".to_string(), Tag::Int(count)),
+                    ])),
+                ),
+            ]))
+        };
+        let shulker_box = ItemWithSlot {
+            slot: 0,
+            item: Item {
+                id: "minecraft:shulker_box".to_string(),
+                tag: None,
+                components: Some(HashMap::from([(
+                    "minecraft:container".to_string(),
+                    Tag::List(List::from(vec![
+                        entry(0, "minecraft:diamond", 64),
+                        entry(1, "minecraft:diamond_block", 2),
+                    ])),
+                )])),
+                count: 1,
+            },
+        };
+        let block_entity = block_entity("minecraft:chest", chest_with(vec![shulker_box]));
+        let config = SearchDupeStashesConfig::default();
+        let inventory = super::search_inventory_block(
+            block_entity.as_container().expect("Chest is a container"),
+            &block_entity,
+            &config,
+        )
+        .expect("Chest contains items");
+        assert_eq!(
+            inventory.items.get("diamond").map(|item| item.count),
+            Some(64 + 2 * 9)
+        );
+    }
+
+    #[test]
+    fn test_search_parsed_component_shulker_box() {
+        let compound = |entries: Vec<(&str, Tag)>| {
+            Tag::Compound(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (key.to_string(), value))
+                    .collect(),
+            )
+        };
+        let string = |value: &str| Tag::String(value.to_string());
+        let entry = |slot: i32, id: &str, count: i32| {
+            compound(vec![
+                ("slot", Tag::Int(slot)),
+                (
+                    "item",
+                    compound(vec![("id", string(id)), ("count", Tag::Int(count))]),
+                ),
+            ])
+        };
+        // A chest holding a shulker box as saved since 1.20.5
+        let shulker_box = compound(vec![
+            ("Slot", Tag::Byte(0)),
+            ("id", string("minecraft:shulker_box")),
+            ("count", Tag::Int(1)),
+            (
+                "components",
+                compound(vec![(
+                    "minecraft:container",
+                    Tag::List(List::from(vec![
+                        entry(0, "minecraft:diamond", 64),
+                        entry(1, "minecraft:diamond_block", 2),
+                    ])),
+                )]),
+            ),
+        ]);
+        let block_entity = BlockEntity::try_from(compound(vec![
+            ("id", string("minecraft:chest")),
+            ("x", Tag::Int(1)),
+            ("y", Tag::Int(2)),
+            ("z", Tag::Int(3)),
+            ("Items", Tag::List(List::from(vec![shulker_box]))),
+        ]))
+        .expect("Chest is valid");
+        let config = SearchDupeStashesConfig::default();
+        let inventory = super::search_inventory_block(
+            block_entity.as_container().expect("Chest is a container"),
+            &block_entity,
+            &config,
+        )
+        .expect("Chest contains items");
+        assert_eq!(
+            inventory.items.get("diamond").map(|item| item.count),
+            Some(64 + 2 * 9)
+        );
+    }
+
+    #[test]
+    fn test_search_inventory_block_counts_bundle_content() {
+        let entry = |id: &str, count: i32, components: Option<Tag>| {
+            let mut item = HashMap::from([
+                ("id".to_string(), Tag::String(id.to_string())),
+                ("count".to_string(), Tag::Int(count)),
+            ]);
+            if let Some(components) = components {
+                item.insert("components".to_string(), components);
+            }
+            Tag::Compound(item)
+        };
+        let bundle_contents = |entries: Vec<Tag>| {
+            HashMap::from([(
+                "minecraft:bundle_contents".to_string(),
+                Tag::List(List::from(entries)),
+            )])
+        };
+        let nested_bundle = entry(
+            "minecraft:bundle",
+            1,
+            Some(Tag::Compound(bundle_contents(vec![entry(
+                "minecraft:diamond",
+                8,
+                None,
+            )]))),
+        );
+        let bundle = ItemWithSlot {
+            slot: 0,
+            item: Item {
+                id: "minecraft:bundle".to_string(),
+                tag: None,
+                components: Some(bundle_contents(vec![
+                    entry("minecraft:diamond", 16, None),
+                    entry("minecraft:diamond_block", 1, None),
+                    nested_bundle,
+                ])),
+                count: 1,
+            },
+        };
+        let block_entity = block_entity("minecraft:chest", chest_with(vec![bundle]));
+        let config = SearchDupeStashesConfig::default();
+        let inventory = super::search_inventory_block(
+            block_entity.as_container().expect("Chest is a container"),
+            &block_entity,
+            &config,
+        )
+        .expect("Chest contains items");
+        assert_eq!(
+            inventory.items.get("diamond").map(|item| item.count),
+            Some(16 + 9 + 8)
+        );
+    }
+
+    #[test]
+    fn test_search_inventory_block_reports_overstacked_item() {
+        let block_entity = block_entity(
+            "minecraft:chest",
+            chest_with(vec![
+                stack("minecraft:diamond_sword", 65),
+                stack("minecraft:diamond", 64),
+            ]),
+        );
+        let config = SearchDupeStashesConfig::default();
+        let inventory = super::search_inventory_block(
+            block_entity.as_container().expect("Chest is a container"),
+            &block_entity,
+            &config,
+        )
+        .expect("Chest contains items");
+        assert_eq!(
+            inventory.anomalies,
+            vec![Anomaly::StackSize(StackSizeAnomaly {
+                inventory_type: "minecraft:chest".to_string(),
+                position: Position { x: 1, y: 2, z: 3 },
+                item_id: "minecraft:diamond_sword".to_string(),
+                count: 65,
+                max_stack_size: 1,
+            })]
+        );
+    }
+
+    #[test_case(&[], "minecraft:ender_pearl", 99 => true; "Vanilla stack size")]
+    #[test_case(&[], "minecraft:ender_pearl", 16 => false; "Full stack")]
+    #[test_case(&[("minecraft:ender_pearl", 99)], "minecraft:ender_pearl", 99 => false; "Configured stack size")]
+    #[test_case(&[("minecraft:diamond", 16)], "minecraft:diamond", 64 => true; "Configured smaller stack size")]
+    fn test_search_inventory_block_uses_configured_stack_size(
+        max_stack_sizes: &[(&str, i8)],
+        id: &str,
+        count: i8,
+    ) -> bool {
+        let block_entity = block_entity("minecraft:chest", chest_with(vec![stack(id, count)]));
+        let config = SearchDupeStashesConfig {
+            max_stack_sizes: max_stack_sizes
+                .iter()
+                .map(|(id, max_stack_size)| (id.to_string(), *max_stack_size))
+                .collect(),
+            ..SearchDupeStashesConfig::default()
+        };
+        let inventory = super::search_inventory_block(
+            block_entity.as_container().expect("Chest is a container"),
+            &block_entity,
+            &config,
+        )
+        .expect("Chest contains items");
+        !inventory.anomalies.is_empty()
+    }
+
+    #[test_case(&[] => (1, true); "Not excluded")]
+    #[test_case(&["minecraft:diamond"] => (0, false); "Excluded")]
+    #[test_case(&["minecraft:dirt"] => (1, true); "Other item excluded")]
+    fn test_search_inventory_block_ignores_excluded_items(exclude_items: &[&str]) -> (usize, bool) {
+        let block_entity = block_entity(
+            "minecraft:chest",
+            chest_with(vec![stack("minecraft:diamond", 99)]),
+        );
+        let config = SearchDupeStashesConfig {
+            exclude_items: exclude_items.iter().map(|id| id.to_string()).collect(),
+            ..SearchDupeStashesConfig::default()
+        };
+        let inventory = super::search_inventory_block(
+            block_entity.as_container().expect("Chest is a container"),
+            &block_entity,
+            &config,
+        )
+        .expect("Chest contains items");
+        (
+            inventory.anomalies.len(),
+            inventory.items.contains_key("diamond"),
+        )
+    }
+
+    #[test]
+    fn test_detail_lists_named_enchanted_item() {
+        let sword = ItemWithSlot {
+            slot: 0,
+            item: Item {
+                id: "minecraft:diamond_sword".to_string(),
+                tag: None,
+                components: Some(HashMap::from([
+                    (
+                        "minecraft:custom_name".to_string(),
+                        Tag::String(r#"{"text":"Excalibur"}"#.to_string()),
+                    ),
+                    (
+                        "minecraft:enchantments".to_string(),
+                        Tag::Compound(HashMap::from([
+                            ("minecraft:sharpness".to_string(), Tag::Int(5)),
+                            ("minecraft:looting".to_string(), Tag::Int(3)),
+                        ])),
+                    ),
+                ])),
+                count: 1,
+            },
+        };
+        let block_entity = block_entity(
+            "minecraft:chest",
+            chest_with(vec![sword, stack("minecraft:dirt", 64)]),
+        );
+        let config = SearchDupeStashesConfig {
+            groups: HashMap::from([(
+                "swords".to_string(),
+                Group {
+                    items: vec![GroupEntry {
+                        id: Some("minecraft:diamond_sword".into()),
+                        nbt: None,
+                        multiplier: 1,
+                    }],
+                    threshold: 0,
+                },
+            )]),
+            ..SearchDupeStashesConfig::default()
+        };
+        let inventory = super::search_inventory_block(
+            block_entity.as_container().expect("Chest is a container"),
+            &block_entity,
+            &config,
+        )
+        .expect("Chest contains items");
+        let finding = Finding {
+            world: None,
+            location: inventory.position.location(CoordinateSpace::Block),
+            container: inventory.inventory_type.clone(),
+            groups: vec![GroupCount {
+                group: "swords".to_string(),
+                count: 1,
+                threshold: 0,
+            }],
+            stacks: inventory
+                .stacks
+                .into_iter()
+                .map(|stack| stack.into_detail(CoordinateSpace::Block))
+                .collect(),
+        };
+        let mut output = Vec::new();
+        super::write_findings_text(&[&finding], &mut output);
+        assert_eq!(
+            String::from_utf8(output).expect("Output is UTF-8"),
+            "1,2,3,minecraft:chest,swords,1\n  1,2,3,minecraft:diamond_sword,1 named \"Excalibur\" enchanted with minecraft:looting 3, minecraft:sharpness 5\n"
+        );
+    }
+
+    #[test_case(4, 4 => 0; "Inside excluded area")]
+    #[test_case(5, 4 => 1; "Outside excluded area")]
+    fn test_search_inventories_in_excluded_chunk(chunk_x: i32, chunk_z: i32) -> usize {
+        let chunk = chunk_at(
+            chunk_x,
+            chunk_z,
+            vec![block_entity("minecraft:chest", chest())],
+        );
+        let config = SearchDupeStashesConfig {
+            exclude: vec![Area::new(0, 0, 4, 4)],
+            ..SearchDupeStashesConfig::default()
+        };
+        super::search_inventories_in_chunk(chunk, &config, None, &(i32::MIN..=i32::MAX), &[])
+            .map(Iterator::count)
+            .unwrap_or_default()
+    }
+
+    #[test_case(10, -4 => 1; "Selected chunk")]
+    #[test_case(11, -4 => 0; "Next chunk on x axis")]
+    #[test_case(10, -3 => 0; "Next chunk on z axis")]
+    fn test_search_inventories_in_single_chunk_area(chunk_x: i32, chunk_z: i32) -> usize {
+        let chunk = chunk_at(
+            chunk_x,
+            chunk_z,
+            vec![block_entity("minecraft:chest", chest())],
+        );
+        let area: Area = serde_json::from_str(r#""10,-4""#).expect("Invalid area");
+        super::search_inventories_in_chunk(
+            chunk,
+            &SearchDupeStashesConfig::default(),
+            Some(&area),
+            &(i32::MIN..=i32::MAX),
+            &[],
+        )
+        .map(Iterator::count)
+        .unwrap_or_default()
+    }
+
+    // Local position 15,64,7 in chunk -33,70 of region -2,2
+    #[test_case(CoordinateSpace::Block => (-513, Some(64), 1127); "Block")]
+    #[test_case(CoordinateSpace::Chunk => (-33, None, 70); "Chunk")]
+    #[test_case(CoordinateSpace::Region => (-2, None, 2); "Region")]
+    fn test_search_inventories_reports_absolute_position(
+        coords: CoordinateSpace,
+    ) -> (i32, Option<i32>, i32) {
+        let chunk = chunk_at(
+            -33,
+            70,
+            vec![BlockEntity {
+                x: -33 * 16 + 15,
+                y: 64,
+                z: 70 * 16 + 7,
+                ..block_entity("minecraft:chest", chest())
+            }],
+        );
+        let inventories = super::search_inventories_in_chunk(
+            chunk,
+            &SearchDupeStashesConfig::default(),
+            None,
+            &(i32::MIN..=i32::MAX),
+            &[],
+        )
+        .expect("Chunk is searched")
+        .collect::<Vec<_>>();
+        let [inventory] = &inventories[..] else {
+            panic!("Expected exactly one inventory");
+        };
+        let Location { x, y, z } = inventory.position.location(coords);
+        (x, y, z)
+    }
+
+    #[test_case(&[] => vec!["minecraft:chest", "minecraft:shulker_box"]; "All containers")]
+    #[test_case(&["minecraft:shulker_box"] => vec!["minecraft:shulker_box"]; "Shulker boxes")]
+    #[test_case(&["minecraft:barrel"] => Vec::<String>::new(); "No matching container")]
+    fn test_search_inventories_of_container_type(containers: &[&str]) -> Vec<String> {
+        let shulker_box = BlockEntityType::ShulkerBox(ShulkerBox {
+            color: None,
+            custom_name: None,
+            items: Some(List::from(vec![item(0)])),
+            lock: None,
+            loot_table: None,
+            loot_table_seed: None,
+        });
+        let chunk = chunk_at(
+            0,
+            0,
+            vec![
+                block_entity("minecraft:chest", chest()),
+                block_entity("minecraft:shulker_box", shulker_box),
+            ],
+        );
+        let containers = containers
+            .iter()
+            .map(|container| container.to_string())
+            .collect::<Vec<_>>();
+        let mut inventory_types = super::search_inventories_in_chunk(
+            chunk,
+            &SearchDupeStashesConfig::default(),
+            None,
+            &(i32::MIN..=i32::MAX),
+            &containers,
+        )
+        .expect("Chunk is searched")
+        .map(|inventory| inventory.inventory_type)
+        .collect::<Vec<_>>();
+        inventory_types.sort();
+        inventory_types
+    }
+
+    #[test_case(0..=10 => 1; "Inside of y range")]
+    #[test_case(2..=2 => 1; "At y range border")]
+    #[test_case(128..=320 => 0; "Below y range")]
+    #[test_case(-64..=1 => 0; "Above y range")]
+    fn test_search_inventories_in_y_range(y_range: std::ops::RangeInclusive<i32>) -> usize {
+        // The chest is at y 2
+        let chunk = chunk_at(0, 0, vec![block_entity("minecraft:chest", chest())]);
+        super::search_inventories_in_chunk(
+            chunk,
+            &SearchDupeStashesConfig::default(),
+            None,
+            &y_range,
+            &[],
+        )
+        .map(Iterator::count)
+        .unwrap_or_default()
+    }
+
+    /// A region file containing a single uncompressed chunk at 0,0.
+    fn region_with_single_chunk() -> Vec<u8> {
+        region_with_chunk(None)
+    }
+
+    /// A region file containing a single uncompressed chunk at 0,0.
+    /// With `chest_items` the chunk contains a chest at 0,0,0 holding the given item ids and counts.
+    fn region_with_chunk(chest_items: Option<&[(&str, i8)]>) -> Vec<u8> {
+        const BYTE_ID: u8 = 1;
+        const INT_ID: u8 = 3;
+        const LONG_ID: u8 = 4;
+        const STRING_ID: u8 = 8;
+        const LIST_ID: u8 = 9;
+        const COMPOUND_ID: u8 = 10;
+        fn push_str(data: &mut Vec<u8>, string: &str) {
+            data.extend((string.len() as u16).to_be_bytes());
+            data.extend(string.as_bytes());
+        }
+        let mut nbt = vec![COMPOUND_ID, 0, 0];
+        for key in ["DataVersion", "xPos", "yPos", "zPos"] {
+            nbt.push(INT_ID);
+            push_str(&mut nbt, key);
+            nbt.extend(0_i32.to_be_bytes());
+        }
+        nbt.push(STRING_ID);
+        push_str(&mut nbt, "Status");
+        push_str(&mut nbt, "full");
+        nbt.push(LONG_ID);
+        push_str(&mut nbt, "LastUpdate");
+        nbt.extend(0_i64.to_be_bytes());
+        nbt.push(LIST_ID);
+        push_str(&mut nbt, "sections");
+        nbt.push(COMPOUND_ID);
+        nbt.extend(0_i32.to_be_bytes());
+        if let Some(chest_items) = chest_items {
+            nbt.push(LIST_ID);
+            push_str(&mut nbt, "block_entities");
+            nbt.push(COMPOUND_ID);
+            nbt.extend(1_i32.to_be_bytes());
+            nbt.push(STRING_ID);
+            push_str(&mut nbt, "id");
+            push_str(&mut nbt, "minecraft:chest");
+            for key in ["x", "y", "z"] {
+                nbt.push(INT_ID);
+                push_str(&mut nbt, key);
+                nbt.extend(0_i32.to_be_bytes());
+            }
+            nbt.push(LIST_ID);
+            push_str(&mut nbt, "Items");
+            nbt.push(COMPOUND_ID);
+            nbt.extend((chest_items.len() as i32).to_be_bytes());
+            for (slot, (id, count)) in chest_items.iter().enumerate() {
+                nbt.push(BYTE_ID);
+                push_str(&mut nbt, "Slot");
+                nbt.push(slot as u8);
+                nbt.push(STRING_ID);
+                push_str(&mut nbt, "id");
+                push_str(&mut nbt, id);
+                nbt.push(BYTE_ID);
+                push_str(&mut nbt, "Count");
+                nbt.extend(count.to_be_bytes());
+                nbt.push(0);
+            }
+            nbt.push(0);
+        }
+        nbt.push(0);
+
+        // Two header sectors followed by the chunk in the third sector
+        let mut region = vec![0; 3 * 4096];
+        region[0..4].copy_from_slice(&[0, 0, 2, 1]);
+        let chunk_start = 2 * 4096;
+        region[chunk_start..chunk_start + 4].copy_from_slice(&(nbt.len() as u32 + 1).to_be_bytes());
+        // Uncompressed
+        region[chunk_start + 4] = 3;
+        region[chunk_start + 5..chunk_start + 5 + nbt.len()].copy_from_slice(&nbt);
+        region
+    }
+
+    #[test]
+    fn test_load_region_chunks_records_profile() {
+        let path = std::env::temp_dir().join(format!(
+            "mc-map-tools-profile-{}-r.0.0.mca",
+            std::process::id()
+        ));
+        std::fs::write(&path, region_with_single_chunk()).expect("Error writing region file");
+        let profile = super::profile::Profile::default();
+        let chunks = async_std::task::block_on(super::load_region_chunks(&path, Some(&profile)));
+        std::fs::remove_file(&path).expect("Error removing region file");
+
+        assert_eq!(chunks.expect("Region is valid").len(), 1);
+        let [region] = &profile.slowest(10)[..] else {
+            panic!("Expected exactly one region");
+        };
+        assert_eq!(region.region, path.display().to_string());
+        assert!(region.read > Duration::ZERO);
+        assert!(region.total() > Duration::ZERO);
+    }
+
+    #[test]
+    fn test_count_items_skips_corrupt_region() {
+        let region_dir =
+            std::env::temp_dir().join(format!("mc-map-tools-errors-{}", std::process::id()));
+        std::fs::create_dir_all(&region_dir).expect("Error creating region directory");
+        std::fs::write(
+            region_dir.join("r.0.0.mca"),
+            region_with_chunk(Some(&[("minecraft:diamond", 64), ("minecraft:diamond", 3)])),
+        )
+        .expect("Error writing region file");
+        // Shorter than the region header
+        std::fs::write(region_dir.join("r.1.0.mca"), [0; 100]).expect("Error writing region file");
+        let region_files = mc_map_reader::files::get_regions_of_region_dir(&region_dir)
+            .expect("Error reading region directory");
+        let errors = super::error_report::ErrorReport::new(false);
+        let item_counts = async_std::task::block_on(super::count_items(
+            &region_files,
+            &SearchDupeStashesConfig::default(),
+            None,
+            &(i32::MIN..=i32::MAX),
+            &[],
+            &errors,
+        ));
+        std::fs::remove_dir_all(&region_dir).expect("Error removing region directory");
+
+        assert_eq!(
+            item_counts,
+            vec![ItemCount {
+                id: "minecraft:diamond".to_string(),
+                count: 67,
+            }]
+        );
+        assert_eq!(errors.len(), 1);
+        let error = errors.first().expect("Error is recorded");
+        assert_eq!(error.kind, "io");
+        assert!(error.region.ends_with("r.1.0.mca"));
+    }
+
+    #[test_case("10" => 1; "Threshold below the count")]
+    #[test_case("63" => 1; "Threshold one below the count")]
+    #[test_case("64" => 0; "Threshold equal to the count")]
+    fn test_set_threshold(threshold: &str) -> usize {
+        use clap::Parser;
+
+        let world_dir = std::env::temp_dir().join(format!(
+            "mc-map-tools-set-threshold-{}-{threshold}",
+            std::process::id()
+        ));
+        let region_dir = world_dir.join("region");
+        std::fs::create_dir_all(&region_dir).expect("Error creating region directory");
+        std::fs::write(
+            region_dir.join("r.0.0.mca"),
+            region_with_chunk(Some(&[("minecraft:diamond", 64)])),
+        )
+        .expect("Error writing region file");
+        let config = crate::config::Config::new(
+            r#"{"search_dupe_stashes": {
+                "groups": {"diamond": {"items": [{"id": "minecraft:diamond"}], "threshold": 100}}
+            }}"#
+            .as_bytes(),
+        )
+        .expect("Invalid config")
+        .set("search_dupe_stashes.groups.diamond.threshold", threshold)
+        .expect("Valid override");
+        let data = super::args::SearchDupeStashes::parse_from([
+            "search-dupe-stashes",
+            "--output-format",
+            "json",
+        ]);
+        let mut output = Vec::new();
+        async_std::task::block_on(super::main(&world_dir, data, config, &mut output));
+        std::fs::remove_dir_all(&world_dir).expect("Error removing world directory");
+
+        let findings: Vec<serde_json::Value> =
+            serde_json::from_slice(&output).expect("Output is valid JSON");
+        findings.len()
+    }
+
+    #[test]
+    fn test_count_items_stops_after_abort() {
+        let region_dir =
+            std::env::temp_dir().join(format!("mc-map-tools-abort-{}", std::process::id()));
+        std::fs::create_dir_all(&region_dir).expect("Error creating region directory");
+        std::fs::write(
+            region_dir.join("r.0.0.mca"),
+            region_with_chunk(Some(&[("minecraft:diamond", 64)])),
+        )
+        .expect("Error writing region file");
+        let region_files = mc_map_reader::files::get_regions_of_region_dir(&region_dir)
+            .expect("Error reading region directory");
+        let errors = super::error_report::ErrorReport::new(true);
+        errors.record(
+            &region_dir.join("r.1.0.mca"),
+            &super::Error::Io(std::io::ErrorKind::UnexpectedEof.into()),
+        );
+        let item_counts = async_std::task::block_on(super::count_items(
+            &region_files,
+            &SearchDupeStashesConfig::default(),
+            None,
+            &(i32::MIN..=i32::MAX),
+            &[],
+            &errors,
+        ));
+        std::fs::remove_dir_all(&region_dir).expect("Error removing region directory");
+
+        assert_eq!(item_counts, vec![], "No region file is read after an abort");
+        assert_eq!(errors.len(), 1);
+    }
+
+    #[test]
+    fn test_item_histogram() {
+        let shulker_box = ItemWithSlot {
+            slot: 2,
+            item: Item {
+                id: "minecraft:shulker_box".to_string(),
+                tag: Some(HashMap::from([(
+                    "BlockEntityTag".to_string(),
+                    Tag::Compound(HashMap::from([(
+                        "Items".to_string(),
+                        Tag::List(List::from(vec![Tag::Compound(HashMap::from([
+                            ("Slot".to_string(), Tag::Byte(0)),
+                            (
+                                "id".to_string(),
+                                Tag::String("minecraft:diamond".to_string()),
+                            ),
+                            ("Count".to_string(), Tag::Byte(32)),
+                        ]))])),
+                    )])),
+                )])),
+                components: None,
+                count: 1,
+            },
+        };
+        let chest = |items: Vec<ItemWithSlot>| block_entity("minecraft:chest", chest_with(items));
+        let chunks = [
+            chunk_at(
+                0,
+                0,
+                vec![
+                    chest(vec![
+                        stack("minecraft:diamond", 64),
+                        stack("minecraft:dirt", 10),
+                        shulker_box,
+                    ]),
+                    chest(vec![stack("minecraft:dirt", 20)]),
+                ],
+            ),
+            chunk_at(1, 0, vec![chest(vec![stack("minecraft:diamond", 64)])]),
+            // Excluded by the config
+            chunk_at(5, 0, vec![chest(vec![stack("minecraft:gold_ingot", 64)])]),
+        ];
+        let config = SearchDupeStashesConfig {
+            exclude: vec![Area::chunk(5, 0)],
+            ..SearchDupeStashesConfig::default()
+        };
+        let histogram = chunks.iter().fold(HashMap::new(), |mut histogram, chunk| {
+            super::count_items_in_chunk(
+                chunk,
+                &config,
+                None,
+                &(i32::MIN..=i32::MAX),
+                &[],
+                &mut histogram,
+            );
+            histogram
+        });
+        assert_eq!(
+            super::sorted_item_counts(histogram),
+            vec![
+                ItemCount {
+                    id: "minecraft:diamond".to_string(),
+                    count: 64 + 32 + 64
+                },
+                ItemCount {
+                    id: "minecraft:dirt".to_string(),
+                    count: 30
+                },
+                ItemCount {
+                    id: "minecraft:shulker_box".to_string(),
+                    count: 1
+                },
+            ]
+        );
+    }
+
+    #[test_case(block_entity("minecraft:chest", chest_with(vec![item(0)])) => "minecraft:chest"; "Chest")]
+    #[test_case(block_entity("minecraft:trapped_chest", BlockEntityType::TrappedChest(TrappedChest {
+        custom_name: None,
+        items: Some(List::from(vec![item(0)])),
+        lock: None,
+        loot_table: None,
+        loot_table_seed: None,
+    })) => "minecraft:trapped_chest"; "Trapped chest")]
+    fn test_search_inventory_block_reports_id(block_entity: BlockEntity) -> String {
+        let config = SearchDupeStashesConfig::default();
+        super::search_inventory_block(
+            block_entity
+                .as_container()
+                .expect("Block entity is a container"),
+            &block_entity,
+            &config,
+        )
+        .expect("Container contains items")
+        .inventory_type
+    }
+
+    #[test]
+    fn test_into_finding() {
+        let config = SearchDupeStashesConfig {
+            groups: HashMap::from([(
+                "diamonds".to_string(),
+                Group {
+                    items: Vec::new(),
+                    threshold: 500,
+                },
+            )]),
+            exclude: Vec::new(),
+            max_stack_sizes: HashMap::new(),
+            exclude_items: Vec::new(),
+        };
+        let group_hash_lookup_table = HashMap::from([(42, "diamonds")]);
+        let finding = super::into_finding(
+            Position {
+                x: 600,
+                y: 64,
+                z: -20,
+            },
+            "minecraft:chest".to_string(),
+            HashMap::from([(42, 1000)]),
+            CoordinateSpace::Chunk,
+            &group_hash_lookup_table,
+            &config,
+        );
+        assert_eq!(
+            finding,
+            Some(Finding {
+                world: None,
+                location: Location {
+                    x: 37,
+                    y: None,
+                    z: -2
+                },
+                container: "minecraft:chest".to_string(),
+                groups: vec![GroupCount {
+                    group: "diamonds".to_string(),
+                    count: 1000,
+                    threshold: 500,
+                }],
+                stacks: Vec::new(),
+            })
+        );
+    }
+
+    #[test_case((5, 5), 10 => vec![(0, 0), (10, 12)]; "Near two findings")]
+    #[test_case((500, -500), 0 => vec![(500, -500)]; "Exact location")]
+    #[test_case((5, 5), 4 => Vec::<(i32, i32)>::new(); "Nothing near")]
+    fn test_findings_near(location: (i32, i32), radius: u32) -> Vec<(i32, i32)> {
+        let finding = |x: i32, z: i32| Finding {
+            world: None,
+            location: Location { x, y: None, z },
+            container: "minecraft:chest".to_string(),
+            groups: vec![],
+            stacks: Vec::new(),
+        };
+        let results = ScanResults(vec![
+            finding(10, 12),
+            finding(500, -500),
+            finding(0, 0),
+            finding(-40, 5),
+        ]);
+        super::findings_near(&results, location, radius)
+            .into_iter()
+            .map(|finding| (finding.location.x, finding.location.z))
+            .collect()
+    }
+
+    #[test]
+    fn test_tag_findings_with_world() {
+        let finding = |x: i32| {
+            (
+                (x, 0),
+                Finding {
+                    world: None,
+                    location: Location { x, y: None, z: 0 },
+                    container: "minecraft:chest".to_string(),
+                    groups: vec![],
+                    stacks: Vec::new(),
+                },
+            )
+        };
+        let findings = super::tag_findings_with_world(vec![finding(1)], "survival")
+            .chain(super::tag_findings_with_world(
+                vec![finding(2), finding(3)],
+                "creative",
+            ))
+            .map(|(_, finding)| (finding.world, finding.location.x))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            findings,
+            vec![
+                (Some("survival".to_string()), 1),
+                (Some("creative".to_string()), 2),
+                (Some("creative".to_string()), 3),
+            ]
+        );
+    }
+
+    #[test_case("/srv/minecraft/survival" => "survival"; "Absolute path")]
+    #[test_case("saves/creative/" => "creative"; "Trailing slash")]
+    fn test_world_name(world_dir: &str) -> String {
+        super::world_name(std::path::Path::new(world_dir))
+    }
+
+    #[test]
+    fn test_into_finding_without_groups() {
+        let config = SearchDupeStashesConfig::default();
+        let finding = super::into_finding(
+            Position { x: 0, y: 0, z: 0 },
+            "minecraft:chest".to_string(),
+            HashMap::new(),
+            CoordinateSpace::Block,
+            &HashMap::new(),
+            &config,
+        );
+        assert_eq!(finding, None);
+    }
+
+    #[test]
+    fn test_modified_since() {
+        let region_dir = std::env::temp_dir().join(format!(
+            "mc-map-tools-modified-since-{}",
+            std::process::id()
+        ));
+        std::fs::create_dir_all(&region_dir).expect("Error creating region directory");
+        let now = SystemTime::now();
+        let day = Duration::from_secs(24 * 60 * 60);
+        for (file_name, modified) in [("r.0.0.mca", now - 10 * day), ("r.1.0.mca", now)] {
+            std::fs::File::create(region_dir.join(file_name))
+                .and_then(|file| file.set_modified(modified))
+                .expect("Error creating region file");
+        }
+        let region_files = mc_map_reader::files::get_regions_of_region_dir(&region_dir)
+            .expect("Error reading region directory");
+        let regions = super::modified_since(region_files, now - day)
+            .iter()
+            .map(|region| (region.x(), region.z()))
+            .collect::<Vec<_>>();
+        std::fs::remove_dir_all(&region_dir).expect("Error removing region directory");
+        assert_eq!(regions, vec![(1, 0)]);
+    }
+
+    #[test_case(None => vec![(-1, 2), (0, 0), (3, -4)]; "Entire world")]
+    #[test_case(Some(Area::new(0, 0, 10, 10)) => vec![(0, 0)]; "Area")]
+    #[test_case(Some(Area::chunk(-20, 70)) => vec![(-1, 2)]; "Single chunk in negative region")]
+    #[test_case(Some(Area::new(-1, 0, 0, 0)) => vec![(0, 0)]; "Narrow area crossing region border")]
+    fn test_find_region_files(area: Option<Area>) -> Vec<(i32, i32)> {
+        let world_dir = std::env::temp_dir().join(format!(
+            "mc-map-tools-find-region-files-{}-{}",
+            std::process::id(),
+            area.as_ref().map_or(String::from("all"), |area| format!(
+                "{}_{}_{}_{}",
+                area.x1, area.z1, area.x2, area.z2
+            ))
+        ));
+        let region_dir = world_dir.join("region");
+        std::fs::create_dir_all(&region_dir).expect("Error creating region directory");
+        for file_name in ["r.0.0.mca", "r.-1.2.mca", "r.3.-4.mca"] {
+            std::fs::File::create(region_dir.join(file_name)).expect("Error creating region file");
+        }
+        let mut regions = super::find_region_files(&world_dir, None, area.as_ref())
+            .iter()
+            .map(|region| (region.x(), region.z()))
+            .collect::<Vec<_>>();
+        std::fs::remove_dir_all(&world_dir).expect("Error removing world directory");
+        regions.sort();
+        regions
+    }
+
+    #[test]
+    fn test_count_chunks_reads_only_header() {
+        struct CountingReader<'a> {
+            data: &'a [u8],
+            bytes_read: &'a mut usize,
+        }
+        impl std::io::Read for CountingReader<'_> {
+            fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+                let read = self.data.read(buf)?;
+                *self.bytes_read += read;
+                Ok(read)
+            }
+        }
+
+        let mut data = vec![0; 8192 + 4096];
+        // Chunks 0,0 and 1,0 of region -1,0 stored in the third sector
+        data[0..4].copy_from_slice(&[0, 0, 2, 1]);
+        data[4..8].copy_from_slice(&[0, 0, 2, 1]);
+        let config = SearchDupeStashesConfig {
+            groups: HashMap::new(),
+            exclude: vec![Area::chunk(-32, 0)],
+            max_stack_sizes: HashMap::new(),
+            exclude_items: Vec::new(),
+        };
+        let mut bytes_read = 0;
+        let chunks = super::count_chunks(
+            CountingReader {
+                data: &data,
+                bytes_read: &mut bytes_read,
+            },
+            -1,
+            0,
+            &config,
+            None,
+        )
+        .expect("Header is valid");
+        assert_eq!(chunks, 1);
+        assert_eq!(bytes_read, 8192);
+    }
+}
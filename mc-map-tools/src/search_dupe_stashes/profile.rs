@@ -0,0 +1,98 @@
+use std::{fmt::Display, io::Write, sync::Mutex, time::Duration};
+
+/// Time spent loading a single region file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RegionProfile {
+    pub region: String,
+    /// Time spent reading the file
+    pub read: Duration,
+    /// Time spent decompressing all chunks
+    pub decompress: Duration,
+    /// Time spent parsing the NBT data of all chunks
+    pub parse: Duration,
+}
+
+impl RegionProfile {
+    pub fn total(&self) -> Duration {
+        self.read + self.decompress + self.parse
+    }
+}
+
+impl Display for RegionProfile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}: {:?} (read {:?}, decompress {:?}, parse {:?})",
+            self.region,
+            self.total(),
+            self.read,
+            self.decompress,
+            self.parse
+        )
+    }
+}
+
+/// Collects the load times of all region files of a scan.
+#[derive(Debug, Default)]
+pub struct Profile(Mutex<Vec<RegionProfile>>);
+
+impl Profile {
+    pub fn record(&self, region: RegionProfile) {
+        self.0.lock().expect("Profile lock poisoned").push(region);
+    }
+
+    /// The `count` region files that took the longest to load, slowest first.
+    pub fn slowest(&self, count: usize) -> Vec<RegionProfile> {
+        let mut regions = self.0.lock().expect("Profile lock poisoned").clone();
+        regions.sort_by(|a, b| b.total().cmp(&a.total()));
+        regions.truncate(count);
+        regions
+    }
+}
+
+/// Print one line per region file.
+pub fn write_profile(regions: &[RegionProfile], writer: &mut dyn Write) -> std::io::Result<()> {
+    writeln!(writer, "Slowest region files:")?;
+    regions
+        .iter()
+        .try_for_each(|region| writeln!(writer, "  {region}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{Profile, RegionProfile};
+
+    fn region(name: &str, millis: u64) -> RegionProfile {
+        RegionProfile {
+            region: name.to_string(),
+            read: Duration::from_millis(millis),
+            decompress: Duration::from_millis(2 * millis),
+            parse: Duration::from_millis(3 * millis),
+        }
+    }
+
+    #[test]
+    fn test_slowest() {
+        let profile = Profile::default();
+        profile.record(region("r.0.0.mca", 1));
+        profile.record(region("r.1.0.mca", 10));
+        profile.record(region("r.0.1.mca", 5));
+        assert_eq!(
+            profile.slowest(2),
+            vec![region("r.1.0.mca", 10), region("r.0.1.mca", 5)]
+        );
+    }
+
+    #[test]
+    fn test_write_profile() {
+        let mut output = Vec::new();
+        super::write_profile(&[region("r.1.0.mca", 10)], &mut output)
+            .expect("Error writing profile");
+        assert_eq!(
+            String::from_utf8(output).expect("Output is valid UTF-8"),
+            "Slowest region files:\n  r.1.0.mca: 60ms (read 10ms, decompress 20ms, parse 30ms)\n"
+        );
+    }
+}
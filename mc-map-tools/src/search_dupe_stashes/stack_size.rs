@@ -0,0 +1,123 @@
+/// Items that can be stacked up to 16.
+const STACKS_OF_16: &[&str] = &[
+    "minecraft:ender_pearl",
+    "minecraft:snowball",
+    "minecraft:egg",
+    "minecraft:blue_egg",
+    "minecraft:brown_egg",
+    "minecraft:bucket",
+    "minecraft:armor_stand",
+    "minecraft:honey_bottle",
+    "minecraft:written_book",
+];
+
+/// Suffixes of item ids that can be stacked up to 16, e.g. all wood types of signs.
+const STACKS_OF_16_SUFFIXES: &[&str] = &["_sign", "_banner"];
+
+/// Items that can not be stacked.
+const UNSTACKABLE: &[&str] = &[
+    "minecraft:bow",
+    "minecraft:crossbow",
+    "minecraft:trident",
+    "minecraft:mace",
+    "minecraft:shield",
+    "minecraft:elytra",
+    "minecraft:fishing_rod",
+    "minecraft:carrot_on_a_stick",
+    "minecraft:warped_fungus_on_a_stick",
+    "minecraft:flint_and_steel",
+    "minecraft:shears",
+    "minecraft:brush",
+    "minecraft:spyglass",
+    "minecraft:totem_of_undying",
+    "minecraft:enchanted_book",
+    "minecraft:writable_book",
+    "minecraft:knowledge_book",
+    "minecraft:potion",
+    "minecraft:splash_potion",
+    "minecraft:lingering_potion",
+    "minecraft:ominous_bottle",
+    "minecraft:milk_bucket",
+    "minecraft:water_bucket",
+    "minecraft:lava_bucket",
+    "minecraft:powder_snow_bucket",
+    "minecraft:axolotl_bucket",
+    "minecraft:cod_bucket",
+    "minecraft:salmon_bucket",
+    "minecraft:pufferfish_bucket",
+    "minecraft:tropical_fish_bucket",
+    "minecraft:tadpole_bucket",
+    "minecraft:mushroom_stew",
+    "minecraft:rabbit_stew",
+    "minecraft:beetroot_soup",
+    "minecraft:suspicious_stew",
+    "minecraft:saddle",
+    "minecraft:cake",
+    "minecraft:debug_stick",
+];
+
+/// Suffixes of item ids that can not be stacked, e.g. tools, armor and shulker boxes.
+const UNSTACKABLE_SUFFIXES: &[&str] = &[
+    "_sword",
+    "_pickaxe",
+    "_axe",
+    "_shovel",
+    "_hoe",
+    "_helmet",
+    "_chestplate",
+    "_leggings",
+    "_boots",
+    "_horse_armor",
+    "shulker_box",
+    "_bed",
+    "_boat",
+    "_raft",
+    "_minecart",
+    "_bundle",
+];
+
+/// The default stack size of most items.
+const DEFAULT_MAX_STACK_SIZE: i8 = 64;
+
+/// The maximum stack size of an item in vanilla Minecraft.
+/// Items that are not known to have a smaller stack size can be stacked up to 64.
+pub fn vanilla_max_stack_size(id: &str) -> i8 {
+    let is_unstackable = UNSTACKABLE.contains(&id)
+        || id == "minecraft:minecart"
+        || id == "minecraft:bundle"
+        || id.starts_with("minecraft:music_disc_")
+        || UNSTACKABLE_SUFFIXES
+            .iter()
+            .any(|suffix| id.ends_with(suffix));
+    if is_unstackable {
+        return 1;
+    }
+    let stacks_of_16 = STACKS_OF_16.contains(&id)
+        || STACKS_OF_16_SUFFIXES
+            .iter()
+            .any(|suffix| id.ends_with(suffix));
+    if stacks_of_16 {
+        return 16;
+    }
+    DEFAULT_MAX_STACK_SIZE
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::vanilla_max_stack_size;
+
+    #[test_case("minecraft:diamond" => 64; "Default")]
+    #[test_case("minecraft:ender_pearl" => 16; "Ender pearl")]
+    #[test_case("minecraft:oak_sign" => 16; "Sign")]
+    #[test_case("minecraft:diamond_sword" => 1; "Sword")]
+    #[test_case("minecraft:netherite_chestplate" => 1; "Armor")]
+    #[test_case("minecraft:shulker_box" => 1; "Undyed shulker box")]
+    #[test_case("minecraft:red_shulker_box" => 1; "Dyed shulker box")]
+    #[test_case("minecraft:music_disc_cat" => 1; "Music disc")]
+    #[test_case("minecraft:totem_of_undying" => 1; "Totem")]
+    fn test_vanilla_max_stack_size(id: &str) -> i8 {
+        vanilla_max_stack_size(id)
+    }
+}
@@ -0,0 +1,97 @@
+use std::{collections::BTreeMap, io::Write};
+
+use super::args::Area;
+
+/// Width and height of a single chunk in pixels.
+const CHUNK_SIZE: i64 = 8;
+/// Opacity of the chunk with the fewest suspicious items.
+/// The chunk with the most items is fully opaque.
+const MIN_OPACITY: f64 = 0.2;
+
+/// Write a map of all chunks containing findings as SVG.
+/// Each chunk is drawn as a red square whose opacity is scaled by the number of suspicious items in it.
+/// The map covers `area` or, if no area is given, the smallest area containing all chunks.
+pub fn write_svg(
+    chunks: &BTreeMap<(i32, i32), u64>,
+    area: Option<&Area>,
+    writer: &mut dyn Write,
+) -> std::io::Result<()> {
+    let (min_x, min_z, max_x, max_z) = match area {
+        Some(area) => (area.x1, area.z1, area.x2, area.z2),
+        None => chunks.keys().fold(
+            (i32::MAX, i32::MAX, i32::MIN, i32::MIN),
+            |(min_x, min_z, max_x, max_z), (x, z)| {
+                (min_x.min(*x), min_z.min(*z), max_x.max(*x), max_z.max(*z))
+            },
+        ),
+    };
+    let (width, height) = if chunks.is_empty() && area.is_none() {
+        (0, 0)
+    } else {
+        (
+            (i64::from(max_x) - i64::from(min_x) + 1) * CHUNK_SIZE,
+            (i64::from(max_z) - i64::from(min_z) + 1) * CHUNK_SIZE,
+        )
+    };
+    let max_count = chunks.values().copied().max().unwrap_or_default().max(1);
+
+    writeln!(
+        writer,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}">"#
+    )?;
+    writeln!(
+        writer,
+        r#"  <rect width="{width}" height="{height}" fill="white"/>"#
+    )?;
+    for ((x, z), count) in chunks {
+        let opacity = MIN_OPACITY + (1.0 - MIN_OPACITY) * (*count as f64 / max_count as f64);
+        writeln!(
+            writer,
+            r#"  <rect class="finding" x="{}" y="{}" width="{CHUNK_SIZE}" height="{CHUNK_SIZE}" fill="red" fill-opacity="{opacity:.2}"><title>{x},{z}: {count}</title></rect>"#,
+            (i64::from(*x) - i64::from(min_x)) * CHUNK_SIZE,
+            (i64::from(*z) - i64::from(min_z)) * CHUNK_SIZE,
+        )?;
+    }
+    writeln!(writer, "</svg>")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::write_svg;
+    use crate::search_dupe_stashes::args::Area;
+
+    fn svg(chunks: &BTreeMap<(i32, i32), u64>, area: Option<&Area>) -> String {
+        let mut output = Vec::new();
+        write_svg(chunks, area, &mut output).expect("Error writing svg");
+        String::from_utf8(output).expect("Svg is not valid utf-8")
+    }
+
+    #[test]
+    fn test_write_svg_contains_one_element_per_chunk() {
+        let chunks = BTreeMap::from([((-2, 3), 100), ((0, 0), 50), ((5, -1), 1000)]);
+        let svg = svg(&chunks, None);
+        assert_eq!(svg.matches(r#"class="finding""#).count(), 3);
+        assert!(svg.contains(r#"width="64" height="40""#));
+        assert!(svg.contains(
+            r#"<rect class="finding" x="56" y="0" width="8" height="8" fill="red" fill-opacity="1.00">"#
+        ));
+    }
+
+    #[test]
+    fn test_write_svg_uses_area() {
+        let chunks = BTreeMap::from([((0, 0), 10)]);
+        let area = Area::new(-10, -10, 9, 9);
+        let svg = svg(&chunks, Some(&area));
+        assert!(svg.contains(r#"width="160" height="160""#));
+        assert!(svg.contains(r#"<rect class="finding" x="80" y="80""#));
+    }
+
+    #[test]
+    fn test_write_svg_without_findings() {
+        let svg = svg(&BTreeMap::new(), None);
+        assert_eq!(svg.matches(r#"class="finding""#).count(), 0);
+        assert!(svg.contains(r#"width="0" height="0""#));
+    }
+}
@@ -0,0 +1,152 @@
+use std::{fmt::Display, ops::RangeInclusive, path::Path};
+
+use mc_map_reader::{
+    coords::BlockPos,
+    data::{block_entity::BlockEntity, chunk::ChunkData, item::Item},
+    data_version,
+};
+use serde::Serialize;
+
+use super::{args, config::SearchDupeStashesConfig};
+
+/// An item that was added in a newer release than the world was last saved with.
+/// Such items were most likely injected with an external tool.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct TooNewItem {
+    /// The name of the world the item is in. Only set if more than one world is searched.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub world: Option<String>,
+    pub id: String,
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+    /// The release that added the item
+    pub introduced: &'static str,
+}
+
+impl Display for TooNewItem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(world) = &self.world {
+            write!(f, "{world},")?;
+        }
+        write!(
+            f,
+            "{},{},{},{} added in {}",
+            self.x, self.y, self.z, self.id, self.introduced
+        )
+    }
+}
+
+/// Get the name of the release that added the item if it is newer than the world's data version.
+pub fn introduced_after(id: &str, world_data_version: i32) -> Option<&'static str> {
+    let introduced = data_version::item_introduced(id)?;
+    if introduced <= world_data_version {
+        return None;
+    }
+    data_version::version_name(introduced)
+}
+
+/// Read the data version of the level.dat of a save.
+/// Saves without a readable level.dat are skipped.
+pub fn world_data_version(save_directory: &Path) -> Option<i32> {
+    let level_dat = std::fs::read(save_directory.join("level.dat"))
+        .map_err(|err| err.to_string())
+        .and_then(|data| mc_map_reader::parse_level_dat(&data).map_err(|err| err.to_string()));
+    match level_dat {
+        Ok(level_dat) => Some(level_dat.data_version),
+        Err(err) => {
+            log::warn!(
+                "Could not check item versions of {}: {err}",
+                save_directory.display()
+            );
+            None
+        }
+    }
+}
+
+/// Find all items in the containers of the chunk that were added after the world's data version.
+/// Items inside of shulker boxes and bundles are checked as well.
+/// Containers are selected like in a search.
+pub fn too_new_items_in_chunk(
+    chunk: &ChunkData,
+    world_data_version: i32,
+    config: &SearchDupeStashesConfig,
+    area: Option<&args::Area>,
+    y_range: &RangeInclusive<i32>,
+    containers: &[String],
+) -> Vec<TooNewItem> {
+    if !super::is_chunk_searched(chunk.x_pos, chunk.z_pos, config, area) {
+        return Vec::new();
+    }
+    let Some(block_entities) = &chunk.block_entities else {
+        return Vec::new();
+    };
+    block_entities
+        .iter()
+        .filter(|block_entity| y_range.contains(&block_entity.y))
+        .filter(|block_entity| super::is_container_searched(&block_entity.id, containers))
+        .flat_map(|block_entity| {
+            let BlockPos { x, y, z } = block_entity.position();
+            container_items(block_entity)
+                .into_iter()
+                .filter(|item| !config.is_item_excluded(&item.id))
+                .filter_map(move |item| {
+                    introduced_after(&item.id, world_data_version).map(|introduced| TooNewItem {
+                        world: None,
+                        id: item.id,
+                        x,
+                        y,
+                        z,
+                        introduced,
+                    })
+                })
+        })
+        .collect()
+}
+
+/// All items of a container including the items nested inside of them.
+fn container_items(block_entity: &BlockEntity) -> Vec<Item> {
+    let Some(container) = block_entity.as_container() else {
+        return Vec::new();
+    };
+    if container.has_loot_table() {
+        return Vec::new();
+    }
+    container
+        .items()
+        .iter()
+        .flat_map(|item| {
+            let nested = super::nested_items(&item.item, 0);
+            std::iter::once(item.item.clone()).chain(nested)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::TooNewItem;
+
+    #[test_case("minecraft:mace", 3465 => Some("1.21"); "Added after world version")]
+    #[test_case("minecraft:mace", 3953 => None; "Added in world version")]
+    #[test_case("minecraft:mace", 4189 => None; "Added before world version")]
+    #[test_case("minecraft:diamond", 1343 => None; "Unknown item")]
+    fn test_introduced_after(id: &str, world_data_version: i32) -> Option<&'static str> {
+        super::introduced_after(id, world_data_version)
+    }
+
+    #[test_case(None => "1,-2,3,minecraft:mace added in 1.21"; "Single world")]
+    #[test_case(Some("world") => "world,1,-2,3,minecraft:mace added in 1.21"; "Tagged with world")]
+    fn test_too_new_item_display(world: Option<&str>) -> String {
+        TooNewItem {
+            world: world.map(str::to_string),
+            id: "minecraft:mace".to_string(),
+            x: 1,
+            y: -2,
+            z: 3,
+            introduced: "1.21",
+        }
+        .to_string()
+    }
+}